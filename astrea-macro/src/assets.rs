@@ -0,0 +1,100 @@
+//! `public/` directory scanning
+//!
+//! / `public/` 目录扫描
+
+use std::path::{Path, PathBuf};
+
+/// A file found in the `public/` directory
+///
+/// / 在 `public/` 目录中找到的文件
+pub struct PublicAsset {
+    /// URL path the file is served at (e.g., /css/app.css)
+    /// / 文件对应的 URL 路径（如 /css/app.css）
+    pub url_path: String,
+    /// Source file absolute path
+    /// / 源文件绝对路径
+    pub file_path: PathBuf,
+}
+
+impl PublicAsset {
+    /// A precompressed sibling (`app.css.br`), if present
+    /// / 预压缩的副本文件（`app.css.br`），如果存在
+    pub fn precompressed(&self, extension: &str) -> Option<PathBuf> {
+        let mut name = self.file_path.as_os_str().to_owned();
+        name.push(format!(".{extension}"));
+        let path = PathBuf::from(name);
+        path.is_file().then_some(path)
+    }
+
+    /// Whether the file name carries a content hash (`app.3f9a1c2b.js`, `index-BX7f2a1c.css`)
+    ///
+    /// / 文件名是否包含内容哈希（`app.3f9a1c2b.js`、`index-BX7f2a1c.css`）
+    ///
+    /// The last `.`- or `-`-separated part of the stem must be at least 8
+    /// alphanumeric characters including a digit.
+    ///
+    /// 文件名主干中最后一个以 `.` 或 `-` 分隔的部分必须是至少 8 个包含数字的字母数字字符。
+    pub fn is_hashed(&self) -> bool {
+        let name = self.url_path.rsplit('/').next().unwrap_or_default();
+        let Some((stem, _)) = name.rsplit_once('.') else {
+            return false;
+        };
+        let Some((_, hash)) = stem.rsplit_once(['.', '-']) else {
+            return false;
+        };
+        hash.len() >= 8
+            && hash.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+            && hash.bytes().any(|b| b.is_ascii_digit())
+    }
+}
+
+/// Strong `ETag` value of a file's contents, in the format of `ETag::from_body`
+///
+/// / 文件内容的强 `ETag` 值，格式与 `ETag::from_body` 相同
+pub fn content_hash(body: &[u8]) -> String {
+    let hash = body.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:x}-{hash:016x}", body.len())
+}
+
+/// Recursively list the files of a `public/` directory, sorted by URL path
+///
+/// / 递归列出 `public/` 目录中的文件，按 URL 路径排序
+///
+/// Precompressed `.br` / `.gz` siblings of another file are not listed on their
+/// own: they are alternative encodings of that file.
+///
+/// 其他文件的预压缩 `.br` / `.gz` 副本不会单独列出：它们是该文件的另一种编码。
+pub fn scan_public(dir: &Path) -> Vec<PublicAsset> {
+    let mut assets = Vec::new();
+    collect(dir, "", &mut assets);
+    assets.retain(|asset| {
+        let base = asset
+            .url_path
+            .strip_suffix(".br")
+            .or_else(|| asset.url_path.strip_suffix(".gz"));
+        base.is_none_or(|base| !dir.join(base.trim_start_matches('/')).is_file())
+    });
+    assets.sort_by(|a, b| a.url_path.cmp(&b.url_path));
+    assets
+}
+
+fn collect(dir: &Path, prefix: &str, assets: &mut Vec<PublicAsset>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        let url_path = format!("{prefix}/{name}");
+        if path.is_dir() {
+            collect(&path, &url_path, assets);
+        } else if path.is_file() {
+            assets.push(PublicAsset {
+                url_path,
+                file_path: path,
+            });
+        }
+    }
+}
@@ -6,23 +6,94 @@ mod openapi;
 mod router;
 mod scope;
 
-pub use scope::generate_scope_code;
+pub use scope::{ScopeCode, generate_scope_code};
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
 use quote::quote;
-use syn::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, Lit, LitStr, MetaNameValue, Token};
+
+/// Arguments of `generate_routes!`
+///
+/// / `generate_routes!` 的参数
+///
+/// `generate_routes!("src/routes", public = "public", embed = true)` — all are optional.
+///
+/// `generate_routes!("src/routes", public = "public", embed = true)` — 全部都是可选的。
+struct GenerateArgs {
+    /// Routes directory, relative to CARGO_MANIFEST_DIR
+    /// / 路由目录，相对于 CARGO_MANIFEST_DIR
+    routes_dir: String,
+    /// `public = "public"` — static file directory, relative to CARGO_MANIFEST_DIR
+    /// / 静态文件目录，相对于 CARGO_MANIFEST_DIR
+    public_dir: Option<String>,
+    /// `embed = true` — embed the public directory into the binary
+    /// / 将 public 目录嵌入二进制文件
+    embed: bool,
+}
+
+impl Parse for GenerateArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = GenerateArgs {
+            routes_dir: "src/routes".to_string(),
+            public_dir: None,
+            embed: false,
+        };
+        if input.peek(LitStr) {
+            args.routes_dir = input.parse::<LitStr>()?.value();
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let options = Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)?;
+        for option in options {
+            let Expr::Lit(ExprLit { lit, .. }) = &option.value else {
+                return Err(syn::Error::new_spanned(
+                    &option.value,
+                    "期望字面量 / expected a literal",
+                ));
+            };
+            match lit {
+                Lit::Str(value) if option.path.is_ident("public") => {
+                    args.public_dir = Some(value.value());
+                }
+                Lit::Bool(value) if option.path.is_ident("embed") => {
+                    args.embed = value.value;
+                }
+                _ if option.path.is_ident("public") => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "public 必须是字符串 / public must be a string",
+                    ));
+                }
+                _ if option.path.is_ident("embed") => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "embed 必须是布尔值 / embed must be a bool",
+                    ));
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &option.path,
+                        "未知的 generate_routes! 选项 / unknown generate_routes! option (expected `public` or `embed`)",
+                    ));
+                }
+            }
+        }
+        if args.embed && args.public_dir.is_none() {
+            return Err(input.error("embed 需要 public 目录 / embed requires a `public` directory"));
+        }
+        Ok(args)
+    }
+}
 
 /// Implementation of the `generate_routes!` procedural macro
 ///
 /// / `generate_routes!` 过程宏的实现
 pub fn impl_generate_routes(input: TokenStream) -> TokenStream {
-    let routes_dir_name = if input.is_empty() {
-        "src/routes".to_string()
-    } else {
-        let lit = syn::parse_macro_input!(input as syn::LitStr);
-        lit.value()
-    };
+    let args = syn::parse_macro_input!(input as GenerateArgs);
+    let routes_dir_name = args.routes_dir;
 
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
         .expect("CARGO_MANIFEST_DIR environment variable not set");
@@ -36,16 +107,26 @@ pub fn impl_generate_routes(input: TokenStream) -> TokenStream {
         return quote! { compile_error!(#msg); }.into();
     }
 
+    let public = match &args.public_dir {
+        Some(name) => {
+            let dir = std::path::PathBuf::from(&manifest_dir).join(name);
+            if !dir.is_dir() {
+                let msg = format!("astrea: public directory not found: {}", dir.display());
+                return quote! { compile_error!(#msg); }.into();
+            }
+            Some((name.clone(), crate::assets::scan_public(&dir)))
+        }
+        None => None,
+    };
+
     // Phase 1: Scan directories and build middleware scope tree
     // 阶段1: 扫描目录并构建中间件作用域树
     let root_scope = crate::scanner::scan_and_build_scope(&routes_dir, &[], &manifest_dir);
 
     // Collect info for TUI logging
     // 收集 TUI 日志信息
-    let route_detail_logs = crate::scanner::collect_route_detail_logs(&root_scope, &[]);
-    let mw_detail_logs = crate::scanner::collect_middleware_detail_logs(&root_scope, None);
+    let route_detail_logs = crate::scanner::collect_route_detail_logs(&root_scope);
     let route_count = route_detail_logs.len();
-    let mw_count = mw_detail_logs.len();
 
     // ── Build TUI route table data (compile-time) ──
     // ── 构建 TUI 路由表数据（编译时）──
@@ -54,55 +135,93 @@ pub fn impl_generate_routes(input: TokenStream) -> TokenStream {
         .map(|r| r.method.as_str())
         .collect();
     let route_paths: Vec<&str> = route_detail_logs.iter().map(|r| r.path.as_str()).collect();
-    let route_mw_chains: Vec<String> = route_detail_logs
-        .iter()
-        .map(|r| {
-            if r.middleware_chain.is_empty() {
-                "(none)".to_string()
-            } else {
-                r.middleware_chain.join(" → ")
-            }
-        })
-        .collect();
-    let route_mw_chain_refs: Vec<&str> = route_mw_chains.iter().map(|s| s.as_str()).collect();
 
-    // ── Build TUI middleware table data (compile-time static parts) ──
-    // ── 构建 TUI 中间件表数据（编译时静态部分）──
-    let mw_scope_paths: Vec<&str> = mw_detail_logs
-        .iter()
-        .map(|m| m.scope_path.as_str())
-        .collect();
-    let mw_parent_paths: Vec<String> = mw_detail_logs
-        .iter()
-        .map(|m| match &m.parent_path {
-            Some(p) => p.clone(),
-            None => String::new(),
-        })
-        .collect();
-    let mw_parent_path_refs: Vec<&str> = mw_parent_paths.iter().map(|s| s.as_str()).collect();
-
-    // Generate runtime mode probe code for each middleware scope
-    // 为每个中间件作用域生成运行时 mode 探测代码
-    let mw_mode_probes: Vec<proc_macro2::TokenStream> = mw_detail_logs
-        .iter()
-        .map(|m| {
-            let mod_ident = Ident::new(&m.module_name, Span::call_site());
+    // Phase 2: Generate module declarations, route/scope tables and router expression
+    // 阶段2: 生成模块声明、路由/作用域表和路由器表达式
+    let mut code = ScopeCode::default();
+    generate_scope_code(&root_scope, &manifest_dir, None, &mut code);
+    let mw_count = code.scope_defs.len();
+    let mod_decls = &code.mod_decls;
+    let openapi_regs = &code.openapi_regs;
+    let router_items = router::build_router_items(&code);
+    let (public_items, public_service) = match (&public, args.embed) {
+        (Some((_, assets)), true) => (
+            router::build_embedded_assets(assets),
+            quote! { ::astrea::static_files::EmbeddedDir::new(__PUBLIC_ASSETS) },
+        ),
+        (Some((name, _)), false) => {
+            // Prefer the directory that was scanned over one relative to the
+            // working directory; `PublicDir::locate` falls back at runtime
+            // 优先使用被扫描的目录而不是相对于工作目录的目录；`PublicDir::locate` 在运行时回退
+            let dir = std::path::Path::new(&manifest_dir)
+                .join(name)
+                .to_string_lossy()
+                .into_owned();
+            (
+                quote! {},
+                quote! { ::astrea::static_files::PublicDir::locate(#name, #dir) },
+            )
+        }
+        (None, _) => (quote! {}, quote! {}),
+    };
+    let router_expr = router::build_router_expr(&code, public.is_some().then_some(public_service));
+
+    // Public assets table (only when a public directory is configured)
+    // 公共资源表（仅当配置了 public 目录时）
+    let public_tui_section = match &public {
+        Some((public_name, assets)) => {
+            let asset_count = assets.len();
+            let asset_rows = assets.iter().map(|asset| {
+                let url_path = &asset.url_path;
+                // A GET route with the same path wins over the file
+                // 同路径的 GET 路由优先于文件
+                let shadowed = route_detail_logs
+                    .iter()
+                    .any(|r| r.method.eq_ignore_ascii_case("GET") && r.path == *url_path);
+                let mut notes = Vec::new();
+                if args.embed {
+                    notes.push("embedded");
+                    if asset.is_hashed() {
+                        notes.push("immutable");
+                    }
+                }
+                if shadowed {
+                    notes.push("shadowed by route");
+                }
+                let note = notes.join(", ");
+                quote! { asset_table.add_row(vec![#url_path, #note]); }
+            });
             quote! {
                 {
-                    let __probe = #mod_ident::middleware::<S>();
-                    if __probe.mode == ::astrea::middleware::MiddlewareMode::Override {
-                        "override"
-                    } else {
-                        "extend"
+                    let mut asset_table = Table::new();
+                    asset_table.load_preset(presets::UTF8_FULL);
+                    asset_table.set_content_arrangement(ContentArrangement::Dynamic);
+                    asset_table.force_no_tty(); // 禁用 ANSI 颜色代码
+
+                    // 公共资源标题 / Public Assets Title
+                    let mut asset_title_row = Row::new();
+                    asset_title_row.add_cell(
+                        Cell::new(concat!("📁 Public Assets (", #public_name, ")"))
+                            .add_attribute(Attribute::Bold)
+                            .set_alignment(CellAlignment::Center)
+                    );
+                    asset_table.add_row(asset_title_row);
+
+                    asset_table.set_header(vec![
+                        Cell::new("Path").add_attribute(Attribute::Bold),
+                        Cell::new("Note").add_attribute(Attribute::Bold),
+                    ]);
+                    #(#asset_rows)*
+
+                    for line in asset_table.to_string().lines() {
+                        ::astrea::tracing::info!("{}", line);
                     }
+                    ::astrea::tracing::info!("📁 {} public asset(s) served from {}", #asset_count, #public_name);
                 }
             }
-        })
-        .collect();
-
-    // Phase 2: Generate module declarations and router expression
-    // 阶段2: 生成模块声明和路由器表达式
-    let (mod_decls, router_expr, openapi_regs) = generate_scope_code(&root_scope, &manifest_dir);
+        }
+        None => quote! {},
+    };
 
     // OpenAPI registration section (only when openapi feature is enabled)
     // OpenAPI 注册部分（仅当启用 openapi feature 时）
@@ -148,9 +267,17 @@ pub fn impl_generate_routes(input: TokenStream) -> TokenStream {
     let expanded = quote! {
         #(#mod_decls)*
 
+        #router_items
+
+        #public_items
+
         /// Create a Router with all file-based routes and middleware
         /// / 创建包含所有文件路由和中间件的 Router
         pub fn create_router<S: Clone + Send + Sync + 'static>() -> ::astrea::axum::Router<S> {
+            // 每个作用域的 middleware() 只调用一次，启动表和路由器共用
+            // Each scope's middleware() is called once, for both the listing and the router
+            let __loaded = ::astrea::router::LoadedScopes::load(__SCOPES, &__middleware_for::<S>);
+
             // ── TUI Logging with comfy_table ──
             // ── 使用 comfy_table 进行 TUI 日志输出 ──
             {
@@ -182,15 +309,18 @@ pub fn impl_generate_routes(input: TokenStream) -> TokenStream {
                 // 数据准备 / Data Preparation
                 let __methods: &[&str] = &[#(#route_methods),*];
                 let __paths: &[&str] = &[#(#route_paths),*];
-                let __mw_chains: &[&str] = &[#(#route_mw_chain_refs),*];
 
                 // 填充路由数据 / Fill Route Data
+                // The chain is resolved at runtime because mode and priority
+                // come from each scope's middleware() function
+                // 中间件链在运行时解析，因为模式和优先级来自各作用域的 middleware() 函数
                 for __i in 0..#route_count {
-                    table.add_row(vec![
-                        __methods[__i],
-                        __paths[__i],
-                        __mw_chains[__i],
-                    ]);
+                    let __chain = __loaded
+                        .chain(__ROUTES, __SCOPES, __methods[__i], __paths[__i])
+                    .filter(|c| !c.is_empty())
+                    .map(|c| c.join(" → "))
+                    .unwrap_or_else(|| "(none)".to_string());
+                    table.add_row(vec![__methods[__i], __paths[__i], __chain.as_str()]);
                 }
 
                 // 打印路由表 / Print Routes
@@ -220,34 +350,39 @@ pub fn impl_generate_routes(input: TokenStream) -> TokenStream {
                     // 中间件表头 / Middleware Headers
                     mw_table.set_header(vec![
                         Cell::new("Scope").add_attribute(Attribute::Bold),
+                        Cell::new("Name").add_attribute(Attribute::Bold),
                         Cell::new("Mode").add_attribute(Attribute::Bold),
+                        Cell::new("Priority").add_attribute(Attribute::Bold),
                         Cell::new("Inherits").add_attribute(Attribute::Bold),
                     ]);
 
-                    let __scope_paths: &[&str] = &[#(#mw_scope_paths),*];
-                    let __parent_paths: &[&str] = &[#(#mw_parent_path_refs),*];
-                    let __modes: &[&str] = &[#(#mw_mode_probes),*];
-
-                    for __i in 0..#mw_count {
-                        let __mode_display = if __parent_paths[__i].is_empty() {
-                            "─"
-                        } else if __modes[__i] == "override" {
-                            "override"
-                        } else {
-                            "extend"
+                    for (__i, __scope) in __SCOPES.iter().enumerate() {
+                        let Some(__probe) = __loaded.get(__i) else {
+                            continue;
                         };
+                        let __is_override =
+                            __probe.mode == ::astrea::middleware::MiddlewareMode::Override;
 
-                        let __inherit_display = if __parent_paths[__i].is_empty() {
-                            "(root)".to_string()
-                        } else if __modes[__i] == "override" {
-                            "⚡ standalone".to_string()
-                        } else {
-                            format!("← {}", __parent_paths[__i])
+                        let __mode_display = match __scope.parent {
+                            None => "─",
+                            Some(_) if __is_override => "override",
+                            Some(_) => "extend",
                         };
 
+                        let __inherit_display = match __scope.parent {
+                            None => "(root)".to_string(),
+                            Some(_) if __is_override => "⚡ standalone".to_string(),
+                            Some(__p) => format!("← {}", __SCOPES[__p].path),
+                        };
+
+                        let __name_display = __probe.name.as_deref().unwrap_or("─").to_string();
+                        let __priority_display = __probe.priority.to_string();
+
                         mw_table.add_row(vec![
-                            __scope_paths[__i],
+                            __scope.path,
+                            __name_display.as_str(),
                             __mode_display,
+                            __priority_display.as_str(),
                             __inherit_display.as_str(),
                         ]);
                     }
@@ -259,6 +394,8 @@ pub fn impl_generate_routes(input: TokenStream) -> TokenStream {
                     }
                 }
 
+                #public_tui_section
+
                 ::astrea::tracing::info!("✅ {} route(s), {} middleware scope(s) loaded", #route_count, #mw_count);
                 ::astrea::tracing::info!("");
             }
@@ -2,112 +2,145 @@
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Ident;
 
-use crate::scanner::MiddlewareScope;
+use super::scope::ScopeCode;
+use crate::assets::{PublicAsset, content_hash};
 
-/// Build the router expression for a single scope
+/// Build the module-level route table, scope table and middleware loader
 ///
-/// / 为单个作用域构建路由器表达式
+/// / 构建模块级路由表、作用域表和中间件加载函数
 ///
-/// Handles four cases:
+/// Emits:
 ///
-/// 处理四种情况：
+/// 生成：
 ///
-/// 1. No middleware, no children → flat `Router::new().route(...)` (backward compatible)
-///    无中间件，无子级 → 扁平 `Router::new().route(...)`（向后兼容）
-/// 2. No middleware, has children → flat routes merged with child routers
-///    无中间件，有子级 → 扁平路由与子路由器合并
-/// 3. Has middleware, no children → routes wrapped by middleware
-///    有中间件，无子级 → 路由被中间件包裹
-/// 4. Has middleware, has children → extend/override grouping with middleware
-///    有中间件，有子级 → 叠加/覆盖分组加中间件
-pub fn build_router_expr(
-    scope: &MiddlewareScope,
-    route_regs: &[TokenStream],
-    child_blocks: &[TokenStream],
-) -> TokenStream {
-    let has_mw = scope.middleware.is_some();
-    let has_children = !child_blocks.is_empty();
+/// - `__ROUTES` / `__SCOPES` — static tables consumed by `astrea::router`
+///   供 `astrea::router` 使用的静态表
+/// - `__middleware_for::<S>(index)` — calls the `middleware()` of scope `index`
+///   调用第 `index` 个作用域的 `middleware()`
+/// - `pub fn middleware_chain(method, path)` — debugging query for tests
+///   用于测试调试的查询函数
+pub fn build_router_items(code: &ScopeCode) -> TokenStream {
+    let route_defs = &code.route_defs;
+    let scope_defs = &code.scope_defs;
 
-    match (has_mw, has_children) {
-        // Case 1: No middleware, no children — simple flat router (backward compatible)
-        // 情况1：无中间件，无子级 — 简单的扁平路由器（向后兼容）
-        (false, false) => {
-            quote! {
-                ::astrea::axum::Router::new()
-                    #(#route_regs)*
-            }
+    let loader_body = if code.mw_modules.is_empty() {
+        quote! {
+            let _ = index;
+            ::astrea::middleware::Middleware::new()
         }
-
-        // Case 2: No middleware, has children — flat routes + merge children
-        // 情况2：无中间件，有子级 — 扁平路由 + 合并子级
-        // (mode is irrelevant since parent has nothing to inherit)
-        // （模式无关紧要，因为父级没有要继承的中间件）
-        (false, true) => {
-            quote! {
-                {
-                    let mut __router = ::astrea::axum::Router::new()
-                        #(#route_regs)*;
-                    #(
-                        let (_, __child) = #child_blocks;
-                        __router = __router.merge(__child);
-                    )*
-                    __router
-                }
+    } else {
+        let indices = 0..code.mw_modules.len();
+        let modules = &code.mw_modules;
+        quote! {
+            match index {
+                #(#indices => #modules::middleware::<S>(),)*
+                _ => ::astrea::middleware::Middleware::new(),
             }
         }
+    };
 
-        // Case 3: Has middleware, no children — routes wrapped by middleware
-        // 情况3：有中间件，无子级 — 路由被中间件包裹
-        (true, false) => {
-            let mw_mod = Ident::new(
-                &scope.middleware.as_ref().unwrap().module_name,
-                proc_macro2::Span::call_site(),
-            );
-            quote! {
-                {
-                    let __routes = ::astrea::axum::Router::new()
-                        #(#route_regs)*;
-                    let __scope_mw = #mw_mod::middleware::<S>();
-                    __scope_mw.apply(__routes)
-                }
-            }
+    quote! {
+        const __ROUTES: &[::astrea::router::RouteDef] = &[#(#route_defs),*];
+        const __SCOPES: &[::astrea::router::ScopeDef] = &[#(#scope_defs),*];
+
+        fn __middleware_for<S: Clone + Send + Sync + 'static>(
+            index: usize,
+        ) -> ::astrea::middleware::Middleware<S> {
+            #loader_body
         }
 
-        // Case 4: Has middleware + children — proximity principle with extend/override
-        // 情况4：有中间件 + 子级 — 就近原则，叠加/覆盖分组
-        //
-        // Extend children: wrapped by this scope's middleware (stacking/叠加)
-        // Override children: NOT wrapped, only their own middleware applies (覆盖)
-        //
-        // 叠加子级：被此作用域中间件包裹
-        // 覆盖子级：不被包裹，仅自身中间件生效
-        (true, true) => {
-            let mw_mod = Ident::new(
-                &scope.middleware.as_ref().unwrap().module_name,
-                proc_macro2::Span::call_site(),
-            );
-            quote! {
-                {
-                    let __direct = ::astrea::axum::Router::new()
-                        #(#route_regs)*;
-                    let mut __extend = __direct;
-                    let mut __override_group = ::astrea::axum::Router::new();
+        /// Get the middleware chain applied to a route, outermost first
+        /// / 获取作用于某个路由的中间件链，最外层在前
+        ///
+        /// `path` may be a route pattern (`/users/{id}`) or a concrete path
+        /// (`/users/42`). Returns `None` if no route matches.
+        ///
+        /// `path` 可以是路由模式（`/users/{id}`）或具体路径（`/users/42`）。
+        /// 没有匹配路由时返回 `None`。
+        pub fn middleware_chain(method: &str, path: &str) -> Option<Vec<String>> {
+            ::astrea::router::middleware_chain(
+                __ROUTES,
+                __SCOPES,
+                &__middleware_for::<()>,
+                method,
+                path,
+            )
+        }
+    }
+}
 
-                    #(
-                        let (__mode, __child) = #child_blocks;
-                        if __mode == ::astrea::middleware::MiddlewareMode::Override {
-                            __override_group = __override_group.merge(__child);
-                        } else {
-                            __extend = __extend.merge(__child);
-                        }
-                    )*
+/// Build the router expression used as the body of `create_router()`
+///
+/// / 构建用作 `create_router()` 函数体的路由器表达式
+///
+/// Layer ordering is resolved at runtime by `astrea::router::build_router_with`,
+/// which reads each scope's mode and priority from the `__loaded` scopes that
+/// `create_router()` loaded for the startup listing.
+///
+/// 中间件层顺序在运行时由 `astrea::router::build_router_with` 解析，
+/// 它会从 `create_router()` 为启动表加载的 `__loaded` 作用域中读取每个作用域的模式和优先级。
+///
+/// With a `public` directory, its files are served as the fallback, so route
+/// files always take precedence.
+///
+/// 配置了 `public` 目录时，其文件作为 fallback 提供，因此路由文件始终优先。
+pub fn build_router_expr(code: &ScopeCode, public_service: Option<TokenStream>) -> TokenStream {
+    let endpoints = &code.endpoints;
+    let fallback = match public_service {
+        Some(service) => quote! { .fallback_service(#service) },
+        None => quote! {},
+    };
+    quote! {
+        ::astrea::router::build_router_with(
+            __ROUTES,
+            __SCOPES,
+            vec![#(#endpoints),*],
+            __loaded,
+            &__middleware_for::<S>,
+        )
+        #fallback
+    }
+}
 
-                    let __scope_mw = #mw_mod::middleware::<S>();
-                    __scope_mw.apply(__extend).merge(__override_group)
-                }
+/// Build the `__PUBLIC_ASSETS` table of embedded files
+///
+/// / 构建嵌入文件表 `__PUBLIC_ASSETS`
+///
+/// Each file is pulled in with `include_bytes!`, so editing it recompiles the
+/// crate. Content hashes are computed here, at compile time.
+///
+/// 每个文件都通过 `include_bytes!` 引入，因此修改文件会触发重新编译。
+/// 内容哈希在此处（编译时）计算。
+pub fn build_embedded_assets(assets: &[PublicAsset]) -> TokenStream {
+    let entries = assets.iter().map(|asset| {
+        let url_path = &asset.url_path;
+        let file = asset.file_path.to_string_lossy().to_string();
+        let etag = std::fs::read(&asset.file_path)
+            .map(|body| content_hash(&body))
+            .unwrap_or_default();
+        let immutable = asset.is_hashed();
+        let variant = |extension: &str| match asset.precompressed(extension) {
+            Some(path) => {
+                let path = path.to_string_lossy().to_string();
+                quote! { ::std::option::Option::Some(include_bytes!(#path)) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+        let br = variant("br");
+        let gzip = variant("gz");
+        quote! {
+            ::astrea::static_files::EmbeddedAsset {
+                path: #url_path,
+                body: include_bytes!(#file),
+                etag: #etag,
+                br: #br,
+                gzip: #gzip,
+                immutable: #immutable,
             }
         }
+    });
+    quote! {
+        static __PUBLIC_ASSETS: &[::astrea::static_files::EmbeddedAsset] = &[#(#entries),*];
     }
 }
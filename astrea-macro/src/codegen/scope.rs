@@ -5,46 +5,119 @@ use quote::quote;
 use std::path::Path;
 use syn::Ident;
 
-use super::router::build_router_expr;
 use crate::scanner::MiddlewareScope;
 
-/// Generate module declarations and the router expression for a scope
+/// Code collected while walking the middleware scope tree
 ///
-/// / 为作用域生成模块声明和路由器表达式
+/// / 遍历中间件作用域树时收集的代码
+#[derive(Default)]
+pub struct ScopeCode {
+    /// Module declarations for routes and middleware files
+    /// / 路由和中间件文件的模块声明
+    pub mod_decls: Vec<TokenStream>,
+    /// `RouteDef` entries, one per route
+    /// / `RouteDef` 条目，每个路由一个
+    pub route_defs: Vec<TokenStream>,
+    /// Method router expressions, in the same order as `route_defs`
+    /// / 方法路由表达式，顺序与 `route_defs` 一致
+    pub endpoints: Vec<TokenStream>,
+    /// `ScopeDef` entries, one per `_middleware.rs` (index = position)
+    /// / `ScopeDef` 条目，每个 `_middleware.rs` 一个（索引 = 位置）
+    pub scope_defs: Vec<TokenStream>,
+    /// Middleware module identifiers, in the same order as `scope_defs`
+    /// / 中间件模块标识符，顺序与 `scope_defs` 一致
+    pub mw_modules: Vec<Ident>,
+    /// OpenAPI registration statements
+    /// / OpenAPI 注册语句
+    pub openapi_regs: Vec<TokenStream>,
+}
+
+/// Generate module declarations, route and scope tables for a scope tree
 ///
-/// Returns `(module_declarations, router_expression, openapi_registrations)`.
+/// / 为作用域树生成模块声明、路由表和作用域表
 ///
-/// 返回 `(模块声明列表, 路由器表达式, OpenAPI 注册列表)`。
+/// `parent` is the index of the nearest enclosing middleware scope. Scopes are
+/// numbered in pre-order, so a parent always has a lower index than its children.
+///
+/// `parent` 是最近的外层中间件作用域索引。作用域按先序编号，
+/// 因此父作用域的索引总是小于其子作用域。
 pub fn generate_scope_code(
     scope: &MiddlewareScope,
     manifest_dir: &str,
-) -> (Vec<TokenStream>, TokenStream, Vec<TokenStream>) {
-    let mut mod_decls = Vec::new();
-    let mut openapi_regs = Vec::new();
+    parent: Option<usize>,
+    out: &mut ScopeCode,
+) {
+    // ── Register this scope's middleware ──
+    // ── 注册此作用域的中间件 ──
+    let current = match &scope.middleware {
+        Some(mw) => {
+            let index = out.scope_defs.len();
+            let mw_mod = Ident::new(&mw.module_name, proc_macro2::Span::call_site());
+            let mw_rel = &mw.rel_path;
+            out.mod_decls.push(quote! {
+                #[allow(unused_imports)]
+                mod #mw_mod {
+                    include!(concat!(env!("CARGO_MANIFEST_DIR"), #mw_rel));
+                }
+            });
+
+            let scope_path = &mw.scope_path;
+            let parent_tokens = option_index_tokens(parent);
+            out.scope_defs.push(quote! {
+                ::astrea::router::ScopeDef {
+                    path: #scope_path,
+                    parent: #parent_tokens,
+                }
+            });
+            out.mw_modules.push(mw_mod);
+            Some(index)
+        }
+        None => parent,
+    };
 
-    // ── Module declarations for routes in this scope ──
-    // ── 此作用域中路由的模块声明 ──
+    // ── Module declarations and table entries for routes in this scope ──
+    // ── 此作用域中路由的模块声明和表条目 ──
+    let scope_tokens = option_index_tokens(current);
     for route in &scope.routes {
         let mod_name = Ident::new(&route.module_name, proc_macro2::Span::call_site());
         let rel_path = Path::new(&route.file_path)
             .strip_prefix(manifest_dir)
             .map(|p| format!("/{}", p.to_string_lossy()))
             .unwrap_or_else(|_| route.file_path.clone());
-        mod_decls.push(quote! {
+        out.mod_decls.push(quote! {
             #[allow(unused_imports)]
             mod #mod_name {
                 include!(concat!(env!("CARGO_MANIFEST_DIR"), #rel_path));
             }
         });
 
+        let method_str = &route.method;
+        let axum_path = &route.axum_path;
+        out.route_defs.push(quote! {
+            ::astrea::router::RouteDef {
+                method: #method_str,
+                path: #axum_path,
+                scope: #scope_tokens,
+            }
+        });
+
+        let method_fn = Ident::new(&route.method.to_lowercase(), proc_macro2::Span::call_site());
+        let source_file = rel_path.trim_start_matches('/');
+        out.endpoints.push(quote! {
+            ::astrea::axum::routing::#method_fn(#mod_name::handler::<S>)
+                .layer(::astrea::axum::Extension(::astrea::router::RouteInfo {
+                    pattern: #axum_path,
+                    source_file: #source_file,
+                }))
+        });
+
         // OpenAPI registration (only when openapi feature is enabled)
         // OpenAPI 注册（仅当启用 openapi feature 时）
         #[cfg(feature = "openapi")]
         {
-            let method_str = &route.method;
             let openapi_path = super::openapi::axum_path_to_openapi(&route.axum_path);
             let op_id = &route.module_name;
-            openapi_regs.push(quote! {
+            out.openapi_regs.push(quote! {
                 ::astrea::openapi::register(
                     #method_str,
                     #openapi_path,
@@ -55,62 +128,19 @@ pub fn generate_scope_code(
         }
     }
 
-    // ── Module declaration for this scope's middleware ──
-    // ── 此作用域的中间件模块声明 ──
-    if let Some(mw) = &scope.middleware {
-        let mw_mod = Ident::new(&mw.module_name, proc_macro2::Span::call_site());
-        let mw_rel = &mw.rel_path;
-        mod_decls.push(quote! {
-            #[allow(unused_imports)]
-            mod #mw_mod {
-                include!(concat!(env!("CARGO_MANIFEST_DIR"), #mw_rel));
-            }
-        });
-    }
-
-    // ── Route registration tokens ──
-    // ── 路由注册令牌 ──
-    let route_regs: Vec<_> = scope
-        .routes
-        .iter()
-        .map(|r| {
-            let axum_path = &r.axum_path;
-            let method_fn = Ident::new(&r.method.to_lowercase(), proc_macro2::Span::call_site());
-            let mod_name = Ident::new(&r.module_name, proc_macro2::Span::call_site());
-            quote! {
-                .route(#axum_path, ::astrea::axum::routing::#method_fn(#mod_name::handler::<S>))
-            }
-        })
-        .collect();
-
     // ── Recursively process child scopes ──
     // ── 递归处理子作用域 ──
-    let mut child_blocks: Vec<TokenStream> = Vec::new();
     for child in &scope.children {
-        let (child_mods, child_router_expr, child_openapi_regs) =
-            generate_scope_code(child, manifest_dir);
-        mod_decls.extend(child_mods);
-        openapi_regs.extend(child_openapi_regs);
-
-        let child_mw_mod = Ident::new(
-            &child.middleware.as_ref().unwrap().module_name,
-            proc_macro2::Span::call_site(),
-        );
-
-        child_blocks.push(quote! {
-            {
-                let __inner = #child_router_expr;
-                let __mw = #child_mw_mod::middleware::<S>();
-                let __mode = __mw.mode;
-                let __built = __mw.apply(__inner);
-                (__mode, __built)
-            }
-        });
+        generate_scope_code(child, manifest_dir, current, out);
     }
+}
 
-    // ── Build router expression ──
-    // ── 构建路由器表达式 ──
-    let router_expr = build_router_expr(scope, &route_regs, &child_blocks);
-
-    (mod_decls, router_expr, openapi_regs)
+/// Generate `Some(index)` / `None` tokens for a scope index
+///
+/// / 为作用域索引生成 `Some(index)` / `None` token
+fn option_index_tokens(index: Option<usize>) -> TokenStream {
+    match index {
+        Some(i) => quote! { Some(#i) },
+        None => quote! { None },
+    }
 }
@@ -10,6 +10,8 @@
 //!   [`route`] - 标记路由处理函数的属性宏
 //! - [`generate_routes!`] - Macro for generating routes from filesystem
 //!   [`generate_routes!`] - 从文件系统生成路由的宏
+//! - [`Validate`](derive@Validate) - Derive macro for declarative request validation
+//!   [`Validate`](derive@Validate) - 声明式请求验证的派生宏
 //!
 //! # Example
 //!
@@ -25,6 +27,7 @@
 //! }
 //! ```
 
+mod assets;
 mod codegen;
 #[cfg(feature = "openapi")]
 mod openapi;
@@ -32,6 +35,7 @@ mod parser;
 mod route;
 mod scanner;
 mod utils;
+mod validate;
 
 use proc_macro::TokenStream;
 
@@ -76,6 +80,49 @@ use proc_macro::TokenStream;
 /// }
 /// ```
 ///
+/// # Options
+///
+/// # 选项
+///
+/// - `body_limit = "10MB"` (or an integer byte count) — maximum request body
+///   size; larger bodies are rejected with `413 Payload Too Large`. Units
+///   `KB`/`MB`/`GB` are binary. Overrides the scope's `Middleware::body_limit`.
+///   最大请求体大小，超出时返回 `413 Payload Too Large`。`KB`/`MB`/`GB` 为二进制单位。
+///   优先于作用域的 `Middleware::body_limit`。
+/// - `body = "stream"` — don't buffer the body into `event.body`; read it with
+///   `get_body_stream(&event)` instead (default `"buffer"`).
+///   不将请求体缓冲到 `event.body`，改用 `get_body_stream(&event)` 读取（默认 `"buffer"`）。
+/// - `cache = "public, max-age=60"` — default `Cache-Control` for successful
+///   responses that don't set one. Overrides the scope's `Middleware::cache_control`.
+///   未设置 `Cache-Control` 的成功响应的默认值。优先于作用域的 `Middleware::cache_control`。
+/// - `if_match = "current_tag"` — when the request carries `If-Match`, call
+///   `async fn current_tag(event: &Event) -> Result<Option<ETag>>` before the
+///   handler body and answer `412 Precondition Failed` if no listed tag matches
+///   (`None` means the resource does not exist). Meant for `PUT` / `PATCH` / `DELETE`.
+///   请求带有 `If-Match` 时，在处理函数体之前调用 `current_tag` 获取资源当前的标签，
+///   没有匹配的标签时返回 `412 Precondition Failed`（`None` 表示资源不存在）。用于 `PUT` / `PATCH` / `DELETE`。
+///
+/// ```rust,ignore
+/// #[route(body = "stream", body_limit = "1GB")]
+/// pub async fn handler(event: Event) -> Result<Response> {
+///     let mut stream = get_body_stream(&event)?;
+///     while let Some(chunk) = stream.chunk().await? {
+///         // ...
+///     }
+///     Ok(no_content())
+/// }
+/// ```
+///
+/// ```rust,ignore
+/// async fn current_tag(event: &Event) -> Result<Option<ETag>> {
+///     let article = db::find(get_param_required(event, "id")?).await?;
+///     Ok(article.map(|a| ETag::strong(a.version.to_string())))
+/// }
+///
+/// #[route(if_match = "current_tag")]
+/// pub async fn update(event: Event) -> Result<Response> { /* ... */ }
+/// ```
+///
 /// # Generated Code
 ///
 /// # 生成的代码
@@ -86,6 +133,8 @@ use proc_macro::TokenStream;
 ///
 /// - Extracts Axum request data (method, URI, headers, params, query, body)
 ///   提取 Axum 请求数据（方法、URI、头、参数、查询、体）
+/// - Reads the body under the configured size limit
+///   在配置的大小限制下读取请求体
 /// - Creates an `Event` struct
 ///   创建 `Event` 结构体
 /// - Calls your handler function
@@ -93,8 +142,8 @@ use proc_macro::TokenStream;
 /// - Automatically converts `Result<Response>` to Axum's response type
 ///   自动将 `Result<Response>` 转换为 Axum 的响应类型
 #[proc_macro_attribute]
-pub fn route(_args: TokenStream, input: TokenStream) -> TokenStream {
-    route::impl_route(_args, input)
+pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
+    route::impl_route(args, input)
 }
 
 // ============================================================================
@@ -132,6 +181,12 @@ pub fn route(_args: TokenStream, input: TokenStream) -> TokenStream {
 /// 最靠近路由处理函数的中间件最先应用（最内层）。
 /// 父中间件包裹子中间件（最外层）。
 ///
+/// A scope can move itself further out with `Middleware::priority`; the
+/// generated `middleware_chain(method, path)` function reports the resolved order.
+///
+/// 作用域可以通过 `Middleware::priority` 将自身移到更外层；
+/// 生成的 `middleware_chain(method, path)` 函数会报告解析后的顺序。
+///
 /// ## Extend vs Override (叠加 vs 覆盖)
 ///
 /// - **Extend** (default): child middleware stacks on parent middleware
@@ -149,6 +204,64 @@ pub fn route(_args: TokenStream, input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// The routes directory defaults to `"src/routes"`; pass another path as the
+/// first argument.
+///
+/// 路由目录默认为 `"src/routes"`；可以通过第一个参数指定其他路径。
+///
+/// # Static Files
+///
+/// # 静态文件
+///
+/// `public = "public"` serves the files of a directory next to the routes, with
+/// MIME types, `ETag` / `Last-Modified`, range requests, precompressed `.br` /
+/// `.gz` variants and `index.html` for directories. Route files take
+/// precedence, and the files are listed in the startup table.
+///
+/// `public = "public"` 会在路由之外提供某个目录中的文件，支持 MIME 类型、
+/// `ETag` / `Last-Modified`、Range 请求、预压缩的 `.br` / `.gz` 变体以及目录的
+/// `index.html`。路由文件优先，文件会列在启动表中。
+///
+/// Like the routes directory, the path is relative to the crate root
+/// (`CARGO_MANIFEST_DIR`), so the startup table and `cargo run` find the files
+/// whatever the working directory. At runtime the `ASTREA_PUBLIC_DIR`
+/// environment variable overrides the directory, and when the build
+/// machine's directory does not exist (a binary deployed elsewhere) the path is
+/// resolved against the working directory instead.
+///
+/// 与路由目录一样，该路径相对于 crate 根目录（`CARGO_MANIFEST_DIR`），因此无论工作目录在哪里，
+/// 启动表和 `cargo run` 都能找到文件。运行时可以通过 `ASTREA_PUBLIC_DIR` 环境变量覆盖该目录；
+/// 当构建机器上的目录不存在时（部署到其他位置的二进制文件），路径会改为相对于工作目录解析。
+///
+/// ```rust,ignore
+/// mod routes {
+///     astrea::generate_routes!("src/routes", public = "public");
+/// }
+/// ```
+///
+/// Add `embed = true` to embed the files into the binary instead. Content
+/// hashes are computed at compile time and served as strong `ETag`s; files with
+/// a hash in their name (`app.3f9a1c2b.js`) are marked `immutable`.
+///
+/// 添加 `embed = true` 可将文件嵌入二进制文件。内容哈希在编译时计算并作为强 `ETag`
+/// 提供；文件名中带有哈希的文件（`app.3f9a1c2b.js`）会被标记为 `immutable`。
+///
+/// Each embedded file is pulled in with `include_bytes!`, so editing an
+/// existing file recompiles the crate. Cargo does not watch the directory
+/// itself, though: adding or removing a file is only picked up after the next
+/// unrelated rebuild. Add a `build.rs` that watches the directory:
+///
+/// 每个嵌入文件都通过 `include_bytes!` 引入，因此修改已有文件会触发重新编译。但 Cargo
+/// 不会监视目录本身：新增或删除文件要等到下一次无关的重新编译才会生效。添加一个监视该目录的
+/// `build.rs`：
+///
+/// ```rust,ignore
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rerun-if-changed=public");
+/// }
+/// ```
+///
 /// # File Convention
 ///
 /// # 文件约定
@@ -170,3 +283,50 @@ pub fn route(_args: TokenStream, input: TokenStream) -> TokenStream {
 pub fn generate_routes(input: TokenStream) -> TokenStream {
     codegen::impl_generate_routes(input)
 }
+
+// ============================================================================
+// #[derive(Validate)] derive macro
+// ============================================================================
+// #[derive(Validate)] 派生宏
+// ============================================================================
+
+/// Derive macro for `astrea::validate::Validate`
+///
+/// / `astrea::validate::Validate` 的派生宏
+///
+/// Each field lists its rules in `#[validate(...)]`:
+///
+/// 每个字段在 `#[validate(...)]` 中列出其规则：
+///
+/// - `length(min = 1, max = 32)` / `length(equal = 6)`
+/// - `range(min = 0, max = 150)` — integers or floats, inclusive
+///   整数或浮点数，包含边界
+/// - `regex = "^[a-z]+$"` — checked at compile time; an invalid pattern is a
+///   compile error on the attribute
+///   在编译时检查，无效的模式会在该属性上报编译错误
+/// - `email`
+/// - `nested` — the field type implements `Validate` itself
+///   字段类型本身实现了 `Validate`
+///
+/// Error paths follow the field's serde name (`rename` / `rename_all`).
+///
+/// 错误路径使用字段的 serde 名称（`rename` / `rename_all`）。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, Validate)]
+/// #[serde(rename_all = "camelCase")]
+/// struct CreateUser {
+///     #[validate(length(min = 1, max = 50))]
+///     display_name: String,   // errors reported at `displayName`
+///     #[validate(email)]
+///     email: Option<String>,  // only checked when present
+/// }
+/// ```
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    validate::impl_validate(input)
+}
@@ -10,24 +10,49 @@ use syn::{Expr, Type};
 // 配置驱动的参数检测
 // ---------------------------------------------------------------------------
 
+/// Where a detected parameter lives in the request
+/// / 检测到的参数在请求中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    Path,
+    Query,
+    Cookie,
+}
+
 /// Configuration for a parameter extraction function
 /// / 参数提取函数的配置
 pub struct ParamFuncConfig {
-    /// Whether this is a path parameter (vs query)
-    /// / 是否为路径参数（否则为查询参数）
-    pub is_path: bool,
+    /// Where the parameter is located
+    /// / 参数位置
+    pub kind: ParamKind,
     /// Whether the parameter is required
     /// / 参数是否必需
     pub required: bool,
+    /// Position of the parameter name argument in the call
+    /// / 调用中参数名参数的位置
+    pub name_arg: usize,
 }
 
 /// Lookup table: function name → parameter config
 /// / 查找表：函数名 → 参数配置
 pub static PARAM_FUNC_MAP: phf::Map<&'static str, ParamFuncConfig> = phf::phf_map! {
-    "get_param" => ParamFuncConfig { is_path: true, required: false },
-    "get_param_required" => ParamFuncConfig { is_path: true, required: true },
-    "get_query_param" => ParamFuncConfig { is_path: false, required: false },
-    "get_query_param_required" => ParamFuncConfig { is_path: false, required: true },
+    "get_param" => ParamFuncConfig { kind: ParamKind::Path, required: false, name_arg: 1 },
+    "get_param_required" => ParamFuncConfig { kind: ParamKind::Path, required: true, name_arg: 1 },
+    "get_query_param" => ParamFuncConfig { kind: ParamKind::Query, required: false, name_arg: 1 },
+    "get_query_param_required" => ParamFuncConfig { kind: ParamKind::Query, required: true, name_arg: 1 },
+    "get_cookie" => ParamFuncConfig { kind: ParamKind::Cookie, required: false, name_arg: 1 },
+    "get_cookie_required" => ParamFuncConfig { kind: ParamKind::Cookie, required: true, name_arg: 1 },
+    "get_signed_cookie" => ParamFuncConfig { kind: ParamKind::Cookie, required: false, name_arg: 2 },
+    "get_private_cookie" => ParamFuncConfig { kind: ParamKind::Cookie, required: false, name_arg: 2 },
+};
+
+/// Lookup table: typed extractor name → (where its struct fields live, whether it validates)
+/// / 查找表：类型化提取函数名 → （其结构体字段所在位置, 是否验证）
+pub static TYPED_PARAM_FUNC_MAP: phf::Map<&'static str, (ParamKind, bool)> = phf::phf_map! {
+    "get_query_as" => (ParamKind::Query, false),
+    "get_params_as" => (ParamKind::Path, false),
+    "get_valid_query" => (ParamKind::Query, true),
+    "get_valid_params" => (ParamKind::Path, true),
 };
 
 // ---------------------------------------------------------------------------
@@ -43,13 +68,25 @@ pub static RESPONSE_CONTENT_TYPE_MAP: phf::Map<&'static str, &'static str> = phf
     "html" => "text/html",
     "no_content" => "none",
     "redirect" => "none",
+    "redirect_permanent" => "none",
+    "redirect_moved" => "none",
+    "redirect_see_other" => "none",
+    "redirect_temporary" => "none",
+    "safe_redirect" => "none",
     "bytes" => "application/octet-stream",
+    "json_stream" => "application/json",
+    "try_json_stream" => "application/json",
+    "ndjson" => "application/x-ndjson",
+    "try_ndjson" => "application/x-ndjson",
 };
 
 /// Set of known response builder function names
 /// / 已知的响应构建器函数名集合
 pub static RESPONSE_BUILDER_SET: phf::Set<&'static str> = phf::phf_set! {
     "json", "text", "html", "no_content", "redirect", "bytes",
+    "json_stream", "try_json_stream", "ndjson", "try_ndjson",
+    "redirect_permanent", "redirect_moved", "redirect_see_other", "redirect_temporary",
+    "safe_redirect",
 };
 
 // ---------------------------------------------------------------------------
@@ -173,8 +210,8 @@ pub fn find_param_in_expr(expr: &Expr) -> Option<String> {
             if let Expr::Path(path) = &*call.func {
                 if let Some(seg) = path.path.segments.last() {
                     let name = seg.ident.to_string();
-                    if PARAM_FUNC_MAP.contains_key(name.as_str()) {
-                        return extract_string_arg(&call.args, 1);
+                    if let Some(cfg) = PARAM_FUNC_MAP.get(name.as_str()) {
+                        return extract_string_arg(&call.args, cfg.name_arg);
                     }
                 }
             }
@@ -208,29 +245,79 @@ pub fn find_param_in_expr(expr: &Expr) -> Option<String> {
     }
 }
 
-/// Check if an expression is or contains a `get_body(...)` call
+/// Name of the function called by an expression, looking through `?` and parentheses
 ///
-/// / 检查表达式是否是或包含 `get_body(...)` 调用
-pub fn is_get_body_call(expr: &Expr) -> bool {
+/// / 表达式所调用函数的名称，会穿过 `?` 和括号
+fn called_fn_name(expr: &Expr) -> Option<String> {
     match expr {
-        Expr::Call(call) => {
-            if let Expr::Path(path) = &*call.func {
-                if let Some(seg) = path.path.segments.last() {
-                    if seg.ident == "get_body" {
-                        return true;
-                    }
-                }
-            }
-            false
-        }
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        },
         // Handle: get_body(&event)?
-        Expr::Try(t) => is_get_body_call(&t.expr),
+        Expr::Try(t) => called_fn_name(&t.expr),
         // Handle: (get_body(...))
-        Expr::Paren(p) => is_get_body_call(&p.expr),
-        _ => false,
+        Expr::Paren(p) => called_fn_name(&p.expr),
+        _ => None,
     }
 }
 
+/// Names of the request body extractors
+/// / 请求体提取函数的名称
+pub const BODY_FUNCS: &[&str] = &["get_body", "get_body_any", "get_valid_body"];
+
+/// If an expression is or contains a `get_body(...)` / `get_body_any(...)` /
+/// `get_valid_body(...)` call, return whether it validates the body
+///
+/// / 如果表达式是或包含 `get_body(...)` / `get_body_any(...)` / `get_valid_body(...)`
+/// 调用，返回其是否验证请求体
+pub fn body_call_validated(expr: &Expr) -> Option<bool> {
+    called_fn_name(expr)
+        .filter(|name| BODY_FUNCS.contains(&name.as_str()))
+        .map(|name| name == "get_valid_body")
+}
+
+/// If an expression is a `get_query_as(...)` / `get_params_as(...)` (or `get_valid_*`)
+/// call, return where its fields live and whether they are validated
+///
+/// / 如果表达式是 `get_query_as(...)` / `get_params_as(...)`（或 `get_valid_*`）调用，
+/// 返回其字段所在位置以及是否经过验证
+pub fn typed_param_call_kind(expr: &Expr) -> Option<(ParamKind, bool)> {
+    called_fn_name(expr).and_then(|name| TYPED_PARAM_FUNC_MAP.get(name.as_str()).copied())
+}
+
+/// Resolve the media types listed in a `get_body_any(&event, &[...])` format argument
+///
+/// / 解析 `get_body_any(&event, &[...])` 格式参数中列出的媒体类型
+///
+/// Returns `None` unless the argument is an array literal of `BodyFormat`
+/// variants, in which case all registered formats are assumed.
+///
+/// 仅当参数是 `BodyFormat` 变体的数组字面量时返回结果，否则返回 `None`（视为接受所有已注册格式）。
+pub fn body_format_media_types(expr: &Expr) -> Option<Vec<String>> {
+    let expr = match expr {
+        Expr::Reference(r) => &*r.expr,
+        other => other,
+    };
+    let Expr::Array(array) = expr else {
+        return None;
+    };
+    array
+        .elems
+        .iter()
+        .map(|elem| match elem {
+            Expr::Path(p) => match p.path.segments.last()?.ident.to_string().as_str() {
+                "Json" => Some("application/json".to_string()),
+                "Form" => Some("application/x-www-form-urlencoded".to_string()),
+                _ => None,
+            },
+            // BodyFormat::Custom("application/yaml")
+            Expr::Call(call) => extract_string_arg(&call.args, 0),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Parse token stream inside a `json!({...})` macro to extract top-level keys
 ///
 /// / 解析 `json!({...})` 宏内部的 token 流以提取顶层键
@@ -5,7 +5,12 @@
 //! Walks the handler function body using `syn::visit::Visit` to detect:
 //! - `get_param` / `get_param_required` calls → path parameters
 //! - `get_query_param` / `get_query_param_required` calls → query parameters
-//! - `get_body::<T>()` calls → request body type
+//! - `get_cookie` / `get_cookie_required` / `get_signed_cookie` calls → cookie parameters
+//! - `get_query_as::<T>()` / `get_params_as::<T>()` calls → parameters derived from `T` at runtime
+//! - `get_valid_query::<T>()` / `get_valid_params::<T>()` calls → the same, plus `T`'s validation rules
+//! - `get_body::<T>()` / `get_body_any::<T>()` calls → request body type and media types
+//! - `get_valid_body::<T>()` calls → request body with a schema reflected from `T` and its rules
+//! - `get_multipart()` calls with `.text("x")` / `.file("x")` → `multipart/form-data` body
 //! - `.parse::<T>()` calls → parameter type inference
 //! - `json()` / `text()` / `html()` calls → response content type
 //! - `json!({...})` macros → response field names
@@ -16,6 +21,7 @@ mod helpers;
 mod visitor;
 
 pub use doc::parse_doc_annotations;
+pub use helpers::ParamKind;
 pub use visitor::{HandlerVisitor, ParamInfo};
 
 use proc_macro2::TokenStream;
@@ -37,7 +43,11 @@ struct MetaTokenBuilder {
     tags: Vec<String>,
     security: Vec<String>,
     params: Vec<ParamInfo>,
+    typed_params: Vec<(ParamKind, syn::Type, bool)>,
     request_body: Option<String>,
+    validated_body_type: Option<syn::Type>,
+    body_media_types: Option<Vec<String>>,
+    form_fields: Option<Vec<(String, bool)>>,
     response_content_type: String,
     response_fields: Vec<String>,
     deprecated: bool,
@@ -52,7 +62,11 @@ impl MetaTokenBuilder {
             tags: Vec::new(),
             security: Vec::new(),
             params: Vec::new(),
+            typed_params: Vec::new(),
             request_body: None,
+            validated_body_type: None,
+            body_media_types: None,
+            form_fields: None,
             response_content_type: String::new(),
             response_fields: Vec::new(),
             deprecated: false,
@@ -85,11 +99,31 @@ impl MetaTokenBuilder {
         self
     }
 
+    fn typed_params(mut self, v: Vec<(ParamKind, syn::Type, bool)>) -> Self {
+        self.typed_params = v;
+        self
+    }
+
     fn request_body(mut self, v: Option<String>) -> Self {
         self.request_body = v;
         self
     }
 
+    fn validated_body_type(mut self, v: Option<syn::Type>) -> Self {
+        self.validated_body_type = v;
+        self
+    }
+
+    fn body_media_types(mut self, v: Option<Vec<String>>) -> Self {
+        self.body_media_types = v;
+        self
+    }
+
+    fn form_fields(mut self, v: Option<Vec<(String, bool)>>) -> Self {
+        self.form_fields = v;
+        self
+    }
+
     fn response_content_type(mut self, v: &str) -> Self {
         self.response_content_type = v.to_string();
         self
@@ -127,6 +161,17 @@ impl MetaTokenBuilder {
         quote! { vec![#(#items.to_string()),*] }
     }
 
+    /// Generate a `ParamLocation` token for a parameter kind
+    ///
+    /// / 为参数类型生成 `ParamLocation` token
+    fn location_tokens(kind: ParamKind) -> TokenStream {
+        match kind {
+            ParamKind::Path => quote! { ::astrea::openapi::ParamLocation::Path },
+            ParamKind::Query => quote! { ::astrea::openapi::ParamLocation::Query },
+            ParamKind::Cookie => quote! { ::astrea::openapi::ParamLocation::Cookie },
+        }
+    }
+
     /// Build the final `HandlerMeta { ... }` TokenStream
     ///
     /// / 构建最终的 `HandlerMeta { ... }` TokenStream
@@ -143,11 +188,7 @@ impl MetaTokenBuilder {
                 let name = &p.name;
                 let required = p.required;
                 let schema_type = &p.schema_type;
-                let location = if p.is_path {
-                    quote! { ::astrea::openapi::ParamLocation::Path }
-                } else {
-                    quote! { ::astrea::openapi::ParamLocation::Query }
-                };
+                let location = Self::location_tokens(p.kind);
                 let format_tokens = Self::option_tokens(&p.schema_format);
                 quote! {
                     ::astrea::openapi::ParamMeta {
@@ -156,19 +197,87 @@ impl MetaTokenBuilder {
                         required: #required,
                         schema_type: #schema_type.to_string(),
                         schema_format: #format_tokens,
+                        item_type: None,
+                        schema_keywords: ::astrea::serde_json::Map::new(),
                     }
                 }
             })
             .collect();
 
-        let request_body_tokens = match &self.request_body {
-            Some(type_name) => quote! {
-                Some(::astrea::openapi::RequestBodyMeta {
-                    content_type: "application/json".to_string(),
-                    schema_type_name: #type_name.to_string(),
+        // Struct fields of `get_query_as::<T>` / `get_params_as::<T>` are only
+        // known at runtime, so append them when the meta is built; validated
+        // types also contribute their rules
+        // `get_query_as::<T>` / `get_params_as::<T>` 的结构体字段只能在运行时得知，
+        // 因此在构建元数据时追加；经过验证的类型还会附带其规则
+        let params_tokens = if self.typed_params.is_empty() {
+            quote! { vec![#(#param_tokens),*] }
+        } else {
+            let typed: Vec<TokenStream> = self
+                .typed_params
+                .iter()
+                .map(|(kind, ty, validated)| {
+                    let location = Self::location_tokens(*kind);
+                    if *validated {
+                        quote! { __params.extend(::astrea::openapi::validated_params_of::<#ty>(#location)); }
+                    } else {
+                        quote! { __params.extend(::astrea::openapi::params_of::<#ty>(#location)); }
+                    }
                 })
-            },
-            None => quote! { None },
+                .collect();
+            quote! {{
+                let mut __params = vec![#(#param_tokens),*];
+                #(#typed)*
+                __params
+            }}
+        };
+
+        let request_body_tokens = match (&self.request_body, &self.form_fields) {
+            (Some(type_name), _) => {
+                // Without an explicit format list, `get_body` accepts every
+                // registered format, which is only known at runtime
+                // 没有显式格式列表时，`get_body` 接受所有已注册格式，这只能在运行时得知
+                let content_types = match &self.body_media_types {
+                    Some(types) => quote! { vec![#(#types.to_string()),*] },
+                    None => quote! {
+                        ::astrea::extract::body::media_types(&::astrea::extract::body_formats())
+                    },
+                };
+                let primary = self
+                    .body_media_types
+                    .as_ref()
+                    .and_then(|types| types.first().cloned())
+                    .unwrap_or_else(|| "application/json".to_string());
+                let schema = match &self.validated_body_type {
+                    Some(ty) => quote! { Some(::astrea::openapi::schema_of::<#ty>()) },
+                    None => quote! { None },
+                };
+                quote! {
+                    Some(::astrea::openapi::RequestBodyMeta {
+                        content_type: #primary.to_string(),
+                        content_types: #content_types,
+                        schema_type_name: #type_name.to_string(),
+                        schema: #schema,
+                        form_fields: vec![],
+                    })
+                }
+            }
+            (None, Some(fields)) => {
+                let names = fields.iter().map(|(n, _)| n);
+                let binaries = fields.iter().map(|(_, b)| b);
+                quote! {
+                    Some(::astrea::openapi::RequestBodyMeta {
+                        content_type: "multipart/form-data".to_string(),
+                        content_types: vec!["multipart/form-data".to_string()],
+                        schema_type_name: String::new(),
+                        schema: None,
+                        form_fields: vec![#(::astrea::openapi::FormFieldMeta {
+                            name: #names.to_string(),
+                            binary: #binaries,
+                        }),*],
+                    })
+                }
+            }
+            (None, None) => quote! { None },
         };
 
         let response_ct = &self.response_content_type;
@@ -186,7 +295,7 @@ impl MetaTokenBuilder {
                 description: #description_tokens,
                 tags: #tags_tokens,
                 security: #security_tokens,
-                parameters: vec![#(#param_tokens),*],
+                parameters: #params_tokens,
                 request_body: #request_body_tokens,
                 response_content_type: #response_ct_tokens,
                 response_schema_fields: #response_fields_tokens,
@@ -226,7 +335,11 @@ pub fn analyze_handler(input_fn: &ItemFn) -> TokenStream {
         .tags(doc.tags)
         .security(doc.security)
         .params(visitor.params)
+        .typed_params(visitor.typed_params)
         .request_body(visitor.body_type_name)
+        .validated_body_type(visitor.validated_body_type)
+        .body_media_types(visitor.body_media_types)
+        .form_fields(visitor.multipart.then_some(visitor.form_fields))
         .response_content_type(response_ct)
         .response_fields(visitor.json_macro_keys)
         .deprecated(doc.deprecated)
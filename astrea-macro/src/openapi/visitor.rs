@@ -6,9 +6,10 @@ use syn::visit::Visit;
 use syn::{Expr, Local};
 
 use super::helpers::{
-    PARAM_FUNC_MAP, RESPONSE_BUILDER_SET, determine_response_content_type, extract_string_arg,
-    find_param_in_expr, is_get_body_call, parse_json_macro_keys, rust_type_to_openapi,
-    type_to_name,
+    BODY_FUNCS, PARAM_FUNC_MAP, ParamKind, RESPONSE_BUILDER_SET, TYPED_PARAM_FUNC_MAP,
+    body_call_validated, body_format_media_types, determine_response_content_type,
+    extract_string_arg, find_param_in_expr, parse_json_macro_keys, rust_type_to_openapi,
+    type_to_name, typed_param_call_kind,
 };
 
 /// Information about a detected parameter
@@ -16,7 +17,7 @@ use super::helpers::{
 #[derive(Debug, Clone)]
 pub struct ParamInfo {
     pub name: String,
-    pub is_path: bool,
+    pub kind: ParamKind,
     pub required: bool,
     pub schema_type: String,
     pub schema_format: Option<String>,
@@ -30,9 +31,25 @@ pub struct HandlerVisitor {
     /// Detected parameters (path + query)
     /// / 检测到的参数（路径 + 查询）
     pub params: Vec<ParamInfo>,
+    /// Types passed to `get_query_as::<T>` / `get_params_as::<T>` (or `get_valid_*`),
+    /// with whether they are validated
+    /// / 传给 `get_query_as::<T>` / `get_params_as::<T>`（或 `get_valid_*`）的类型及其是否经过验证
+    pub typed_params: Vec<(ParamKind, syn::Type, bool)>,
     /// Detected request body type name
     /// / 检测到的请求体类型名
     pub body_type_name: Option<String>,
+    /// Body type passed to `get_valid_body`, whose schema is reflected at runtime
+    /// / 传给 `get_valid_body` 的请求体类型，其模式在运行时反射得到
+    pub validated_body_type: Option<syn::Type>,
+    /// Media types listed in `get_body_any(&event, &[...])`; `None` means all registered formats
+    /// / `get_body_any(&event, &[...])` 中列出的媒体类型；`None` 表示所有已注册格式
+    pub body_media_types: Option<Vec<String>>,
+    /// Whether `get_multipart` / `get_multipart_with` is called
+    /// / 是否调用了 `get_multipart` / `get_multipart_with`
+    pub multipart: bool,
+    /// Form fields from `.text("name")` / `.file("name")` calls: `(name, is_file)`
+    /// / 来自 `.text("name")` / `.file("name")` 调用的表单字段：`(名称, 是否文件)`
+    pub form_fields: Vec<(String, bool)>,
     /// Response builder function names found
     /// / 找到的响应构建器函数名
     pub response_builders: Vec<String>,
@@ -53,25 +70,45 @@ impl<'ast> Visit<'ast> for HandlerVisitor {
                 // Config-driven parameter detection
                 // 配置驱动的参数检测
                 if let Some(cfg) = PARAM_FUNC_MAP.get(func_name.as_str()) {
-                    if let Some(name) = extract_string_arg(&node.args, 1) {
+                    if let Some(name) = extract_string_arg(&node.args, cfg.name_arg) {
                         self.params.push(ParamInfo {
                             name,
-                            is_path: cfg.is_path,
+                            kind: cfg.kind,
                             required: cfg.required,
                             schema_type: "string".to_string(),
                             schema_format: None,
                         });
                     }
-                } else if func_name == "get_body" {
+                } else if let Some(&(kind, validated)) =
+                    TYPED_PARAM_FUNC_MAP.get(func_name.as_str())
+                {
+                    // Typed extraction: get_query_as::<Type>(...)
+                    // 类型化提取: get_query_as::<Type>(...)
+                    if let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments {
+                        if let Some(syn::GenericArgument::Type(ty)) = args.args.first() {
+                            self.push_typed_param(kind, ty, validated);
+                        }
+                    }
+                } else if BODY_FUNCS.contains(&func_name.as_str()) {
                     // Request body extraction
                     // 请求体提取
                     // Check for turbofish: get_body::<Type>(...)
                     // 检查 turbofish: get_body::<Type>(...)
                     if let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments {
                         if let Some(syn::GenericArgument::Type(ty)) = args.args.first() {
-                            self.body_type_name = Some(type_to_name(ty));
+                            self.set_body_type(ty, func_name == "get_valid_body");
                         }
                     }
+                    // Accepted formats: get_body_any(&event, &[BodyFormat::Form])
+                    // 接受的格式: get_body_any(&event, &[BodyFormat::Form])
+                    if func_name == "get_body_any" {
+                        self.body_media_types =
+                            node.args.iter().nth(1).and_then(body_format_media_types);
+                    }
+                } else if func_name == "get_multipart" || func_name == "get_multipart_with" {
+                    // Multipart form extraction
+                    // Multipart 表单提取
+                    self.multipart = true;
                 } else if RESPONSE_BUILDER_SET.contains(func_name.as_str()) {
                     // Response builder detection
                     // 响应构建器检测
@@ -104,6 +141,17 @@ impl<'ast> Visit<'ast> for HandlerVisitor {
             }
         }
 
+        // Detect form.text("name") / form.file("name") for multipart fields
+        // 检测 form.text("name") / form.file("name") 以获取 multipart 字段
+        if node.method == "text" || node.method == "file" {
+            if let Some(name) = extract_string_arg(&node.args, 0) {
+                let is_file = node.method == "file";
+                if !self.form_fields.iter().any(|(n, _)| *n == name) {
+                    self.form_fields.push((name, is_file));
+                }
+            }
+        }
+
         // Continue recursion
         // 继续递归
         syn::visit::visit_expr_method_call(self, node);
@@ -113,9 +161,16 @@ impl<'ast> Visit<'ast> for HandlerVisitor {
         // Detect: let body: T = get_body(&event)?;
         // 检测: let body: T = get_body(&event)?;
         if let Some(init) = &node.init {
-            if is_get_body_call(&init.expr) {
+            if let Some(validated) = body_call_validated(&init.expr) {
+                if let syn::Pat::Type(pat_type) = &node.pat {
+                    self.set_body_type(&pat_type.ty, validated);
+                }
+            }
+            // Detect: let query: T = get_query_as(&event)?;
+            // 检测: let query: T = get_query_as(&event)?;
+            if let Some((kind, validated)) = typed_param_call_kind(&init.expr) {
                 if let syn::Pat::Type(pat_type) = &node.pat {
-                    self.body_type_name = Some(type_to_name(&pat_type.ty));
+                    self.push_typed_param(kind, &pat_type.ty, validated);
                 }
             }
         }
@@ -149,6 +204,27 @@ impl HandlerVisitor {
         determine_response_content_type(&self.response_builders)
     }
 
+    /// Record a typed extraction, ignoring duplicates
+    ///
+    /// / 记录类型化提取，忽略重复项
+    fn push_typed_param(&mut self, kind: ParamKind, ty: &syn::Type, validated: bool) {
+        if !self
+            .typed_params
+            .iter()
+            .any(|(k, t, _)| *k == kind && t == ty)
+        {
+            self.typed_params.push((kind, ty.clone(), validated));
+        }
+    }
+
+    /// Record the request body type
+    ///
+    /// / 记录请求体类型
+    fn set_body_type(&mut self, ty: &syn::Type, validated: bool) {
+        self.body_type_name = Some(type_to_name(ty));
+        self.validated_body_type = validated.then(|| ty.clone());
+    }
+
     /// Apply deferred type updates after the full AST traversal
     ///
     /// / 在完整的 AST 遍历后应用延迟的类型更新
@@ -4,12 +4,141 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{ItemFn, parse_macro_input};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, ItemFn, Lit, MetaNameValue, Token, parse_macro_input};
+
+/// Options accepted by `#[route(...)]`
+///
+/// / `#[route(...)]` 接受的选项
+#[derive(Default)]
+struct RouteArgs {
+    /// `body_limit = "10MB"` or `body_limit = 1024`
+    body_limit: Option<usize>,
+    /// `body = "stream"`
+    stream: bool,
+    /// `cache = "public, max-age=60"`
+    cache: Option<String>,
+    /// `if_match = "current_tag"`
+    if_match: Option<syn::Path>,
+}
+
+fn parse_args(args: TokenStream) -> syn::Result<RouteArgs> {
+    let mut parsed = RouteArgs::default();
+    let metas = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse(args)?;
+    for meta in metas {
+        let Expr::Lit(ExprLit { lit, .. }) = &meta.value else {
+            return Err(syn::Error::new_spanned(
+                &meta.value,
+                "期望字面量 / expected a literal",
+            ));
+        };
+        if meta.path.is_ident("body_limit") {
+            parsed.body_limit = Some(parse_size(lit)?);
+        } else if meta.path.is_ident("body") {
+            parsed.stream = match lit {
+                Lit::Str(s) if s.value() == "stream" => true,
+                Lit::Str(s) if s.value() == "buffer" => false,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "body 必须是 \"buffer\" 或 \"stream\" / body must be \"buffer\" or \"stream\"",
+                    ));
+                }
+            };
+        } else if meta.path.is_ident("cache") {
+            parsed.cache = Some(parse_cache(lit)?);
+        } else if meta.path.is_ident("if_match") {
+            parsed.if_match = Some(parse_callback(lit)?);
+        } else {
+            return Err(syn::Error::new_spanned(
+                &meta.path,
+                "未知的 #[route] 选项 / unknown #[route] option (expected `body_limit`, `body`, `cache` or `if_match`)",
+            ));
+        }
+    }
+    Ok(parsed)
+}
+
+/// Parse a `Cache-Control` value, which must be a valid header value
+///
+/// / 解析 `Cache-Control` 值，必须是合法的响应头值
+fn parse_cache(lit: &Lit) -> syn::Result<String> {
+    let Lit::Str(s) = lit else {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "cache 必须是字符串 / cache must be a string",
+        ));
+    };
+    let value = s.value();
+    let valid = !value.trim().is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_graphic() || b == b' ' || b == b'\t');
+    if !valid {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "cache 不是合法的 Cache-Control 值 / cache is not a valid Cache-Control value",
+        ));
+    }
+    Ok(value.trim().to_string())
+}
+
+/// Parse the path of the `If-Match` resource-tag callback
+///
+/// / 解析 `If-Match` 资源标签回调的路径
+fn parse_callback(lit: &Lit) -> syn::Result<syn::Path> {
+    let error = || {
+        syn::Error::new_spanned(
+            lit,
+            "if_match 必须是函数路径字符串 / if_match must be a function path string (e.g. \"current_tag\")",
+        )
+    };
+    let Lit::Str(s) = lit else {
+        return Err(error());
+    };
+    s.parse::<syn::Path>().map_err(|_| error())
+}
+
+/// Parse a byte size such as `1024`, `"512KB"` or `"10MB"` (binary units)
+///
+/// / 解析字节大小，如 `1024`、`"512KB"` 或 `"10MB"`（二进制单位）
+fn parse_size(lit: &Lit) -> syn::Result<usize> {
+    let error = || {
+        syn::Error::new_spanned(
+            lit,
+            "无效的大小 / invalid size (expected e.g. `1024`, \"512KB\", \"10MB\", \"1GB\")",
+        )
+    };
+    let text = match lit {
+        Lit::Int(i) => return i.base10_parse::<usize>(),
+        Lit::Str(s) => s.value(),
+        _ => return Err(error()),
+    };
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: usize = number.parse().map_err(|_| error())?;
+    let multiplier: usize = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return Err(error()),
+    };
+    number.checked_mul(multiplier).ok_or_else(error)
+}
 
 /// Implementation of the `#[route]` attribute macro
 ///
 /// / `#[route]` 属性宏的实现
-pub fn impl_route(_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn impl_route(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let input_fn = parse_macro_input!(input as ItemFn);
 
     let vis = &input_fn.vis;
@@ -54,6 +183,57 @@ pub fn impl_route(_args: TokenStream, input: TokenStream) -> TokenStream {
     #[cfg(not(feature = "openapi"))]
     let openapi_fn = quote! {};
 
+    // 请求体读取方式 / How the request body is read
+    let route_limit = match args.body_limit {
+        Some(limit) => quote! { ::std::option::Option::Some(#limit) },
+        None => quote! { ::std::option::Option::None },
+    };
+    let route_cache = match &args.cache {
+        Some(policy) => quote! { ::std::option::Option::Some(#policy) },
+        None => quote! { ::std::option::Option::None },
+    };
+    // `If-Match` 在处理函数体之前检查 / `If-Match` is checked before the handler body
+    let check_if_match = match &args.if_match {
+        Some(callback) => quote! {
+            if #event_name.headers().contains_key(::astrea::axum::http::header::IF_MATCH) {
+                let __precondition = match #callback(&#event_name).await {
+                    Ok(current) => ::astrea::extract::check_preconditions(&#event_name, current.as_ref(), None),
+                    Err(error) => Err(error),
+                };
+                if let Err(error) = __precondition {
+                    return error.into_response_with_request_id(&__request_id);
+                }
+            }
+        },
+        None => quote! {},
+    };
+    let (body_setup, attach_stream) = if args.stream {
+        (
+            quote! {
+                let (__body, __limit) = ::astrea::event::body::limited_body(__request, __limit);
+                if let Err(error) = ::astrea::event::body::check_content_length(&__headers, __limit) {
+                    return error.into_response_with_request_id(&__request_id);
+                }
+                let __body_stream = ::astrea::event::BodyStream::new(__body, __limit);
+                let __body_bytes = ::astrea::bytes::Bytes::new();
+            },
+            quote! {
+                #event_name = #event_name.with_body_stream(__body_stream);
+            },
+        )
+    } else {
+        (
+            quote! {
+                let (__body, __limit) = ::astrea::event::body::limited_body(__request, __limit);
+                let __body_bytes = match ::astrea::event::body::read_body(&__headers, __body, __limit).await {
+                    Ok(bytes) => bytes,
+                    Err(error) => return error.into_response_with_request_id(&__request_id),
+                };
+            },
+            quote! {},
+        )
+    };
+
     // 生成包装函数 — 所有外部类型通过 ::astrea:: 引用，用户无需直接依赖 axum / bytes
     // Generate wrapper function - all external types referenced via ::astrea::
     let expanded = quote! {
@@ -64,7 +244,7 @@ pub fn impl_route(_args: TokenStream, input: TokenStream) -> TokenStream {
             __headers: ::astrea::axum::http::HeaderMap,
             __path_params: ::astrea::axum::extract::Path<std::collections::HashMap<String, String>>,
             __query_params: ::astrea::axum::extract::Query<std::collections::HashMap<String, String>>,
-            __body_bytes: ::astrea::bytes::Bytes,
+            __request: ::astrea::axum::extract::Request,
         ) -> impl ::astrea::axum::response::IntoResponse
         where
             S: Clone + Send + Sync + 'static,
@@ -72,28 +252,77 @@ pub fn impl_route(_args: TokenStream, input: TokenStream) -> TokenStream {
             use ::astrea::{Event, Response};
             use ::astrea::axum::response::IntoResponse;
 
-            let __path = __uri.path().to_string();
-
-            let mut #event_name = Event::new(
-                __method,
-                __path,
-                __uri,
-                __headers,
-                __path_params.0,
-                __query_params.0,
-                __body_bytes,
+            // 每个请求一个 tracing span / One tracing span per request
+            let __request_id = ::astrea::event::request_id::resolve(&__headers);
+            let __route_info = __request.extensions().get::<::astrea::router::RouteInfo>().copied();
+            let __route = match __route_info {
+                Some(info) => info.pattern.to_string(),
+                None => __request
+                    .extensions()
+                    .get::<::astrea::axum::extract::MatchedPath>()
+                    .map_or_else(|| __uri.path().to_string(), |p| p.as_str().to_string()),
+            };
+            let __span = ::astrea::tracing::info_span!(
+                "request",
+                method = %__method,
+                route = %__route,
+                request_id = %__request_id,
+                status = ::astrea::tracing::field::Empty,
             );
 
-            // 注入状态 / Inject state
-            #event_name.state = Some(::std::sync::Arc::new(__state) as ::std::sync::Arc<dyn ::std::any::Any + Send + Sync>);
+            let __conditions = ::astrea::response::conditional::Conditions::from_request(&__method, &__headers);
 
-            let result: ::std::result::Result<::astrea::Response, ::astrea::RouteError> =
-                async move #block.await;
+            let __handle = async {
+                let __limit = ::astrea::event::body::resolve_limit(#route_limit, __request.extensions());
+                let __connection = ::astrea::event::ConnectionInfo::from_parts(__request.version(), __request.extensions());
+                let __cache = ::astrea::response::cache_control::resolve(#route_cache, __request.extensions());
+                #body_setup
 
-            match result {
-                Ok(response) => response.into_axum_response(),
-                Err(error) => error.into_response(),
-            }
+                let __path = __uri.path().to_string();
+                let __pretty = cfg!(debug_assertions)
+                    && ::astrea::response::builders::wants_pretty(&__query_params.0);
+
+                let mut #event_name = Event::new(
+                    __method,
+                    __path,
+                    __uri,
+                    __headers,
+                    __path_params.0,
+                    __query_params.0,
+                    __body_bytes,
+                )
+                .with_connection(__connection)
+                .with_request_id(__request_id.clone());
+                if let Some(info) = __route_info {
+                    #event_name = #event_name.with_route(info);
+                }
+                #attach_stream
+
+                // 注入状态 / Inject state
+                #event_name.state = Some(::std::sync::Arc::new(__state) as ::std::sync::Arc<dyn ::std::any::Any + Send + Sync>);
+                #check_if_match
+
+                let result: ::std::result::Result<::astrea::Response, ::astrea::RouteError> =
+                    async move #block.await;
+
+                match result {
+                    Ok(mut response) => {
+                        // debug 构建中 `?pretty=1` 缩进 JSON / `?pretty=1` indents JSON in debug builds
+                        if __pretty {
+                            response = ::astrea::response::builders::prettify(response);
+                        }
+                        __conditions
+                            .apply(::astrea::response::cache_control::apply_default(response, __cache.as_ref()))
+                            .into_axum_response()
+                    }
+                    Err(error) => error.into_response_with_request_id(&__request_id),
+                }
+            };
+
+            let mut __response = ::astrea::tracing::Instrument::instrument(__handle, __span.clone()).await;
+            __span.record("status", __response.status().as_u16());
+            ::astrea::event::request_id::echo(__response.headers_mut(), &__request_id);
+            __response
         }
 
         #openapi_fn
@@ -0,0 +1,317 @@
+//! `#[derive(Validate)]` implementation
+//!
+//! / `#[derive(Validate)]` 实现
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Lit, Meta, Token, UnOp};
+
+/// Implementation of `#[derive(Validate)]`
+///
+/// / `#[derive(Validate)]` 的实现
+pub fn impl_validate(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(Validate)] 仅支持结构体 / #[derive(Validate)] only supports structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(Validate)] 需要具名字段 / #[derive(Validate)] requires named fields",
+        ));
+    };
+
+    let rename_all = serde_string(&input.attrs, "rename_all")?;
+
+    let mut checks = Vec::new();
+    let mut constraints = Vec::new();
+    for field in &fields.named {
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+        let rules = parse_rules(&field.attrs)?;
+        if rules.is_empty() {
+            continue;
+        }
+
+        let raw_name = ident.to_string();
+        let raw_name = raw_name.trim_start_matches("r#");
+        let path = match serde_string(&field.attrs, "rename")? {
+            Some(name) => name,
+            None => match &rename_all {
+                Some(rule) => apply_rename_all(raw_name, rule),
+                None => raw_name.to_string(),
+            },
+        };
+        let ty = &field.ty;
+
+        for rule in rules {
+            let (check, constraint) = rule.tokens(ident, ty, &path);
+            checks.push(check);
+            constraints.push(constraint);
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::astrea::validate::Validate for #name #ty_generics #where_clause {
+            fn validate(&self) -> ::std::result::Result<(), ::astrea::validate::ValidationErrors> {
+                let mut __errors = ::astrea::validate::ValidationErrors::new();
+                #(#checks)*
+                __errors.into_result()
+            }
+
+            fn constraints() -> ::std::vec::Vec<(::std::string::String, ::astrea::validate::Constraint)> {
+                let mut __constraints = ::std::vec::Vec::new();
+                #(#constraints)*
+                __constraints
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Rules
+// 规则
+// ============================================================================
+
+enum Rule {
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    Range {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Regex(String),
+    Email,
+    Nested,
+}
+
+impl Rule {
+    /// Generate the check and the `constraints()` entry for this rule
+    ///
+    /// / 为此规则生成检查代码和 `constraints()` 条目
+    fn tokens(
+        &self,
+        ident: &syn::Ident,
+        ty: &syn::Type,
+        path: &str,
+    ) -> (TokenStream2, TokenStream2) {
+        let rules = quote! { ::astrea::validate::rules };
+        let constraint = quote! { ::astrea::validate::Constraint };
+        match self {
+            Self::Length { min, max } => {
+                let min = option_tokens(*min);
+                let max = option_tokens(*max);
+                (
+                    quote! { #rules::length(&mut __errors, #path, &self.#ident, #min, #max); },
+                    quote! {
+                        __constraints.push((#path.to_string(), #constraint::Length { min: #min, max: #max }));
+                    },
+                )
+            }
+            Self::Range { min, max } => {
+                let min = option_tokens(*min);
+                let max = option_tokens(*max);
+                (
+                    quote! { #rules::range(&mut __errors, #path, &self.#ident, #min, #max); },
+                    quote! {
+                        __constraints.push((#path.to_string(), #constraint::Range { min: #min, max: #max }));
+                    },
+                )
+            }
+            Self::Regex(pattern) => (
+                quote! {{
+                    static __PATTERN: #rules::RegexCell = #rules::RegexCell::new();
+                    #rules::regex(&mut __errors, #path, &self.#ident, &__PATTERN, #pattern);
+                }},
+                quote! {
+                    __constraints.push((#path.to_string(), #constraint::Regex(#pattern)));
+                },
+            ),
+            Self::Email => (
+                quote! { #rules::email(&mut __errors, #path, &self.#ident); },
+                quote! {
+                    __constraints.push((#path.to_string(), #constraint::Email));
+                },
+            ),
+            Self::Nested => (
+                quote! { #rules::nested(&mut __errors, #path, &self.#ident); },
+                quote! {
+                    for (__path, __c) in <#ty as ::astrea::validate::Validate>::constraints() {
+                        __constraints.push((::astrea::validate::join_path(#path, &__path), __c));
+                    }
+                },
+            ),
+        }
+    }
+}
+
+fn option_tokens<T: quote::ToTokens>(value: Option<T>) -> TokenStream2 {
+    match value {
+        Some(v) => quote! { ::std::option::Option::Some(#v) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+/// Parse every `#[validate(...)]` attribute of a field
+///
+/// / 解析字段上的所有 `#[validate(...)]` 属性
+fn parse_rules(attrs: &[syn::Attribute]) -> syn::Result<Vec<Rule>> {
+    let mut rules = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("validate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("email") {
+                rules.push(Rule::Email);
+            } else if meta.path.is_ident("nested") {
+                rules.push(Rule::Nested);
+            } else if meta.path.is_ident("regex") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                // 在编译时检查模式，而不是在请求时 panic
+                // Check the pattern at compile time instead of panicking at request time
+                if let Err(error) = regex::Regex::new(&lit.value()) {
+                    return Err(syn::Error::new_spanned(
+                        &lit,
+                        format!("无效的正则表达式 / invalid regex: {error}"),
+                    ));
+                }
+                rules.push(Rule::Regex(lit.value()));
+            } else if meta.path.is_ident("length") {
+                let (mut min, mut max) = (None, None);
+                meta.parse_nested_meta(|bound| {
+                    let value: syn::LitInt = bound.value()?.parse()?;
+                    let value = value.base10_parse::<usize>()?;
+                    if bound.path.is_ident("min") {
+                        min = Some(value);
+                    } else if bound.path.is_ident("max") {
+                        max = Some(value);
+                    } else if bound.path.is_ident("equal") {
+                        min = Some(value);
+                        max = Some(value);
+                    } else {
+                        return Err(bound.error("expected `min`, `max` or `equal`"));
+                    }
+                    Ok(())
+                })?;
+                rules.push(Rule::Length { min, max });
+            } else if meta.path.is_ident("range") {
+                let (mut min, mut max) = (None, None);
+                meta.parse_nested_meta(|bound| {
+                    let expr: Expr = bound.value()?.parse()?;
+                    let value = number(&expr)?;
+                    if bound.path.is_ident("min") {
+                        min = Some(value);
+                    } else if bound.path.is_ident("max") {
+                        max = Some(value);
+                    } else {
+                        return Err(bound.error("expected `min` or `max`"));
+                    }
+                    Ok(())
+                })?;
+                rules.push(Rule::Range { min, max });
+            } else {
+                return Err(meta.error(
+                    "未知的验证规则 / unknown validation rule (expected `length`, `range`, `regex`, `email` or `nested`)",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rules)
+}
+
+/// Parse a numeric literal, optionally negated
+///
+/// / 解析数字字面量（可带负号）
+fn number(expr: &Expr) -> syn::Result<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(i), ..
+        }) => i.base10_parse::<f64>(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(f), ..
+        }) => f.base10_parse::<f64>(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => number(expr).map(|n| -n),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "期望数字字面量 / expected a number literal",
+        )),
+    }
+}
+
+// ============================================================================
+// serde attributes
+// serde 属性
+// ============================================================================
+
+/// Read a `#[serde(key = "...")]` string value
+///
+/// / 读取 `#[serde(key = "...")]` 字符串值
+fn serde_string(attrs: &[syn::Attribute], key: &str) -> syn::Result<Option<String>> {
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            if let Meta::NameValue(nv) = meta
+                && nv.path.is_ident(key)
+                && let Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) = &nv.value
+            {
+                return Ok(Some(s.value()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Apply a serde `rename_all` rule to a snake_case field name
+///
+/// / 将 serde `rename_all` 规则应用于 snake_case 字段名
+fn apply_rename_all(name: &str, rule: &str) -> String {
+    let words: Vec<&str> = name.split('_').filter(|w| !w.is_empty()).collect();
+    let capitalize = |w: &str| {
+        let mut chars = w.chars();
+        chars.next().map_or_else(String::new, |c| {
+            c.to_uppercase().collect::<String>() + chars.as_str()
+        })
+    };
+    match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "PascalCase" => words.iter().map(|w| capitalize(w)).collect(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == 0 {
+                    (*w).to_string()
+                } else {
+                    capitalize(w)
+                }
+            })
+            .collect(),
+        "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.replace('_', "-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
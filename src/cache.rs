@@ -0,0 +1,649 @@
+//! In-process response cache for `GET` routes
+//!
+//! / `GET` 路由的进程内响应缓存
+//!
+//! [`layer`] caches successful `GET` responses in memory. Entries are keyed by
+//! route pattern, path parameters, query parameters (all of them, or the keys
+//! selected with [`CacheLayer::query_keys`]) and the request headers named in
+//! the response's `Vary`. Concurrent misses for the same key are collapsed
+//! into a single handler call (single-flight).
+//!
+//! [`layer`] 在内存中缓存成功的 `GET` 响应。缓存项以路由模式、路径参数、查询参数
+//! （全部或由 [`CacheLayer::query_keys`] 选定的键）以及响应 `Vary` 中列出的请求头为键。
+//! 同一键的并发未命中会合并为一次处理函数调用（single-flight）。
+//!
+//! Responses are not stored when they are not `200 OK`, set cookies, carry
+//! `Vary: *`, or have `Cache-Control: no-store`, `no-cache` or `private`
+//! (e.g. through [`Response::cache_control`](crate::Response::cache_control)).
+//!
+//! 非 `200 OK`、设置了 Cookie、带有 `Vary: *`，或 `Cache-Control` 为 `no-store`、
+//! `no-cache` 或 `private`（例如通过 [`Response::cache_control`](crate::Response::cache_control)
+//! 设置）的响应不会被存储。
+//!
+//! Requests with `Authorization` or `Cookie` may be answered per user, so
+//! they only share responses marked `public` or `s-maxage` (RFC 9111 §3.5):
+//! other responses to them are neither stored nor served from the cache.
+//!
+//! 带有 `Authorization` 或 `Cookie` 的请求可能按用户返回不同内容，因此它们只共享标记为
+//! `public` 或 `s-maxage` 的响应（RFC 9111 §3.5）：其他响应既不会被存储，也不会从缓存中返回。
+//!
+//! # Example
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! // src/routes/api/users/_middleware.rs
+//! pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+//!     Middleware::new().wrap(|router| {
+//!         router.layer(astrea::cache::layer().ttl(Duration::from_secs(30)).max_entries(10_000))
+//!     })
+//! }
+//!
+//! // src/routes/api/users/[id].put.rs
+//! #[route]
+//! pub async fn handler(event: Event) -> Result<Response> {
+//!     let id = get_param_required(&event, "id")?;
+//!     // ... update the user ...
+//!     astrea::cache::invalidate("/api/users/{id}", id);
+//!     Ok(no_content())
+//! }
+//! ```
+
+use axum::body::Body;
+use axum::extract::{FromRequestParts, MatchedPath, RawPathParams, Request};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, header};
+use axum::response::Response as AxumResponse;
+use bytes::Bytes;
+use http_body::Body as _;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError, Weak};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+use crate::event::request_id;
+use crate::response::conditional::Conditions;
+
+/// Response header reporting `HIT` or `MISS`
+/// / 报告 `HIT` 或 `MISS` 的响应头
+pub const CACHE_STATUS_HEADER: HeaderName = HeaderName::from_static("x-cache");
+
+/// Default time to live / 默认存活时间
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// Default maximum number of entries / 默认最大缓存项数
+const DEFAULT_MAX_ENTRIES: usize = 1024;
+
+/// Default largest cacheable body / 默认可缓存的最大响应体
+const DEFAULT_MAX_BODY: u64 = 1024 * 1024;
+
+// ============================================================================
+// 缓存存储
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    pattern: String,
+    params: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+}
+
+struct Entry {
+    /// Request header values this variant was produced for
+    /// / 此变体对应的请求头值
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    expires: Instant,
+    seq: u64,
+    /// Marked `public` or `s-maxage`, so it may answer credentialed requests
+    /// / 标记为 `public` 或 `s-maxage`，因此可以响应带凭据的请求
+    shared: bool,
+}
+
+impl Entry {
+    fn matches(&self, headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| headers.get(name) == value.as_ref())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<Key, Vec<Entry>>,
+    pending: HashMap<Key, watch::Receiver<bool>>,
+    len: usize,
+    seq: u64,
+    /// Bumped on every invalidation so in-flight fills never store stale data
+    /// / 每次失效时递增，避免进行中的填充存储过期数据
+    generation: u64,
+}
+
+impl State {
+    fn lookup(&mut self, key: &Key, headers: &HeaderMap, now: Instant) -> Option<&Entry> {
+        let variants = self.entries.get_mut(key)?;
+        let before = variants.len();
+        variants.retain(|entry| entry.expires > now);
+        self.len -= before - variants.len();
+        let credentialed = has_credentials(headers);
+        variants
+            .iter()
+            .find(|entry| entry.matches(headers) && (entry.shared || !credentialed))
+    }
+
+    fn insert(&mut self, key: Key, mut entry: Entry, max_entries: usize) {
+        self.seq += 1;
+        entry.seq = self.seq;
+        let variants = self.entries.entry(key).or_default();
+        let before = variants.len();
+        variants.retain(|old| old.vary != entry.vary);
+        let replaced = before - variants.len();
+        variants.push(entry);
+        self.len = self.len + 1 - replaced;
+
+        if self.len > max_entries {
+            let now = Instant::now();
+            self.remove_where(|_, entry| entry.expires <= now);
+        }
+        while self.len > max_entries {
+            let Some(oldest) = self.entries.values().flatten().map(|e| e.seq).min() else {
+                break;
+            };
+            self.remove_where(|_, entry| entry.seq == oldest);
+        }
+    }
+
+    fn remove_where(&mut self, mut remove: impl FnMut(&Key, &Entry) -> bool) {
+        let mut removed = 0;
+        self.entries.retain(|key, variants| {
+            let before = variants.len();
+            variants.retain(|entry| !remove(key, entry));
+            removed += before - variants.len();
+            !variants.is_empty()
+        });
+        self.len -= removed;
+    }
+}
+
+#[derive(Default)]
+struct Store {
+    state: Mutex<State>,
+}
+
+impl Store {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Every live store, so [`invalidate`] can reach all of them
+/// / 所有存活的存储，使 [`invalidate`] 能够访问全部存储
+fn stores() -> &'static Mutex<Vec<Weak<Store>>> {
+    static STORES: OnceLock<Mutex<Vec<Weak<Store>>>> = OnceLock::new();
+    STORES.get_or_init(Mutex::default)
+}
+
+fn for_each_store(mut f: impl FnMut(&mut State)) {
+    let mut stores = stores().lock().unwrap_or_else(PoisonError::into_inner);
+    stores.retain(|store| store.strong_count() > 0);
+    for store in stores.iter().filter_map(Weak::upgrade) {
+        let mut state = store.lock();
+        state.generation += 1;
+        f(&mut state);
+    }
+}
+
+// ============================================================================
+// 失效
+// ============================================================================
+
+/// Path parameter values accepted by [`invalidate`]
+///
+/// / [`invalidate`] 接受的路径参数值
+///
+/// Implemented for strings, integers and arrays, slices or vectors of them.
+///
+/// 已为字符串、整数及其数组、切片和 `Vec` 实现。
+pub trait PathValues {
+    /// The values, in the order the parameters appear in the pattern
+    /// / 按参数在模式中出现顺序排列的值
+    fn into_values(self) -> Vec<String>;
+}
+
+macro_rules! impl_path_values {
+    ($($ty:ty),*) => {
+        $(
+            impl PathValues for $ty {
+                fn into_values(self) -> Vec<String> {
+                    vec![self.to_string()]
+                }
+            }
+        )*
+    };
+}
+
+impl_path_values!(
+    &str, String, &String, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl<T: PathValues, const N: usize> PathValues for [T; N] {
+    fn into_values(self) -> Vec<String> {
+        self.into_iter().flat_map(PathValues::into_values).collect()
+    }
+}
+
+impl<T: PathValues + Clone> PathValues for &[T] {
+    fn into_values(self) -> Vec<String> {
+        self.iter()
+            .cloned()
+            .flat_map(PathValues::into_values)
+            .collect()
+    }
+}
+
+impl<T: PathValues> PathValues for Vec<T> {
+    fn into_values(self) -> Vec<String> {
+        self.into_iter().flat_map(PathValues::into_values).collect()
+    }
+}
+
+/// Drop cached responses of a route for the given path parameter values
+///
+/// / 删除某个路由在给定路径参数值下的缓存响应
+///
+/// Values are matched against the route's parameters in order; passing fewer
+/// values than the route has parameters drops every entry sharing that prefix.
+///
+/// 值按顺序与路由参数匹配；传入的值少于路由参数个数时，会删除所有共享该前缀的缓存项。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// astrea::cache::invalidate("/api/users/{id}", id);
+/// astrea::cache::invalidate("/orgs/{org}/users/{id}", [org]); // every user of the org
+/// ```
+pub fn invalidate(pattern: &str, values: impl PathValues) {
+    let values = values.into_values();
+    for_each_store(|state| {
+        state.remove_where(|key, _| {
+            key.pattern == pattern
+                && key.params.len() >= values.len()
+                && key
+                    .params
+                    .iter()
+                    .zip(&values)
+                    .all(|((_, v), want)| v == want)
+        });
+    });
+}
+
+/// Drop every cached response of a route
+/// / 删除某个路由的所有缓存响应
+pub fn invalidate_route(pattern: &str) {
+    for_each_store(|state| state.remove_where(|key, _| key.pattern == pattern));
+}
+
+/// Drop every cached response
+/// / 删除所有缓存响应
+pub fn clear() {
+    for_each_store(|state| state.remove_where(|_, _| true));
+}
+
+// ============================================================================
+// 中间件
+// ============================================================================
+
+/// Create a response cache layer with default settings
+///
+/// / 使用默认设置创建响应缓存中间件层
+///
+/// Defaults: 60 second TTL, 1024 entries, bodies up to 1 MiB.
+///
+/// 默认值：存活 60 秒，1024 个缓存项，响应体最大 1 MiB。
+#[must_use]
+pub fn layer() -> CacheLayer {
+    CacheLayer::new()
+}
+
+/// Tower layer caching `GET` responses, see [`layer`]
+/// / 缓存 `GET` 响应的 Tower 层，参见 [`layer`]
+#[derive(Clone)]
+pub struct CacheLayer {
+    store: Arc<Store>,
+    ttl: Duration,
+    max_entries: usize,
+    max_body: u64,
+    query_keys: Option<Arc<[String]>>,
+}
+
+impl CacheLayer {
+    /// Create a layer with its own, empty store
+    /// / 创建拥有独立空存储的中间件层
+    #[must_use]
+    pub fn new() -> Self {
+        let store = Arc::new(Store::default());
+        stores()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Arc::downgrade(&store));
+        Self {
+            store,
+            ttl: DEFAULT_TTL,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_body: DEFAULT_MAX_BODY,
+            query_keys: None,
+        }
+    }
+
+    /// How long an entry stays fresh / 缓存项的有效时长
+    #[must_use]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Maximum number of entries; the oldest are evicted first
+    /// / 最大缓存项数；最早的缓存项先被淘汰
+    #[must_use]
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Largest body (in bytes) that is cached / 可缓存的最大响应体（字节）
+    #[must_use]
+    pub fn max_body_size(mut self, bytes: u64) -> Self {
+        self.max_body = bytes;
+        self
+    }
+
+    /// Only these query parameters are part of the key; others are ignored
+    /// / 只有这些查询参数属于缓存键，其余参数被忽略
+    #[must_use]
+    pub fn query_keys<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.query_keys = Some(keys.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl Default for CacheLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for CacheLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheLayer")
+            .field("ttl", &self.ttl)
+            .field("max_entries", &self.max_entries)
+            .field("max_body", &self.max_body)
+            .field("query_keys", &self.query_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> tower::Layer<S> for CacheLayer {
+    type Service = CacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CacheService {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+/// Tower service produced by [`CacheLayer`]
+/// / 由 [`CacheLayer`] 生成的 Tower 服务
+#[derive(Debug, Clone)]
+pub struct CacheService<S> {
+    inner: S,
+    config: CacheLayer,
+}
+
+impl<S> tower::Service<Request> for CacheService<S>
+where
+    S: tower::Service<Request, Response = AxumResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = AxumResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<AxumResponse, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Use the service that was polled ready, leave a fresh clone behind
+        // 使用已就绪的服务，留下新的克隆
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            if request.method() != Method::GET {
+                return inner.call(request).await;
+            }
+            let (mut parts, body) = request.into_parts();
+            let Some(key) = config.key(&mut parts).await else {
+                return inner.call(Request::from_parts(parts, body)).await;
+            };
+            let request = Request::from_parts(parts, body);
+
+            // Wait for an in-flight fill of the same key, or become the filler
+            // 等待同一键正在进行的填充，或自己成为填充者
+            let sender = loop {
+                let mut waiting = {
+                    let mut state = config.store.lock();
+                    if let Some(entry) = state.lookup(&key, request.headers(), Instant::now()) {
+                        return Ok(hit(entry, request.headers()));
+                    }
+                    match state.pending.get(&key) {
+                        Some(receiver) => receiver.clone(),
+                        None => {
+                            let (sender, receiver) = watch::channel(false);
+                            state.pending.insert(key.clone(), receiver);
+                            break Flight {
+                                store: Arc::clone(&config.store),
+                                key: key.clone(),
+                                generation: state.generation,
+                                sender,
+                            };
+                        }
+                    }
+                };
+                // Resolves once the filler finishes or is dropped; an uncacheable
+                // response lets every waiter through instead of queueing them
+                // 填充者完成或被丢弃时返回；响应不可缓存时放行所有等待者，而不是让其排队
+                let _ = waiting.changed().await;
+                if *waiting.borrow() {
+                    return inner.call(request).await;
+                }
+            };
+
+            let headers = request.headers().clone();
+            let response = inner.call(request).await?;
+            Ok(config.fill(sender, &headers, response).await)
+        })
+    }
+}
+
+/// The single filler of a key; clears the pending marker and wakes waiters on drop
+/// / 某个键唯一的填充者；被丢弃时清除等待标记并唤醒等待者
+struct Flight {
+    store: Arc<Store>,
+    key: Key,
+    generation: u64,
+    /// `true` tells waiters the response could not be cached
+    /// / `true` 表示响应无法缓存
+    sender: watch::Sender<bool>,
+}
+
+impl Drop for Flight {
+    fn drop(&mut self) {
+        self.store.lock().pending.remove(&self.key);
+    }
+}
+
+impl CacheLayer {
+    async fn key(&self, parts: &mut axum::http::request::Parts) -> Option<Key> {
+        let pattern = parts.extensions.get::<MatchedPath>()?.as_str().to_string();
+        let params = RawPathParams::from_request_parts(parts, &())
+            .await
+            .ok()?
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        let mut query: Vec<(String, String)> =
+            serde_urlencoded::from_str(parts.uri.query().unwrap_or_default()).unwrap_or_default();
+        if let Some(keys) = &self.query_keys {
+            query.retain(|(name, _)| keys.contains(name));
+        }
+        query.sort();
+        Some(Key {
+            pattern,
+            params,
+            query,
+        })
+    }
+
+    async fn fill(
+        &self,
+        flight: Flight,
+        request_headers: &HeaderMap,
+        response: AxumResponse,
+    ) -> AxumResponse {
+        let (mut parts, body) = response.into_parts();
+        let size = body.size_hint().exact();
+        let shared = shared(&parts.headers);
+        if !cacheable(parts.status, &parts.headers)
+            || (has_credentials(request_headers) && !shared)
+            || size.is_none_or(|n| n > self.max_body)
+        {
+            flight.sender.send_replace(true);
+            return AxumResponse::from_parts(parts, body);
+        }
+        let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+            flight.sender.send_replace(true);
+            return AxumResponse::from_parts(parts, Body::empty());
+        };
+
+        let vary = vary_names(&parts.headers)
+            .into_iter()
+            .map(|name| {
+                let value = request_headers.get(&name).cloned();
+                (name, value)
+            })
+            .collect();
+        let mut stored = parts.headers.clone();
+        stored.remove(request_id::REQUEST_ID_HEADER);
+        let entry = Entry {
+            vary,
+            status: parts.status,
+            headers: stored,
+            body: bytes.clone(),
+            expires: Instant::now() + self.ttl,
+            seq: 0,
+            shared,
+        };
+        {
+            let mut state = self.store.lock();
+            if state.generation == flight.generation {
+                state.insert(flight.key.clone(), entry, self.max_entries);
+            }
+        }
+        drop(flight);
+
+        parts
+            .headers
+            .insert(CACHE_STATUS_HEADER, HeaderValue::from_static("MISS"));
+        AxumResponse::from_parts(parts, Body::from(bytes))
+    }
+}
+
+/// Names of the `Cache-Control` directives, lowercased
+/// / `Cache-Control` 指令名称（小写）
+fn directives(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|d| {
+            d.split('=')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+        })
+        .collect()
+}
+
+/// Whether a response may be stored / 响应是否可以被存储
+fn cacheable(status: StatusCode, headers: &HeaderMap) -> bool {
+    status == StatusCode::OK
+        && !headers.contains_key(header::SET_COOKIE)
+        && !directives(headers)
+            .iter()
+            .any(|d| matches!(d.as_str(), "no-store" | "no-cache" | "private"))
+        && headers
+            .get_all(header::VARY)
+            .iter()
+            .all(|v| v.to_str().is_ok_and(|v| !v.contains('*')))
+}
+
+/// Whether a response explicitly allows shared caches to reuse it
+/// / 响应是否明确允许共享缓存复用
+fn shared(headers: &HeaderMap) -> bool {
+    directives(headers)
+        .iter()
+        .any(|d| matches!(d.as_str(), "public" | "s-maxage"))
+}
+
+/// Whether the request carries credentials / 请求是否携带凭据
+fn has_credentials(headers: &HeaderMap) -> bool {
+    headers.contains_key(header::AUTHORIZATION) || headers.contains_key(header::COOKIE)
+}
+
+fn vary_names(headers: &HeaderMap) -> Vec<HeaderName> {
+    headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .filter_map(|name| HeaderName::try_from(name.trim()).ok())
+        .collect()
+}
+
+/// Build the response for a cache hit, honoring `If-None-Match` / `If-Modified-Since`
+/// / 构建缓存命中的响应，并处理 `If-None-Match` / `If-Modified-Since`
+fn hit(entry: &Entry, request_headers: &HeaderMap) -> AxumResponse {
+    let mut headers = entry.headers.clone();
+    let conditions = Conditions::from_request(&Method::GET, request_headers);
+    let (status, body) = if conditions.not_modified(&headers) {
+        headers.remove(header::CONTENT_TYPE);
+        headers.remove(header::CONTENT_LENGTH);
+        (StatusCode::NOT_MODIFIED, Body::empty())
+    } else {
+        (entry.status, Body::from(entry.body.clone()))
+    };
+    headers.insert(CACHE_STATUS_HEADER, HeaderValue::from_static("HIT"));
+    request_id::echo(&mut headers, &request_id::resolve(request_headers));
+
+    let mut response = AxumResponse::new(body);
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    response
+}
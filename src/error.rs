@@ -32,6 +32,9 @@ use axum::{
 use serde_json::json;
 use std::fmt;
 
+pub use crate::validate::FieldError;
+use crate::validate::ValidationErrors;
+
 /// Main error type for route handlers
 ///
 /// / 路由处理函数的主要错误类型
@@ -51,10 +54,15 @@ use std::fmt;
 /// - `NotFound(404)` - Resource not found / 资源未找到
 /// - `MethodNotAllowed(405)` - HTTP method not supported / 不支持的 HTTP 方法
 /// - `Conflict(409)` - Resource conflict / 资源冲突
+/// - `PreconditionFailed(412)` - Conditional request failed / 条件请求失败
+/// - `PayloadTooLarge(413)` - Request body too large / 请求体过大
 /// - `Validation(422)` - Validation failed / 验证失败
+/// - `ValidationFailed(422)` - Field-level validation failures / 字段级验证失败
 /// - `RateLimit(429)` - Too many requests / 请求过多
 /// - `Internal(500)` - Internal server error / 内部服务器错误
 /// - `Custom` - Custom status code / 自定义状态码
+/// - `Detailed` - Any of the above plus a code, field errors or details /
+///   上述任一错误附加错误码、字段错误或详情
 #[derive(thiserror::Error, Debug)]
 pub enum RouteError {
     /// Bad request (400) - The request was malformed or contains invalid data
@@ -87,11 +95,31 @@ pub enum RouteError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// Precondition failed (412) - `If-Match` / `If-Unmodified-Since` did not hold
+    /// / 前置条件失败 (412) - `If-Match` / `If-Unmodified-Since` 不成立
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    /// Payload too large (413) - The request body exceeds the configured limit
+    /// / 请求体过大 (413) - 请求体超过配置的限制
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
     /// Validation error (422) - The request failed validation
     /// / 验证错误 (422) - 请求验证失败
     #[error("Validation error: {0}")]
     Validation(String),
 
+    /// Validation failed (422) with one entry per failing field
+    /// / 验证失败 (422)，每个未通过的字段对应一个条目
+    ///
+    /// Produced by the `get_valid_*` extractors; the response body lists the
+    /// failures under `errors`.
+    ///
+    /// 由 `get_valid_*` 提取函数产生；响应体在 `errors` 下列出所有失败。
+    #[error("Validation error: {0}")]
+    ValidationFailed(#[from] ValidationErrors),
+
     /// Too many requests (429) - Rate limit exceeded
     /// / 请求过多 (429) - 超过速率限制
     #[error("Too many requests: {0}")]
@@ -111,6 +139,36 @@ pub enum RouteError {
     /// / 带有特定状态码的自定义错误
     #[error("Error {status}: {message}")]
     Custom { status: StatusCode, message: String },
+
+    /// Another error decorated with a machine code, field errors or JSON details
+    /// / 附加了机器可读错误码、字段错误或 JSON 详情的其他错误
+    ///
+    /// Built by [`code`](Self::code), [`field`](Self::field) and
+    /// [`details`](Self::details); status and message come from `inner`.
+    ///
+    /// 由 [`code`](Self::code)、[`field`](Self::field) 和 [`details`](Self::details)
+    /// 构建；状态码和消息取自 `inner`。
+    #[error("{inner}")]
+    Detailed {
+        inner: Box<RouteError>,
+        extra: Box<ErrorDetails>,
+    },
+}
+
+/// Structured data attached to a [`RouteError`]
+///
+/// / 附加到 [`RouteError`] 的结构化数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorDetails {
+    /// Machine-readable error code, serialized as `code`
+    /// / 机器可读的错误码，序列化为 `code`
+    pub code: Option<String>,
+    /// Field-level errors, serialized as `errors`
+    /// / 字段级错误，序列化为 `errors`
+    pub fields: Vec<FieldError>,
+    /// Arbitrary JSON, serialized as `details`
+    /// / 任意 JSON，序列化为 `details`
+    pub details: Option<serde_json::Value>,
 }
 
 impl RouteError {
@@ -184,6 +242,34 @@ impl RouteError {
         Self::Conflict(message.to_string())
     }
 
+    /// Create a new precondition failed error (412)
+    /// / 创建一个新的前置条件失败错误 (412)
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// Err(RouteError::precondition_failed("Article was edited by someone else"))
+    /// ```
+    pub fn precondition_failed<M: fmt::Display>(message: M) -> Self {
+        Self::PreconditionFailed(message.to_string())
+    }
+
+    /// Create a new payload too large error (413)
+    /// / 创建一个新的请求体过大错误 (413)
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// Err(RouteError::payload_too_large("Upload exceeds 10 MB"))
+    /// ```
+    pub fn payload_too_large<M: fmt::Display>(message: M) -> Self {
+        Self::PayloadTooLarge(message.to_string())
+    }
+
     /// Create a new validation error (422)
     /// / 创建一个新的验证错误 (422)
     ///
@@ -230,6 +316,145 @@ impl RouteError {
         }
     }
 
+    // ========================================================================
+    // 结构化错误信息
+    // ========================================================================
+
+    /// Attach a machine-readable error code
+    /// / 附加机器可读的错误码
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// Err(RouteError::conflict("Email already registered").code("email_taken"))
+    /// // {"error": "Email already registered", "status": 409, "code": "email_taken"}
+    /// ```
+    #[must_use]
+    pub fn code(self, code: impl Into<String>) -> Self {
+        self.decorate(|extra| extra.code = Some(code.into()))
+    }
+
+    /// Attach an error for a single field
+    /// / 附加单个字段的错误
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// Err(RouteError::validation("bad input")
+    ///     .field("email", "invalid_format")
+    ///     .field("age", "too_young"))
+    /// // {"error": "bad input", "status": 422,
+    /// //  "errors": [{"path": "email", "code": "invalid_format"}, ...]}
+    /// ```
+    #[must_use]
+    pub fn field(self, path: impl Into<String>, code: impl Into<String>) -> Self {
+        self.field_with_message(path, code, "")
+    }
+
+    /// Attach an error for a single field with a human-readable message
+    /// / 附加单个字段的错误及人类可读的消息
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// Err(RouteError::validation("bad input")
+    ///     .field_with_message("email", "invalid_format", "must contain '@'"))
+    /// ```
+    #[must_use]
+    pub fn field_with_message(
+        self,
+        path: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        let error = FieldError {
+            path: path.into(),
+            code: code.into(),
+            message: message.into(),
+        };
+        self.decorate(|extra| extra.fields.push(error))
+    }
+
+    /// Attach arbitrary JSON details, replacing any set before
+    /// / 附加任意 JSON 详情，覆盖之前设置的值
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// Err(RouteError::rate_limit("Slow down").details(json!({ "retry_after": 30 })))
+    /// ```
+    #[must_use]
+    pub fn details(self, details: serde_json::Value) -> Self {
+        self.decorate(|extra| extra.details = Some(details))
+    }
+
+    /// The machine-readable error code, if one was attached
+    /// / 已附加的机器可读错误码
+    #[must_use]
+    pub fn error_code(&self) -> Option<&str> {
+        match self {
+            Self::Detailed { extra, .. } => extra.code.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// All field errors, including those from [`ValidationErrors`]
+    /// / 所有字段错误，包括来自 [`ValidationErrors`] 的错误
+    #[must_use]
+    pub fn field_errors(&self) -> Vec<&FieldError> {
+        match self {
+            Self::ValidationFailed(errors) => errors.errors().iter().collect(),
+            Self::Detailed { inner, extra } => {
+                let mut fields = inner.field_errors();
+                fields.extend(&extra.fields);
+                fields
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The attached JSON details, if any
+    /// / 已附加的 JSON 详情
+    #[must_use]
+    pub fn error_details(&self) -> Option<&serde_json::Value> {
+        match self {
+            Self::Detailed { extra, .. } => extra.details.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The underlying error without any attached details
+    ///
+    /// / 去除附加信息后的底层错误
+    ///
+    /// Useful for matching on the variant of a decorated error.
+    ///
+    /// 便于对附加了信息的错误按变体进行匹配。
+    #[must_use]
+    pub fn kind(&self) -> &Self {
+        match self {
+            Self::Detailed { inner, .. } => inner,
+            other => other,
+        }
+    }
+
+    fn decorate(self, apply: impl FnOnce(&mut ErrorDetails)) -> Self {
+        let (inner, mut extra) = match self {
+            Self::Detailed { inner, extra } => (inner, extra),
+            other => (Box::new(other), Box::default()),
+        };
+        apply(&mut extra);
+        Self::Detailed { inner, extra }
+    }
+
     /// Get the HTTP status code for this error
     /// / 获取此错误的 HTTP 状态码
     #[must_use]
@@ -241,10 +466,13 @@ impl RouteError {
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
             Self::Conflict(_) => StatusCode::CONFLICT,
-            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Validation(_) | Self::ValidationFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::RateLimit(_) => StatusCode::TOO_MANY_REQUESTS,
             Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Custom { status, .. } => *status,
+            Self::Detailed { inner, .. } => inner.status_code(),
         }
     }
 
@@ -259,10 +487,14 @@ impl RouteError {
             | Self::Forbidden(msg)
             | Self::MethodNotAllowed(msg)
             | Self::Conflict(msg)
+            | Self::PreconditionFailed(msg)
+            | Self::PayloadTooLarge(msg)
             | Self::Validation(msg)
             | Self::RateLimit(msg)
             | Self::Custom { message: msg, .. } => msg.clone(),
+            Self::ValidationFailed(errors) => errors.to_string(),
             Self::Internal(e) => e.to_string(),
+            Self::Detailed { inner, .. } => inner.message(),
         }
     }
 
@@ -281,15 +513,54 @@ impl RouteError {
     }
 }
 
-impl IntoResponse for RouteError {
-    fn into_response(self) -> AxumResponse {
+impl RouteError {
+    /// Convert into a response that carries the request ID
+    ///
+    /// / 转换为携带请求 ID 的响应
+    ///
+    /// Adds `request_id` to the JSON body and sets the `X-Request-Id` header.
+    /// The `#[route]` wrapper uses this for every error a handler returns.
+    ///
+    /// 在 JSON 响应体中添加 `request_id` 并设置 `X-Request-Id` 头。
+    /// `#[route]` 包装代码对处理函数返回的所有错误使用此方法。
+    #[must_use]
+    pub fn into_response_with_request_id(self, request_id: &str) -> AxumResponse {
+        let status = self.status_code();
+        let mut body = self.body();
+        body["request_id"] = json!(request_id);
+
+        let mut response = (status, Json(body)).into_response();
+        crate::event::request_id::echo(response.headers_mut(), request_id);
+        crate::response::defaults::apply(response.headers_mut());
+        response
+    }
+
+    /// The JSON error body / JSON 错误响应体
+    fn body(&self) -> serde_json::Value {
         let status = self.status_code();
-        let body = json!({
+        let mut body = json!({
             "error": self.message(),
             "status": status.as_u16(),
         });
+        if let Some(code) = self.error_code() {
+            body["code"] = json!(code);
+        }
+        let fields = self.field_errors();
+        if !fields.is_empty() {
+            body["errors"] = json!(fields);
+        }
+        if let Some(details) = self.error_details() {
+            body["details"] = details.clone();
+        }
+        body
+    }
+}
 
-        (status, Json(body)).into_response()
+impl IntoResponse for RouteError {
+    fn into_response(self) -> AxumResponse {
+        let mut response = (self.status_code(), Json(self.body())).into_response();
+        crate::response::defaults::apply(response.headers_mut());
+        response
     }
 }
 
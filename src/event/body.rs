@@ -0,0 +1,270 @@
+//! Request body reading with size limits
+//!
+//! / 带大小限制的请求体读取
+//!
+//! The `#[route]` wrapper reads the request body through this module. By
+//! default the body is buffered into [`Event::body`](super::Event::body) up to
+//! the limit of Axum's `DefaultBodyLimit` layer, which is
+//! [`DEFAULT_BODY_LIMIT`] when no such layer is installed, and unlimited after
+//! `DefaultBodyLimit::disable()`. The limit can be changed per scope with
+//! [`Middleware::body_limit`](crate::middleware::Middleware::body_limit) or per
+//! route with `#[route(body_limit = "10MB")]`, both of which take precedence
+//! over `DefaultBodyLimit`. Exceeding it produces `413 Payload Too Large`
+//! through [`RouteError::PayloadTooLarge`].
+//!
+//! `#[route]` 包装函数通过此模块读取请求体。默认情况下请求体会被缓冲到
+//! [`Event::body`](super::Event::body)，上限取自 Axum 的 `DefaultBodyLimit` 层：未安装该层时为
+//! [`DEFAULT_BODY_LIMIT`]，使用 `DefaultBodyLimit::disable()` 后不限制。
+//! 可以通过 [`Middleware::body_limit`](crate::middleware::Middleware::body_limit)
+//! 按作用域修改，或通过 `#[route(body_limit = "10MB")]` 按路由修改，两者都优先于 `DefaultBodyLimit`。
+//! 超过限制会通过 [`RouteError::PayloadTooLarge`] 返回 `413 Payload Too Large`。
+//!
+//! With `#[route(body = "stream")]` the body is not buffered at all; the handler
+//! reads it chunk by chunk with [`get_body_stream`](crate::extract::get_body_stream).
+//!
+//! 使用 `#[route(body = "stream")]` 时请求体完全不会被缓冲；处理函数通过
+//! [`get_body_stream`](crate::extract::get_body_stream) 逐块读取。
+
+use axum::RequestExt;
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{HeaderMap, header};
+use bytes::{Bytes, BytesMut};
+use http_body::Body as _;
+
+use crate::error::{Result, RouteError};
+
+/// Default request body limit (2 MiB, Axum's default when no `DefaultBodyLimit` layer is set)
+/// / 默认请求体限制（2 MiB，即未设置 `DefaultBodyLimit` 层时 Axum 的默认值）
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Body limit set by the nearest middleware scope, stored as a request extension
+///
+/// / 由最近的中间件作用域设置的请求体限制，以请求扩展形式存储
+///
+/// Inserted by the generated router; read by the `#[route]` wrapper.
+///
+/// 由生成的路由器插入，由 `#[route]` 包装函数读取。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimit(pub usize);
+
+/// Resolve Astrea's own body limit for a request
+///
+/// / 解析请求的 Astrea 自身请求体限制
+///
+/// The route's own limit wins over the scope's. `None` leaves the limit to
+/// Axum's `DefaultBodyLimit`, see [`limited_body`].
+///
+/// 路由自身的限制优先于作用域限制。`None` 表示交由 Axum 的 `DefaultBodyLimit` 决定，
+/// 参见 [`limited_body`]。
+#[must_use]
+pub fn resolve_limit(
+    route_limit: Option<usize>,
+    extensions: &axum::http::Extensions,
+) -> Option<usize> {
+    route_limit.or_else(|| extensions.get::<BodyLimit>().map(|l| l.0))
+}
+
+/// Take a request's body together with the limit to read it under
+///
+/// / 取出请求体及其读取限制
+///
+/// With an Astrea limit the body is returned as is. Without one the body is
+/// limited by Axum's `DefaultBodyLimit` ([`DEFAULT_BODY_LIMIT`] when no layer
+/// is installed), which then enforces the limit while the body is read, so the
+/// returned limit is `usize::MAX`.
+///
+/// 有 Astrea 限制时原样返回请求体。没有时由 Axum 的 `DefaultBodyLimit` 限制请求体
+/// （未安装该层时为 [`DEFAULT_BODY_LIMIT`]），在读取时执行限制，因此返回的限制为 `usize::MAX`。
+pub fn limited_body(request: Request, limit: Option<usize>) -> (Body, usize) {
+    match limit {
+        Some(limit) => (request.into_body(), limit),
+        None => (request.into_limited_body(), usize::MAX),
+    }
+}
+
+/// Whether a body error comes from Axum's `DefaultBodyLimit`
+/// / 请求体错误是否来自 Axum 的 `DefaultBodyLimit`
+pub(crate) fn is_length_limit(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if error.is::<http_body_util::LengthLimitError>() {
+            return true;
+        }
+        source = error.source();
+    }
+    false
+}
+
+/// Buffer a request body, failing with 413 once it exceeds `limit` bytes
+///
+/// / 缓冲请求体，超过 `limit` 字节时返回 413
+///
+/// A `Content-Length` above the limit is rejected before reading anything.
+///
+/// 如果 `Content-Length` 超过限制，会在读取前直接拒绝。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// - `RouteError::PayloadTooLarge` if the body exceeds `limit`
+///   请求体超过 `limit` 时返回 `RouteError::PayloadTooLarge`
+/// - `RouteError::BadRequest` if the body cannot be read
+///   无法读取请求体时返回 `RouteError::BadRequest`
+pub async fn read_body(headers: &HeaderMap, body: Body, limit: usize) -> Result<Bytes> {
+    check_content_length(headers, limit)?;
+    let mut stream = BodyStream::new(body, limit);
+    let mut buffer = BytesMut::new();
+    while let Some(chunk) = stream.chunk().await? {
+        buffer.extend_from_slice(&chunk);
+    }
+    Ok(buffer.freeze())
+}
+
+/// Reject a request whose declared `Content-Length` exceeds `limit`
+///
+/// / 拒绝声明的 `Content-Length` 超过 `limit` 的请求
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::PayloadTooLarge` if `Content-Length` exceeds `limit`.
+///
+/// 如果 `Content-Length` 超过 `limit`，返回 `RouteError::PayloadTooLarge`。
+pub fn check_content_length(headers: &HeaderMap, limit: usize) -> Result<()> {
+    let length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    match length {
+        Some(len) if len > limit as u64 => Err(too_large(limit)),
+        _ => Ok(()),
+    }
+}
+
+fn too_large(limit: usize) -> RouteError {
+    RouteError::payload_too_large(format!("Request body exceeds the limit of {limit} bytes"))
+}
+
+/// An unbuffered request body, read chunk by chunk under a size limit
+///
+/// / 未缓冲的请求体，在大小限制下逐块读取
+pub struct BodyStream {
+    body: Body,
+    limit: usize,
+    read: usize,
+}
+
+impl BodyStream {
+    /// Wrap a body with a size limit
+    /// / 使用大小限制包装请求体
+    #[must_use]
+    pub fn new(body: Body, limit: usize) -> Self {
+        Self {
+            body,
+            limit,
+            read: 0,
+        }
+    }
+
+    /// The size limit in bytes
+    /// / 大小限制（字节）
+    #[must_use]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Read the next chunk, or `None` at the end of the body
+    ///
+    /// / 读取下一个数据块，请求体结束时返回 `None`
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// - `RouteError::PayloadTooLarge` once more than `limit` bytes have been read
+    ///   读取超过 `limit` 字节时返回 `RouteError::PayloadTooLarge`
+    /// - `RouteError::BadRequest` if the connection fails mid-body
+    ///   连接在读取中途失败时返回 `RouteError::BadRequest`
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>> {
+        loop {
+            let frame =
+                std::future::poll_fn(|cx| std::pin::Pin::new(&mut self.body).poll_frame(cx))
+                    .await
+                    .transpose()
+                    .map_err(|e| {
+                        if is_length_limit(&e) {
+                            RouteError::payload_too_large(
+                                "Request body exceeds the limit set by DefaultBodyLimit",
+                            )
+                        } else {
+                            RouteError::bad_request(format!("Failed to read request body: {e}"))
+                        }
+                    })?;
+            let Some(frame) = frame else {
+                return Ok(None);
+            };
+            // Skip trailers / 跳过 trailers
+            let Ok(data) = frame.into_data() else {
+                continue;
+            };
+            self.read += data.len();
+            if self.read > self.limit {
+                return Err(too_large(self.limit));
+            }
+            return Ok(Some(data));
+        }
+    }
+
+    /// Read the rest of the body into memory
+    ///
+    /// / 将剩余的请求体读入内存
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Same as [`chunk`](Self::chunk).
+    ///
+    /// 与 [`chunk`](Self::chunk) 相同。
+    pub async fn bytes(mut self) -> Result<Bytes> {
+        let mut buffer = BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+        }
+        Ok(buffer.freeze())
+    }
+
+    /// Split into the raw body and its limit
+    /// / 拆分为原始请求体及其限制
+    pub(crate) fn into_parts(self) -> (Body, usize) {
+        (self.body, self.limit)
+    }
+}
+
+/// Where the handler's body stream comes from
+/// / 处理函数请求体流的来源
+#[derive(Debug, Default)]
+pub(crate) enum BodySlot {
+    /// The body was buffered into `Event::body`
+    /// / 请求体已缓冲到 `Event::body`
+    #[default]
+    Buffered,
+    /// An unread stream from `#[route(body = "stream")]`
+    /// / 来自 `#[route(body = "stream")]` 的未读取流
+    Pending(BodyStream),
+    /// The stream was already handed out
+    /// / 流已被取出
+    Taken,
+}
+
+impl std::fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BodyStream")
+            .field("limit", &self.limit)
+            .field("read", &self.read)
+            .finish()
+    }
+}
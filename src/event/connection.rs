@@ -0,0 +1,325 @@
+//! Connection information and proxy-aware client addresses
+//!
+//! / 连接信息与支持代理的客户端地址
+//!
+//! The `#[route]` wrapper records the peer address (from Axum's `ConnectInfo`)
+//! and the HTTP version of every request. Forwarding headers are only honored
+//! when the peer is listed in [`TrustedProxies`], which is installed as a
+//! request extension. Only the header family the proxies set is read —
+//! `X-Forwarded-*` by default, or `Forwarded` with
+//! [`TrustedProxies::header`] — because a proxy passes the other one through
+//! from the client untouched:
+//!
+//! `#[route]` 包装函数会记录每个请求的对端地址（来自 Axum 的 `ConnectInfo`）和
+//! HTTP 版本。只有当对端位于 [`TrustedProxies`] 中时，才会采信转发头。
+//! [`TrustedProxies`] 以请求扩展的形式安装。只读取代理设置的那一类请求头——默认为
+//! `X-Forwarded-*`，或通过 [`TrustedProxies::header`] 选择 `Forwarded`——因为代理会原样
+//! 透传客户端发送的另一类请求头：
+//!
+//! ```rust,ignore
+//! // src/routes/_middleware.rs
+//! pub fn middleware() -> Middleware {
+//!     Middleware::new().wrap(|router| {
+//!         router.layer(astrea::axum::Extension(TrustedProxies::new(["10.0.0.0/8", "127.0.0.1"])))
+//!     })
+//! }
+//!
+//! // A proxy that sets `Forwarded` (RFC 7239) instead
+//! // 改为设置 `Forwarded`（RFC 7239）的代理
+//! pub fn middleware() -> Middleware {
+//!     Middleware::new().wrap(|router| {
+//!         let proxies = TrustedProxies::new(["10.0.0.0/8"]).header(ForwardedHeader::Forwarded);
+//!         router.layer(astrea::axum::Extension(proxies))
+//!     })
+//! }
+//!
+//! // main.rs — the peer address is only known with connect info
+//! // main.rs — 只有启用 connect info 才能获得对端地址
+//! axum::serve(
+//!     listener,
+//!     app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+//! )
+//! .await?;
+//! ```
+
+use axum::extract::ConnectInfo;
+use axum::extract::connect_info::MockConnectInfo;
+use axum::http::{Extensions, HeaderMap, Version};
+use std::net::{IpAddr, SocketAddr};
+
+/// The forwarding headers a trusted proxy sets
+///
+/// / 受信任代理设置的转发头
+///
+/// The two families are never mixed: the other one reaches the application
+/// exactly as the client sent it.
+///
+/// 两类请求头从不混用：另一类会原样保留客户端发送的内容到达应用。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ForwardedHeader {
+    /// `X-Forwarded-For`, `X-Forwarded-Proto` and `X-Forwarded-Host` (default)
+    /// / `X-Forwarded-For`、`X-Forwarded-Proto` 和 `X-Forwarded-Host`（默认）
+    #[default]
+    XForwarded,
+    /// `Forwarded: for=...;proto=...;host=...` (RFC 7239)
+    /// / `Forwarded: for=...;proto=...;host=...`（RFC 7239）
+    Forwarded,
+}
+
+/// Proxies whose forwarding headers are trusted, as addresses or CIDR ranges
+///
+/// / 被信任其转发头的代理，以地址或 CIDR 网段表示
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let proxies = TrustedProxies::new(["127.0.0.1", "10.0.0.0/8", "fd00::/8"]);
+/// let proxies = proxies.header(ForwardedHeader::Forwarded);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+    header: ForwardedHeader,
+}
+
+impl TrustedProxies {
+    /// Build the list from addresses (`10.0.0.1`) or CIDR ranges (`10.0.0.0/8`)
+    /// / 从地址（`10.0.0.1`）或 CIDR 网段（`10.0.0.0/8`）构建列表
+    ///
+    /// # Panics
+    ///
+    /// # 恐慌
+    ///
+    /// Panics if an entry is not a valid address or range. Proxies are fixed
+    /// configuration, so a typo should stop the application at startup.
+    ///
+    /// 如果某项不是有效的地址或网段则会 panic。代理属于固定配置，
+    /// 拼写错误应在启动时就终止应用。
+    #[must_use]
+    pub fn new<I, S>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let networks = entries
+            .into_iter()
+            .map(|entry| {
+                let entry = entry.as_ref();
+                parse_network(entry)
+                    .unwrap_or_else(|| panic!("Invalid trusted proxy address: {entry}"))
+            })
+            .collect();
+        Self {
+            networks,
+            header: ForwardedHeader::default(),
+        }
+    }
+
+    /// Choose the forwarding headers the proxies set (default `X-Forwarded-*`)
+    /// / 选择代理设置的转发头（默认为 `X-Forwarded-*`）
+    #[must_use]
+    pub fn header(mut self, header: ForwardedHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Check whether an address belongs to a trusted proxy
+    /// / 检查地址是否属于受信任的代理
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = canonical(ip);
+        self.networks
+            .iter()
+            .any(|&(network, prefix)| in_network(ip, network, prefix))
+    }
+}
+
+fn parse_network(entry: &str) -> Option<(IpAddr, u8)> {
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+        None => (entry, None),
+    };
+    let addr = canonical(addr.trim().parse::<IpAddr>().ok()?);
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max);
+    (prefix <= max).then_some((addr, prefix))
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Treat IPv4-mapped IPv6 addresses (`::ffff:1.2.3.4`) as IPv4
+/// / 将 IPv4 映射的 IPv6 地址（`::ffff:1.2.3.4`）视为 IPv4
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    }
+}
+
+/// Per-connection data captured by the `#[route]` wrapper
+///
+/// / 由 `#[route]` 包装函数捕获的连接数据
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionInfo {
+    /// Peer address, if the server was started with connect info
+    /// / 对端地址（服务器启用 connect info 时可用）
+    pub remote_addr: Option<SocketAddr>,
+    /// HTTP version of the request
+    /// / 请求的 HTTP 版本
+    pub version: Version,
+    /// Proxies whose forwarding headers are trusted
+    /// / 被信任其转发头的代理
+    pub trusted_proxies: Option<TrustedProxies>,
+}
+
+impl ConnectionInfo {
+    /// Capture connection data from a request's version and extensions
+    /// / 从请求的版本和扩展中捕获连接数据
+    #[must_use]
+    pub fn from_parts(version: Version, extensions: &Extensions) -> Self {
+        Self {
+            remote_addr: extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|info| info.0)
+                .or_else(|| {
+                    extensions
+                        .get::<MockConnectInfo<SocketAddr>>()
+                        .map(|info| info.0)
+                }),
+            version,
+            trusted_proxies: extensions.get::<TrustedProxies>().cloned(),
+        }
+    }
+
+    /// Whether the direct peer is a trusted proxy
+    /// / 直接对端是否为受信任的代理
+    #[must_use]
+    pub fn peer_is_trusted(&self) -> bool {
+        match (&self.trusted_proxies, self.remote_addr) {
+            (Some(proxies), Some(addr)) => proxies.contains(addr.ip()),
+            _ => false,
+        }
+    }
+
+    /// Resolve the originating client address
+    ///
+    /// / 解析原始客户端地址
+    ///
+    /// Walks the forwarding chain from the nearest hop outwards and returns the
+    /// first address that is not a trusted proxy. The chain comes from the
+    /// header chosen with [`TrustedProxies::header`] only.
+    ///
+    /// 从最近的一跳向外遍历转发链，返回第一个不属于受信任代理的地址。
+    /// 转发链只来自通过 [`TrustedProxies::header`] 选择的请求头。
+    #[must_use]
+    pub fn client_ip(&self, headers: &HeaderMap) -> Option<IpAddr> {
+        let peer = canonical(self.remote_addr?.ip());
+        let Some(proxies) = self.trusted_proxies.as_ref().filter(|p| p.contains(peer)) else {
+            return Some(peer);
+        };
+
+        let chain = match proxies.header {
+            ForwardedHeader::XForwarded => header_list(headers, "x-forwarded-for"),
+            ForwardedHeader::Forwarded => forwarded_values(headers, "for"),
+        };
+
+        let mut client = peer;
+        for hop in chain.iter().rev() {
+            let Some(ip) = parse_node(hop) else {
+                // Unknown or obfuscated hop: stop at the last trusted address
+                // 未知或混淆的节点：停在最后一个可信地址
+                break;
+            };
+            client = ip;
+            if !proxies.contains(ip) {
+                break;
+            }
+        }
+        Some(client)
+    }
+
+    /// A forwarded value reported by a trusted proxy
+    ///
+    /// / 由受信任代理报告的转发值
+    ///
+    /// Reads the `Forwarded` parameter or the `X-Forwarded-*` header, whichever
+    /// the proxies are configured to set, taking the entry added by the nearest
+    /// proxy.
+    ///
+    /// 读取代理配置设置的 `Forwarded` 参数或 `X-Forwarded-*` 头，取最近代理添加的条目。
+    pub(crate) fn forwarded<'a>(
+        &self,
+        headers: &'a HeaderMap,
+        param: &str,
+        header: &str,
+    ) -> Option<&'a str> {
+        if !self.peer_is_trusted() {
+            return None;
+        }
+        let values = match self.trusted_proxies.as_ref()?.header {
+            ForwardedHeader::XForwarded => header_list(headers, header),
+            ForwardedHeader::Forwarded => forwarded_values(headers, param),
+        };
+        values.last().copied().filter(|v| !v.is_empty())
+    }
+}
+
+/// Values of one parameter across every `Forwarded` element, in order
+/// / 所有 `Forwarded` 元素中某个参数的值，按顺序排列
+fn forwarded_values<'a>(headers: &'a HeaderMap, param: &str) -> Vec<&'a str> {
+    headers
+        .get_all("forwarded")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|element| {
+            element.split(';').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                key.trim()
+                    .eq_ignore_ascii_case(param)
+                    .then(|| value.trim().trim_matches('"'))
+            })
+        })
+        .collect()
+}
+
+/// Comma-separated values across every instance of a header, in order
+/// / 某个请求头所有实例中以逗号分隔的值，按顺序排列
+fn header_list<'a>(headers: &'a HeaderMap, name: &str) -> Vec<&'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect()
+}
+
+/// Parse a forwarding node: `1.2.3.4`, `1.2.3.4:80`, `[::1]` or `[::1]:80`
+/// / 解析转发节点：`1.2.3.4`、`1.2.3.4:80`、`[::1]` 或 `[::1]:80`
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Ok(ip) = node.parse::<IpAddr>() {
+        return Some(canonical(ip));
+    }
+    if let Some(rest) = node.strip_prefix('[') {
+        let (addr, _) = rest.split_once(']')?;
+        return addr.parse::<IpAddr>().ok().map(canonical);
+    }
+    node.parse::<SocketAddr>()
+        .ok()
+        .map(|addr| canonical(addr.ip()))
+}
@@ -2,11 +2,17 @@
 //!
 //! / 内部事件数据结构
 
-use axum::http::{HeaderMap, Method, Uri};
+use axum::http::{HeaderMap, Method, Uri, header};
+use cookie::{Cookie, CookieJar};
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::body::BodySlot;
+use super::connection::ConnectionInfo;
 
 use crate::error::{Result, RouteError};
+use crate::router::RouteInfo;
 
 /// Inner event data shared via Arc
 ///
@@ -36,6 +42,21 @@ pub struct EventInner {
     /// Lazy cached query parameters
     /// / 延迟缓存的查询参数
     pub query: OnceCell<HashMap<String, String>>,
+    /// Lazy cached request cookies
+    /// / 延迟缓存的请求 Cookie
+    pub cookies: OnceCell<CookieJar>,
+    /// Unbuffered request body, if the route streams it
+    /// / 未缓冲的请求体（路由使用流式读取时）
+    pub(crate) body_stream: Mutex<BodySlot>,
+    /// Peer address, HTTP version and trusted proxies
+    /// / 对端地址、HTTP 版本和受信任代理
+    pub connection: OnceCell<ConnectionInfo>,
+    /// Request ID (from `X-Request-Id` or generated)
+    /// / 请求 ID（来自 `X-Request-Id` 或自动生成）
+    pub request_id: OnceCell<String>,
+    /// File-derived route pattern and source file
+    /// / 由文件派生的路由模式和源文件
+    pub route: OnceCell<RouteInfo>,
 }
 
 impl EventInner {
@@ -55,6 +76,11 @@ impl EventInner {
             headers,
             params: OnceCell::from(params),
             query: OnceCell::from(query),
+            cookies: OnceCell::new(),
+            body_stream: Mutex::new(BodySlot::Buffered),
+            connection: OnceCell::new(),
+            request_id: OnceCell::new(),
+            route: OnceCell::new(),
         }
     }
 
@@ -78,6 +104,16 @@ impl EventInner {
         &self.headers
     }
 
+    /// Get connection info (defaults when the wrapper did not record any)
+    pub fn connection(&self) -> &ConnectionInfo {
+        self.connection.get_or_init(ConnectionInfo::default)
+    }
+
+    /// Get the request ID (generated on first access if none was recorded)
+    pub fn request_id(&self) -> &str {
+        self.request_id.get_or_init(super::request_id::generate)
+    }
+
     /// Get path parameters (lazy cached)
     pub fn params(&self) -> &HashMap<String, String> {
         self.params.get_or_init(HashMap::new)
@@ -93,6 +129,24 @@ impl EventInner {
         })
     }
 
+    /// Get request cookies (lazy cached)
+    ///
+    /// Parses every `Cookie` header; malformed pairs are skipped.
+    pub fn cookies(&self) -> &CookieJar {
+        self.cookies.get_or_init(|| {
+            let mut jar = CookieJar::new();
+            for value in self.headers.get_all(header::COOKIE) {
+                let Ok(value) = value.to_str() else {
+                    continue;
+                };
+                for cookie in Cookie::split_parse_encoded(value).flatten() {
+                    jar.add_original(cookie.into_owned());
+                }
+            }
+            jar
+        })
+    }
+
     /// Parse JSON body from bytes
     pub fn parse_json<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
         serde_json::from_slice(bytes)
@@ -48,13 +48,21 @@
 //! }
 //! ```
 
-use axum::http::{HeaderMap, Method, Uri};
+use axum::http::{HeaderMap, Method, Uri, Version, header};
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
+pub mod body;
+pub mod connection;
 pub mod inner;
 pub mod parse;
+pub mod request_id;
 
+pub use body::BodyStream;
+pub use connection::{ConnectionInfo, ForwardedHeader, TrustedProxies};
+
+use crate::router::RouteInfo;
 pub use inner::EventInner;
 
 /// Request event containing all request information
@@ -90,8 +98,8 @@ pub struct Event {
     /// Application state (type-erased, stored as Arc<dyn Any + Send + Sync>)
     /// / 应用状态（类型擦除，存储为 Arc<dyn Any + Send + Sync>）
     pub state: Option<std::sync::Arc<dyn std::any::Any + Send + Sync>>,
-    /// Request body bytes
-    /// / 请求体字节数据
+    /// Request body bytes (empty when the route uses `#[route(body = "stream")]`)
+    /// / 请求体字节数据（路由使用 `#[route(body = "stream")]` 时为空）
     pub body: bytes::Bytes,
 }
 
@@ -133,6 +141,89 @@ impl Event {
         }
     }
 
+    /// Attach an unbuffered request body
+    ///
+    /// / 附加未缓冲的请求体
+    ///
+    /// Called by the `#[route(body = "stream")]` wrapper instead of filling
+    /// [`body`](Self::body).
+    ///
+    /// 由 `#[route(body = "stream")]` 包装代码调用，用于替代填充 [`body`](Self::body)。
+    #[must_use]
+    pub fn with_body_stream(self, stream: BodyStream) -> Self {
+        *lock_slot(&self.inner) = body::BodySlot::Pending(stream);
+        self
+    }
+
+    /// Attach connection data (peer address, HTTP version, trusted proxies)
+    ///
+    /// / 附加连接数据（对端地址、HTTP 版本、受信任代理）
+    ///
+    /// Called by the `#[route]` wrapper; only the first call has an effect.
+    ///
+    /// 由 `#[route]` 包装代码调用；只有第一次调用生效。
+    #[must_use]
+    pub fn with_connection(self, connection: ConnectionInfo) -> Self {
+        let _ = self.inner.connection.set(connection);
+        self
+    }
+
+    /// Attach the request ID
+    ///
+    /// / 附加请求 ID
+    ///
+    /// Called by the `#[route]` wrapper; only the first call has an effect.
+    ///
+    /// 由 `#[route]` 包装代码调用；只有第一次调用生效。
+    #[must_use]
+    pub fn with_request_id(self, request_id: impl Into<String>) -> Self {
+        let _ = self.inner.request_id.set(request_id.into());
+        self
+    }
+
+    /// Attach the matched route's pattern and source file
+    ///
+    /// / 附加匹配路由的模式和源文件
+    ///
+    /// Called by the `#[route]` wrapper; only the first call has an effect.
+    ///
+    /// 由 `#[route]` 包装代码调用；只有第一次调用生效。
+    #[must_use]
+    pub fn with_route(self, route: RouteInfo) -> Self {
+        let _ = self.inner.route.set(route);
+        self
+    }
+
+    /// Take the request body as a stream
+    ///
+    /// / 以流的形式取出请求体
+    ///
+    /// For streaming routes this hands out the unread body once and returns
+    /// `None` afterwards. For buffered routes it returns a stream over
+    /// [`body`](Self::body) every time.
+    ///
+    /// 对于流式路由，只会交出一次未读取的请求体，之后返回 `None`。
+    /// 对于缓冲路由，每次都返回基于 [`body`](Self::body) 的流。
+    ///
+    /// Note: For a `Result`-returning version, use
+    /// [`get_body_stream`](crate::extract::get_body_stream).
+    ///
+    /// 注意：需要返回 `Result` 的版本请使用 [`get_body_stream`](crate::extract::get_body_stream)。
+    pub fn take_body_stream(&self) -> Option<BodyStream> {
+        let mut slot = lock_slot(&self.inner);
+        match std::mem::replace(&mut *slot, body::BodySlot::Taken) {
+            body::BodySlot::Buffered => {
+                *slot = body::BodySlot::Buffered;
+                Some(BodyStream::new(
+                    axum::body::Body::from(self.body.clone()),
+                    usize::MAX,
+                ))
+            }
+            body::BodySlot::Pending(stream) => Some(stream),
+            body::BodySlot::Taken => None,
+        }
+    }
+
     /// Get the HTTP method
     ///
     /// / 获取 HTTP 方法
@@ -195,6 +286,170 @@ impl Event {
         self.inner.headers()
     }
 
+    /// Get the matched route pattern
+    ///
+    /// / 获取匹配的路由模式
+    ///
+    /// Unlike [`path`](Self::path) this is the file-derived pattern
+    /// (`/api/users/{id}`), suitable as a low-cardinality key for metrics,
+    /// logging and authorization policies. `None` when the handler was not
+    /// mounted by `generate_routes!`.
+    ///
+    /// 与 [`path`](Self::path) 不同，这是由文件派生的模式（`/api/users/{id}`），
+    /// 适合作为指标、日志和授权策略的低基数键。处理函数不是由 `generate_routes!`
+    /// 挂载时返回 `None`。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// // GET /api/users/42
+    /// assert_eq!(event.route_pattern(), Some("/api/users/{id}"));
+    /// assert_eq!(event.path(), "/api/users/42");
+    /// ```
+    #[must_use]
+    pub fn route_pattern(&self) -> Option<&'static str> {
+        self.inner.route.get().map(|route| route.pattern)
+    }
+
+    /// Get the route file that handles this request
+    ///
+    /// / 获取处理此请求的路由文件
+    ///
+    /// Relative to the crate root, e.g. `src/routes/api/users/[id].get.rs`.
+    ///
+    /// 相对于 crate 根目录，如 `src/routes/api/users/[id].get.rs`。
+    #[must_use]
+    pub fn route_source_file(&self) -> Option<&'static str> {
+        self.inner.route.get().map(|route| route.source_file)
+    }
+
+    /// Get the request ID
+    ///
+    /// / 获取请求 ID
+    ///
+    /// Taken from the `X-Request-Id` request header when present, otherwise
+    /// generated. The same ID is echoed in the `X-Request-Id` response header
+    /// and in error bodies, and recorded on the request's tracing span.
+    ///
+    /// 存在 `X-Request-Id` 请求头时取自该头，否则自动生成。同一 ID 会在
+    /// `X-Request-Id` 响应头和错误响应体中回显，并记录到请求的 tracing span 中。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// audit_log.write(event.request_id(), "user deleted").await?;
+    /// ```
+    #[must_use]
+    pub fn request_id(&self) -> &str {
+        self.inner.request_id()
+    }
+
+    /// Get the HTTP version of the request
+    /// / 获取请求的 HTTP 版本
+    #[must_use]
+    pub fn version(&self) -> Version {
+        self.inner.connection().version
+    }
+
+    /// Get the address of the directly connected peer
+    ///
+    /// / 获取直接连接的对端地址
+    ///
+    /// Requires the server to be started with
+    /// `into_make_service_with_connect_info::<SocketAddr>()`; returns `None`
+    /// otherwise. Behind a proxy this is the proxy's address, see
+    /// [`client_ip`](Self::client_ip).
+    ///
+    /// 需要服务器使用 `into_make_service_with_connect_info::<SocketAddr>()` 启动，
+    /// 否则返回 `None`。位于代理之后时这是代理的地址，参见 [`client_ip`](Self::client_ip)。
+    #[must_use]
+    pub fn remote_addr(&self) -> Option<SocketAddr> {
+        self.inner.connection().remote_addr
+    }
+
+    /// Get the originating client IP
+    ///
+    /// / 获取原始客户端 IP
+    ///
+    /// `X-Forwarded-For` (or `Forwarded`, see [`TrustedProxies::header`]) is
+    /// only honored when the peer is one of the configured [`TrustedProxies`];
+    /// otherwise this is the peer's IP. Returns `None` when the peer address is
+    /// unknown.
+    ///
+    /// 只有当对端属于已配置的 [`TrustedProxies`] 时才采信 `X-Forwarded-For`（或
+    /// `Forwarded`，参见 [`TrustedProxies::header`]），否则返回对端 IP。对端地址未知时返回 `None`。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// let ip = event.client_ip().map(|ip| ip.to_string()).unwrap_or_default();
+    /// tracing::info!(%ip, "login attempt");
+    /// ```
+    #[must_use]
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.inner.connection().client_ip(self.headers())
+    }
+
+    /// Get the scheme the client used (`http` or `https`)
+    ///
+    /// / 获取客户端使用的协议（`http` 或 `https`）
+    ///
+    /// A trusted proxy's `X-Forwarded-Proto` (or `Forwarded: proto=`, see
+    /// [`TrustedProxies::header`]) wins over the request URI; defaults to `http`.
+    ///
+    /// 受信任代理的 `X-Forwarded-Proto`（或 `Forwarded: proto=`）优先于请求 URI；
+    /// 默认为 `http`。
+    #[must_use]
+    pub fn scheme(&self) -> &str {
+        self.inner
+            .connection()
+            .forwarded(self.headers(), "proto", "x-forwarded-proto")
+            .or_else(|| self.uri().scheme_str())
+            .unwrap_or("http")
+    }
+
+    /// Get the host the client requested, including any port
+    ///
+    /// / 获取客户端请求的主机（包括端口）
+    ///
+    /// A trusted proxy's `X-Forwarded-Host` (or `Forwarded: host=`, see
+    /// [`TrustedProxies::header`]) wins over the `Host` header, which wins over
+    /// the URI authority (HTTP/2).
+    ///
+    /// 受信任代理的 `X-Forwarded-Host`（或 `Forwarded: host=`）优先于 `Host` 头，
+    /// `Host` 头优先于 URI authority（HTTP/2）。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// let host = event.host().unwrap_or("localhost");
+    /// let url = format!("{}://{host}/users/{id}", event.scheme());
+    /// ```
+    #[must_use]
+    pub fn host(&self) -> Option<&str> {
+        self.inner
+            .connection()
+            .forwarded(self.headers(), "host", "x-forwarded-host")
+            .or_else(|| {
+                self.headers()
+                    .get(header::HOST)
+                    .and_then(|v| v.to_str().ok())
+            })
+            .or_else(|| {
+                self.uri()
+                    .authority()
+                    .map(axum::http::uri::Authority::as_str)
+            })
+    }
+
     /// Get path parameters (lazy cached)
     ///
     /// / 获取路径参数（延迟缓存）
@@ -231,6 +486,34 @@ impl Event {
         self.inner.query()
     }
 
+    /// Get request cookies (lazy cached)
+    ///
+    /// / 获取请求 Cookie（延迟缓存）
+    ///
+    /// All `Cookie` headers are parsed on first access and cached in a
+    /// [`CookieJar`](cookie::CookieJar). Values are percent-decoded.
+    ///
+    /// 所有 `Cookie` 请求头在首次访问时解析并缓存到 [`CookieJar`](cookie::CookieJar) 中。
+    /// 值会进行百分号解码。
+    ///
+    /// Note: For more convenient access, use [`get_cookie`](crate::extract::get_cookie).
+    ///
+    /// 注意：为了更方便的访问，请使用 [`get_cookie`](crate::extract::get_cookie)。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// for cookie in event.cookies().iter() {
+    ///     tracing::debug!("{} = {}", cookie.name(), cookie.value());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn cookies(&self) -> &cookie::CookieJar {
+        self.inner.cookies()
+    }
+
     /// Get a value from the application state
     ///
     /// / 从应用状态获取值
@@ -367,3 +650,10 @@ impl Event {
         self.inner.parse_text(bytes)
     }
 }
+
+fn lock_slot(inner: &EventInner) -> std::sync::MutexGuard<'_, body::BodySlot> {
+    inner
+        .body_stream
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
@@ -0,0 +1,101 @@
+//! Request IDs
+//!
+//! / 请求 ID
+//!
+//! Every request handled by a `#[route]` function gets an ID: the incoming
+//! `X-Request-Id` header when it looks sane, otherwise a freshly generated
+//! UUID-formatted value. The ID is available as
+//! [`Event::request_id`](super::Event::request_id), recorded on the request's
+//! tracing span, echoed in the `X-Request-Id` response header and included in
+//! JSON error bodies as `request_id`.
+//!
+//! 每个由 `#[route]` 函数处理的请求都有一个 ID：如果传入的 `X-Request-Id` 头合法则使用它，
+//! 否则生成一个 UUID 格式的新值。该 ID 可通过 [`Event::request_id`](super::Event::request_id)
+//! 获取，会被记录到请求的 tracing span 中，在 `X-Request-Id` 响应头中回显，
+//! 并以 `request_id` 字段包含在 JSON 错误响应体中。
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Header carrying the request ID in both directions
+/// / 在请求和响应中携带请求 ID 的头
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest incoming request ID that is reused as-is
+/// / 可直接复用的传入请求 ID 的最大长度
+const MAX_INCOMING_LEN: usize = 128;
+
+/// Take the request ID from `X-Request-Id`, or generate one
+///
+/// / 从 `X-Request-Id` 获取请求 ID，或生成一个新的
+///
+/// Incoming IDs are only reused when they are 1–128 visible ASCII characters,
+/// so they are safe to log and to echo back.
+///
+/// 只有由 1–128 个可见 ASCII 字符组成的传入 ID 才会被复用，
+/// 以确保其可以安全地记录日志和回显。
+#[must_use]
+pub fn resolve(headers: &HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_INCOMING_LEN
+                && id.bytes().all(|b| b.is_ascii_graphic())
+        })
+        .map_or_else(generate, str::to_string)
+}
+
+/// Generate a random UUID-formatted (version 4) request ID
+/// / 生成随机的 UUID 格式（版本 4）请求 ID
+#[must_use]
+pub fn generate() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    // RandomState is seeded randomly per process; hashing the counter and the
+    // clock gives unique, unpredictable bits without an extra dependency.
+    // RandomState 在每个进程中随机初始化；对计数器和时钟取哈希即可得到唯一且不可预测的位，
+    // 无需额外依赖。
+    let state = std::collections::hash_map::RandomState::new();
+    let mut halves = [0_u64; 2];
+    for (salt, half) in halves.iter_mut().enumerate() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(salt);
+        hasher.write_u64(count);
+        hasher.write_u128(nanos);
+        *half = hasher.finish();
+    }
+
+    let mut bits = (u128::from(halves[0]) << 64) | u128::from(halves[1]);
+    // Version 4, RFC 4122 variant / 版本 4，RFC 4122 变体
+    bits = (bits & !(0xf_u128 << 76)) | (0x4_u128 << 76);
+    bits = (bits & !(0x3_u128 << 62)) | (0x2_u128 << 62);
+
+    let hex = format!("{bits:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Set `X-Request-Id` on a response unless a handler already did
+/// / 在响应上设置 `X-Request-Id`，处理函数已设置时除外
+pub fn echo(headers: &mut HeaderMap, request_id: &str) {
+    if headers.contains_key(REQUEST_ID_HEADER) {
+        return;
+    }
+    if let Ok(value) = HeaderValue::from_str(request_id) {
+        headers.insert(REQUEST_ID_HEADER, value);
+    }
+}
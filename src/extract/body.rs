@@ -1,12 +1,169 @@
 //! Request body extraction
 //!
 //! / 请求体提取
+//!
+//! [`get_body`] picks a decoder from the request's `Content-Type`:
+//!
+//! [`get_body`] 根据请求的 `Content-Type` 选择解码器：
+//!
+//! - `application/json` and `application/*+json` → JSON
+//!   `application/json` 和 `application/*+json` → JSON
+//! - `application/x-www-form-urlencoded` → form (repeated keys and `a[b]` nesting
+//!   are supported, like [`get_query_as`](super::get_query_as))
+//!   表单（与 [`get_query_as`](super::get_query_as) 一样支持重复键和 `a[b]` 嵌套）
+//! - any media type added with [`register_body_format`]
+//!   通过 [`register_body_format`] 添加的任意媒体类型
+//!
+//! A request without `Content-Type` is parsed as JSON. Any other media type is
+//! rejected with `415 Unsupported Media Type`.
+//!
+//! 没有 `Content-Type` 的请求按 JSON 解析。其他媒体类型返回 `415 Unsupported Media Type`。
+
+use std::sync::{OnceLock, RwLock};
+
+use axum::http::{StatusCode, header};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Event,
+    error::{Result, RouteError},
+    event::BodyStream,
+    validate::Validate,
+};
+
+/// Media type for JSON bodies
+/// / JSON 请求体的媒体类型
+const JSON: &str = "application/json";
+
+/// Media type for URL-encoded form bodies
+/// / URL 编码表单请求体的媒体类型
+const FORM: &str = "application/x-www-form-urlencoded";
+
+/// Decoder for a custom body format
+///
+/// / 自定义请求体格式的解码器
+///
+/// Decodes the raw body into a `serde_json::Value`, which is then
+/// deserialized into the handler's target type.
+///
+/// 将原始请求体解码为 `serde_json::Value`，再反序列化为处理函数的目标类型。
+pub type BodyDecoder = fn(&[u8]) -> anyhow::Result<serde_json::Value>;
+
+static CUSTOM_FORMATS: OnceLock<RwLock<Vec<(&'static str, BodyDecoder)>>> = OnceLock::new();
+
+fn custom_formats() -> &'static RwLock<Vec<(&'static str, BodyDecoder)>> {
+    CUSTOM_FORMATS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// A request body format accepted by [`get_body_any`]
+///
+/// / [`get_body_any`] 接受的请求体格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    /// `application/json` (and `application/*+json`)
+    Json,
+    /// `application/x-www-form-urlencoded`
+    Form,
+    /// A media type added with [`register_body_format`]
+    /// / 通过 [`register_body_format`] 添加的媒体类型
+    Custom(&'static str),
+}
+
+impl BodyFormat {
+    /// The media type of this format
+    /// / 此格式的媒体类型
+    #[must_use]
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Self::Json => JSON,
+            Self::Form => FORM,
+            Self::Custom(media_type) => media_type,
+        }
+    }
+
+    /// Whether a `Content-Type` essence (lowercase, no parameters) matches this format
+    /// / `Content-Type` 主体（小写、无参数）是否匹配此格式
+    fn matches(&self, essence: &str) -> bool {
+        match self {
+            Self::Json => {
+                essence == JSON
+                    || (essence.starts_with("application/") && essence.ends_with("+json"))
+            }
+            Self::Form => essence == FORM,
+            Self::Custom(media_type) => essence.eq_ignore_ascii_case(media_type),
+        }
+    }
+}
+
+/// Register a decoder for an additional body media type
+///
+/// / 为额外的请求体媒体类型注册解码器
+///
+/// Registering the same media type again replaces its decoder. Register
+/// formats before calling `create_router()` so the OpenAPI spec lists them.
+///
+/// 重复注册同一媒体类型会替换其解码器。请在调用 `create_router()` 之前注册，
+/// 以便 OpenAPI 规范列出这些格式。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// fn decode_yaml(bytes: &[u8]) -> anyhow::Result<serde_json::Value> {
+///     Ok(serde_yaml::from_slice(bytes)?)
+/// }
+///
+/// astrea::extract::register_body_format("application/yaml", decode_yaml);
+/// ```
+pub fn register_body_format(media_type: &'static str, decoder: BodyDecoder) {
+    let mut formats = custom_formats().write().unwrap();
+    match formats
+        .iter_mut()
+        .find(|(m, _)| m.eq_ignore_ascii_case(media_type))
+    {
+        Some(entry) => entry.1 = decoder,
+        None => formats.push((media_type, decoder)),
+    }
+}
 
-use crate::{Event, error::Result};
+/// Get every body format accepted by [`get_body`], built-in formats first
+///
+/// / 获取 [`get_body`] 接受的所有请求体格式，内置格式在前
+#[must_use]
+pub fn body_formats() -> Vec<BodyFormat> {
+    let mut formats = vec![BodyFormat::Json, BodyFormat::Form];
+    formats.extend(
+        custom_formats()
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(m, _)| BodyFormat::Custom(m)),
+    );
+    formats
+}
 
-/// Parse request body as JSON
+/// Get the media types of the given formats
+///
+/// / 获取给定格式的媒体类型
 ///
-/// / 将请求体解析为 JSON
+/// Used by generated OpenAPI metadata to list accepted request body types.
+///
+/// 由生成的 OpenAPI 元数据用于列出接受的请求体类型。
+#[must_use]
+pub fn media_types(formats: &[BodyFormat]) -> Vec<String> {
+    formats.iter().map(|f| f.media_type().to_string()).collect()
+}
+
+/// Parse the request body according to its `Content-Type`
+///
+/// / 根据 `Content-Type` 解析请求体
+///
+/// Accepts JSON, URL-encoded forms and every format added with
+/// [`register_body_format`]. Requests without `Content-Type` are parsed as JSON.
+///
+/// 接受 JSON、URL 编码表单以及通过 [`register_body_format`] 添加的所有格式。
+/// 没有 `Content-Type` 的请求按 JSON 解析。
 ///
 /// # Type Parameters
 ///
@@ -19,9 +176,12 @@ use crate::{Event, error::Result};
 ///
 /// # 错误
 ///
-/// Returns `RouteError::BadRequest` if the JSON is invalid.
-///
-/// 如果 JSON 无效，返回 `RouteError::BadRequest`。
+/// - `RouteError::BadRequest` if the body cannot be decoded
+///   请求体无法解码时返回 `RouteError::BadRequest`
+/// - `RouteError::Validation` if a form field has the wrong type
+///   表单字段类型错误时返回 `RouteError::Validation`
+/// - `415 Unsupported Media Type` for any other `Content-Type`
+///   其他 `Content-Type` 返回 `415 Unsupported Media Type`
 ///
 /// # Example
 ///
@@ -36,8 +196,128 @@ use crate::{Event, error::Result};
 ///
 /// let body: CreateUserRequest = get_body(&event)?;
 /// ```
-pub fn get_body<T: serde::de::DeserializeOwned>(event: &Event) -> Result<T> {
-    event.parse_json(&event.body)
+pub fn get_body<T: DeserializeOwned>(event: &Event) -> Result<T> {
+    decode_body(event, &body_formats())
+}
+
+/// Parse the request body, accepting only the given formats
+///
+/// / 解析请求体，仅接受给定的格式
+///
+/// Requests without `Content-Type` are parsed with the first format.
+///
+/// 没有 `Content-Type` 的请求使用第一个格式解析。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Same as [`get_body`]; media types outside `formats` get `415 Unsupported Media Type`.
+///
+/// 与 [`get_body`] 相同；不在 `formats` 中的媒体类型返回 `415 Unsupported Media Type`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// // HTML form endpoint that should not accept JSON
+/// let login: LoginForm = get_body_any(&event, &[BodyFormat::Form])?;
+/// ```
+pub fn get_body_any<T: DeserializeOwned>(event: &Event, formats: &[BodyFormat]) -> Result<T> {
+    decode_body(event, formats)
+}
+
+/// Parse the request body like [`get_body`], then validate it
+///
+/// / 像 [`get_body`] 一样解析请求体，然后进行验证
+///
+/// # Errors
+///
+/// # 错误
+///
+/// - Same as [`get_body`] if the body cannot be decoded
+///   请求体无法解码时与 [`get_body`] 相同
+/// - `RouteError::ValidationFailed` listing every rule that failed
+///   返回列出所有未通过规则的 `RouteError::ValidationFailed`
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, Validate)]
+/// struct CreateUserRequest {
+///     #[validate(length(min = 1, max = 50))]
+///     name: String,
+///     #[validate(email)]
+///     email: String,
+/// }
+///
+/// let body: CreateUserRequest = get_valid_body(&event)?;
+/// ```
+pub fn get_valid_body<T: DeserializeOwned + Validate>(event: &Event) -> Result<T> {
+    let value: T = get_body(event)?;
+    value.validate()?;
+    Ok(value)
+}
+
+fn decode_body<T: DeserializeOwned>(event: &Event, formats: &[BodyFormat]) -> Result<T> {
+    let content_type = event
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+
+    let format = match content_type {
+        None => formats.first(),
+        Some(ct) => {
+            let essence = ct
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            formats.iter().find(|f| f.matches(&essence))
+        }
+    };
+    let Some(format) = format else {
+        let expected: Vec<&str> = formats.iter().map(BodyFormat::media_type).collect();
+        return Err(RouteError::custom(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!(
+                "Unsupported Content-Type `{}`, expected one of: {}",
+                content_type.unwrap_or_default(),
+                expected.join(", ")
+            ),
+        ));
+    };
+
+    match format {
+        BodyFormat::Json => event.parse_json(&event.body),
+        BodyFormat::Form => {
+            let pairs: Vec<(String, String)> = serde_urlencoded::from_bytes(&event.body)
+                .map_err(|e| RouteError::bad_request(format!("Invalid form data: {e}")))?;
+            super::de::from_pairs(pairs)
+                .map_err(|e| RouteError::validation(super::de::error_message("form field", &e)))
+        }
+        BodyFormat::Custom(media_type) => {
+            let decoder = custom_formats()
+                .read()
+                .unwrap()
+                .iter()
+                .find(|(m, _)| m.eq_ignore_ascii_case(media_type))
+                .map(|(_, d)| *d)
+                .ok_or_else(|| {
+                    RouteError::Internal(anyhow::anyhow!(
+                        "No decoder registered for `{media_type}`"
+                    ))
+                })?;
+            let value = decoder(&event.body)
+                .map_err(|e| RouteError::bad_request(format!("Invalid {media_type} body: {e}")))?;
+            serde_json::from_value(value)
+                .map_err(|e| RouteError::bad_request(format!("Invalid {media_type} body: {e}")))
+        }
+    }
 }
 
 /// Get raw request body bytes
@@ -87,3 +367,45 @@ pub fn get_body_bytes(event: &Event) -> Result<&[u8]> {
 pub fn get_body_text(event: &Event) -> Result<String> {
     event.parse_text(&event.body)
 }
+
+/// Get the request body as a stream
+///
+/// / 以流的形式获取请求体
+///
+/// On `#[route(body = "stream")]` routes the body is not buffered before the
+/// handler runs; this hands it out for chunk-by-chunk reading under the route's
+/// body limit. On buffered routes it returns a single-chunk stream over
+/// `event.body`.
+///
+/// 在 `#[route(body = "stream")]` 路由上，请求体不会在处理函数运行前缓冲；
+/// 此函数将其交出，以便在路由的请求体限制下逐块读取。
+/// 在缓冲路由上，返回基于 `event.body` 的单块流。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the stream was already taken.
+///
+/// 如果流已被取出，返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[route(body = "stream", body_limit = "1GB")]
+/// async fn handler(event: Event) -> Result<Response> {
+///     let mut stream = get_body_stream(&event)?;
+///     let mut total = 0;
+///     while let Some(chunk) = stream.chunk().await? {
+///         total += chunk.len();
+///     }
+///     json(json!({ "received": total }))
+/// }
+/// ```
+pub fn get_body_stream(event: &Event) -> Result<BodyStream> {
+    event
+        .take_body_stream()
+        .ok_or_else(|| RouteError::bad_request("Request body has already been read"))
+}
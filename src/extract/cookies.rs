@@ -0,0 +1,165 @@
+//! Request cookie extraction
+//!
+//! / 请求 Cookie 提取
+
+use crate::{
+    Event,
+    error::{Result, RouteError},
+};
+
+#[cfg(any(feature = "signed-cookies", feature = "private-cookies"))]
+use cookie::Key;
+
+/// Get a request cookie value by name
+///
+/// / 根据名称获取请求 Cookie 值
+///
+/// Returns `None` if the cookie doesn't exist. The value is percent-decoded.
+///
+/// 如果 Cookie 不存在，返回 `None`。值已进行百分号解码。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let theme = get_cookie(&event, "theme").unwrap_or("light");
+/// ```
+///
+/// # See Also
+///
+/// # 另请参阅
+///
+/// - [`get_cookie_required`] for a version that returns an error if the cookie is missing
+///   [`get_cookie_required`] - Cookie 缺失时返回错误的版本
+#[must_use]
+pub fn get_cookie<'a>(event: &'a Event, name: &str) -> Option<&'a str> {
+    event.cookies().get(name).map(cookie::Cookie::value)
+}
+
+/// Get a required request cookie value
+///
+/// / 获取必需的请求 Cookie 值
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the cookie is missing.
+///
+/// 如果 Cookie 缺失，返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let session = get_cookie_required(&event, "session")?;
+/// ```
+pub fn get_cookie_required<'a>(event: &'a Event, name: &str) -> Result<&'a str> {
+    get_cookie(event, name)
+        .ok_or_else(|| RouteError::bad_request(format!("Missing required cookie: {name}")))
+}
+
+/// Get the cookie signing/encryption key from application state
+///
+/// / 从应用状态获取 Cookie 签名/加密密钥
+///
+/// The state type `S` must implement `FromRef<S> for Key`, following the same
+/// convention as Axum's sub-state extraction.
+///
+/// 状态类型 `S` 必须满足 `Key: FromRef<S>`，与 Axum 子状态提取的约定一致。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::Internal` if the state is not found.
+///
+/// 如果未找到状态，返回 `RouteError::Internal`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(Clone)]
+/// struct AppState {
+///     cookie_key: Key,
+/// }
+///
+/// impl FromRef<AppState> for Key {
+///     fn from_ref(state: &AppState) -> Self {
+///         state.cookie_key.clone()
+///     }
+/// }
+///
+/// let key = cookie_key::<AppState>(&event)?;
+/// let user_id = get_signed_cookie(&event, &key, "user_id");
+/// ```
+#[cfg(any(feature = "signed-cookies", feature = "private-cookies"))]
+pub fn cookie_key<S>(event: &Event) -> Result<Key>
+where
+    S: Clone + Send + Sync + 'static,
+    Key: axum::extract::FromRef<S>,
+{
+    let state = event
+        .state::<S>()
+        .ok_or_else(|| RouteError::Internal(anyhow::anyhow!("State not found")))?;
+    Ok(<Key as axum::extract::FromRef<S>>::from_ref(&state))
+}
+
+/// Get a signed request cookie value, verifying its signature
+///
+/// / 获取签名的请求 Cookie 值并验证签名
+///
+/// Returns `None` if the cookie is missing or its signature is invalid.
+/// Requires the `signed-cookies` feature.
+///
+/// 如果 Cookie 缺失或签名无效，返回 `None`。需要启用 `signed-cookies` feature。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let key = cookie_key::<AppState>(&event)?;
+/// let user_id = get_signed_cookie(&event, &key, "user_id")
+///     .ok_or_else(|| RouteError::unauthorized("Not signed in"))?;
+/// ```
+#[cfg(feature = "signed-cookies")]
+#[must_use]
+pub fn get_signed_cookie(event: &Event, key: &Key, name: &str) -> Option<String> {
+    event
+        .cookies()
+        .signed(key)
+        .get(name)
+        .map(|c| c.value().to_string())
+}
+
+/// Get an encrypted request cookie value, decrypting and authenticating it
+///
+/// / 获取加密的请求 Cookie 值，解密并验证
+///
+/// Returns `None` if the cookie is missing or cannot be decrypted.
+/// Requires the `private-cookies` feature.
+///
+/// 如果 Cookie 缺失或无法解密，返回 `None`。需要启用 `private-cookies` feature。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let key = cookie_key::<AppState>(&event)?;
+/// let token = get_private_cookie(&event, &key, "refresh_token");
+/// ```
+#[cfg(feature = "private-cookies")]
+#[must_use]
+pub fn get_private_cookie(event: &Event, key: &Key, name: &str) -> Option<String> {
+    event
+        .cookies()
+        .private(key)
+        .get(name)
+        .map(|c| c.value().to_string())
+}
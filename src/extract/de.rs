@@ -0,0 +1,428 @@
+//! Deserializer for flat key/value pairs (query strings, path parameters)
+//!
+//! / 扁平键值对（查询字符串、路径参数）的反序列化器
+//!
+//! Pairs are first folded into a small tree, then deserialized with serde:
+//!
+//! 键值对先被折叠为一棵小树，再通过 serde 反序列化：
+//!
+//! - Repeated keys (`tag=a&tag=b`) and `tag[]=a` become sequences
+//!   重复键（`tag=a&tag=b`）和 `tag[]=a` 会成为序列
+//! - Bracketed keys (`filter[name]=x`) become nested maps / structs
+//!   方括号键（`filter[name]=x`）会成为嵌套映射/结构体
+//! - Indexed keys (`items[1]=b&items[0]=a`) become sequences ordered by index
+//!   索引键（`items[1]=b&items[0]=a`）会成为按索引排序的序列
+//! - Values are parsed on demand into numbers, booleans, enums, etc.
+//!   值按需解析为数字、布尔值、枚举等
+
+use std::collections::HashMap;
+
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer, StringDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Unexpected, Visitor};
+
+/// Deserialize `T` from key/value pairs, tracking the path of the failing field
+///
+/// / 从键值对反序列化 `T`，并跟踪出错字段的路径
+pub(crate) fn from_pairs<T, I, K, V>(pairs: I) -> Result<T, serde_path_to_error::Error<Error>>
+where
+    T: DeserializeOwned,
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    let mut root = Entries::default();
+    for (key, value) in pairs {
+        insert(&mut root, key.as_ref(), value.into());
+    }
+    serde_path_to_error::deserialize(NodeDeserializer(Node::Map(root)))
+}
+
+/// Format a deserialization error as a human-readable message naming the field
+///
+/// / 将反序列化错误格式化为包含字段名的可读消息
+///
+/// `kind` describes the source, e.g. `"query parameter"`.
+///
+/// `kind` 描述数据来源，如 `"query parameter"`。
+pub(crate) fn error_message(kind: &str, err: &serde_path_to_error::Error<Error>) -> String {
+    let path = err.path().to_string();
+    if path == "." {
+        format!("Invalid {kind}s: {}", err.inner())
+    } else {
+        format!("Invalid {kind} `{path}`: {}", err.inner())
+    }
+}
+
+// ============================================================================
+// Pair tree
+// 键值对树
+// ============================================================================
+
+enum Node {
+    /// All values seen for a key, in order
+    /// / 某个键的所有值，按出现顺序
+    Values(Vec<String>),
+    /// Nested keys, in first-seen order
+    /// / 嵌套键，按首次出现顺序
+    Map(Entries),
+}
+
+/// Nested keys in first-seen order, indexed by name so lookups stay O(1)
+///
+/// / 按首次出现顺序排列的嵌套键，并按名称建立索引，使查找保持 O(1)
+#[derive(Default)]
+struct Entries {
+    list: Vec<(String, Node)>,
+    index: HashMap<String, usize>,
+}
+
+/// Insert a `key=value` pair, splitting `a[b][c]` into nested segments
+///
+/// / 插入 `key=value` 键值对，将 `a[b][c]` 拆分为嵌套段
+fn insert(map: &mut Entries, key: &str, value: String) {
+    let (head, rest) = match key.find('[') {
+        Some(i) if i > 0 && key.ends_with(']') => (&key[..i], &key[i..]),
+        _ => (key, ""),
+    };
+    let segments: Vec<&str> = if rest.is_empty() {
+        Vec::new()
+    } else {
+        rest[1..rest.len() - 1].split("][").collect()
+    };
+
+    let mut node = entry(map, head, segments.first().is_some_and(|s| !s.is_empty()));
+    for (i, segment) in segments.iter().enumerate() {
+        // `[]` only marks a sequence; values are appended below
+        // `[]` 仅标记序列；值在下方追加
+        if segment.is_empty() {
+            break;
+        }
+        let nested = segments.get(i + 1).is_some_and(|s| !s.is_empty());
+        node = match node {
+            Node::Map(children) => entry(children, segment, nested),
+            // Conflicting shapes (`a=1&a[b]=2`): keep the first one
+            // 结构冲突（`a=1&a[b]=2`）：保留先出现的
+            Node::Values(_) => return,
+        };
+    }
+    if let Node::Values(values) = node {
+        values.push(value);
+    }
+}
+
+fn entry<'a>(map: &'a mut Entries, key: &str, nested: bool) -> &'a mut Node {
+    let index = match map.index.get(key) {
+        Some(&i) => i,
+        None => {
+            let node = if nested {
+                Node::Map(Entries::default())
+            } else {
+                Node::Values(Vec::new())
+            };
+            map.list.push((key.to_string(), node));
+            map.index.insert(key.to_string(), map.list.len() - 1);
+            map.list.len() - 1
+        }
+    };
+    &mut map.list[index].1
+}
+
+// ============================================================================
+// Node deserializer
+// 节点反序列化器
+// ============================================================================
+
+struct NodeDeserializer(Node);
+
+impl NodeDeserializer {
+    /// The single value used for scalar targets (last one wins)
+    /// / 标量目标使用的单个值（取最后一个）
+    fn scalar(self) -> Result<ValueDeserializer, Error> {
+        match self.0 {
+            Node::Values(mut values) => Ok(ValueDeserializer(values.pop().unwrap_or_default())),
+            Node::Map(_) => Err(de::Error::invalid_type(Unexpected::Map, &"a single value")),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for NodeDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Order `[0]`, `[1]`, ... entries by their index
+///
+/// / 按索引排列 `[0]`、`[1]` 等条目
+///
+/// Gaps are closed (`a[0]=x&a[5]=y` gives two elements); keys that aren't
+/// numbers, or name the same index twice (`a[1]` and `a[01]`), are rejected.
+///
+/// 空缺会被合并（`a[0]=x&a[5]=y` 得到两个元素）；不是数字的键，或两次指向同一索引的键
+/// （`a[1]` 和 `a[01]`）会被拒绝。
+fn indexed(entries: Vec<(String, Node)>) -> Result<Vec<Node>, Error> {
+    let mut items = entries
+        .into_iter()
+        .map(|(key, node)| match key.parse::<usize>() {
+            Ok(index) => Ok((index, node)),
+            Err(_) => Err(de::Error::custom(format_args!(
+                "invalid index `{key}`, expected a number"
+            ))),
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    items.sort_by_key(|(index, _)| *index);
+    if let Some(pair) = items.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        return Err(de::Error::custom(format_args!(
+            "duplicate index `{}`",
+            pair[0].0
+        )));
+    }
+    Ok(items.into_iter().map(|(_, node)| node).collect())
+}
+
+macro_rules! forward_scalar {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.scalar()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Node::Values(values) if values.len() > 1 => visit_values(values, visitor),
+            Node::Values(mut values) => visitor.visit_string(values.pop().unwrap_or_default()),
+            Node::Map(entries) => visit_entries(entries.list, visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.0 {
+            Node::Values(values) if values.iter().all(String::is_empty) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Node::Values(values) => visit_values(values, visitor),
+            // `items[0][name]=a&items[1][name]=b`
+            Node::Map(entries) => visitor.visit_seq(SeqDeserializer::new(
+                indexed(entries.list)?.into_iter().map(NodeDeserializer),
+            )),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Node::Map(entries) => visit_entries(entries.list, visitor),
+            Node::Values(_) => Err(de::Error::invalid_type(Unexpected::Str("value"), &visitor)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.scalar()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_scalar! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_identifier
+    }
+}
+
+fn visit_values<'de, V: Visitor<'de>>(values: Vec<String>, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_seq(SeqDeserializer::new(
+        values.into_iter().map(ValueDeserializer),
+    ))
+}
+
+fn visit_entries<'de, V: Visitor<'de>>(
+    entries: Vec<(String, Node)>,
+    visitor: V,
+) -> Result<V::Value, Error> {
+    let mut map = MapDeserializer::new(
+        entries
+            .into_iter()
+            .map(|(key, node)| (key, NodeDeserializer(node))),
+    );
+    let value = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(value)
+}
+
+// ============================================================================
+// Single value deserializer
+// 单值反序列化器
+// ============================================================================
+
+struct ValueDeserializer(String);
+
+impl ValueDeserializer {
+    fn string(self) -> StringDeserializer<Error> {
+        self.0.into_deserializer()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! parse_scalar {
+    ($($method:ident => $visit:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.0.trim().parse::<$ty>() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(de::Error::invalid_value(
+                        Unexpected::Str(&self.0),
+                        &stringify!($ty),
+                    )),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0.trim() {
+            "true" | "1" | "on" | "yes" => visitor.visit_bool(true),
+            "false" | "0" | "off" | "no" => visitor.visit_bool(false),
+            _ => Err(de::Error::invalid_value(
+                Unexpected::Str(&self.0),
+                &"a boolean",
+            )),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visit_values(vec![self.0], visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.string().deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    parse_scalar! {
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_char => visit_char: char,
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit_struct tuple tuple_struct map struct identifier
+    }
+}
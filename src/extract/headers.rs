@@ -3,7 +3,10 @@
 //! / 请求头提取
 
 use crate::Event;
+use crate::error::{Result, RouteError};
+use crate::response::conditional::{ETag, preconditions_pass};
 use axum::http::HeaderMap;
+use std::time::SystemTime;
 
 /// Get a request header by name
 ///
@@ -48,3 +51,65 @@ pub fn get_header<'a>(event: &'a Event, name: &str) -> Option<&'a str> {
 pub fn get_headers(event: &Event) -> &HeaderMap {
     event.headers()
 }
+
+/// Check `If-Match` against the resource's current tag before updating it
+///
+/// / 在更新资源前，使用其当前标签检查 `If-Match`
+///
+/// Requests without `If-Match` pass. Uses strong comparison, so weak tags
+/// never match.
+///
+/// 不含 `If-Match` 的请求直接通过。使用强比较，因此弱标签永远不会匹配。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::PreconditionFailed` (412) if no listed tag matches.
+///
+/// 如果没有列出的标签匹配，返回 `RouteError::PreconditionFailed` (412)。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let article = db.article(id).await?;
+/// check_if_match(&event, &ETag::strong(article.version.to_string()))?;
+/// let updated = db.update(id, get_body(&event)?).await?;
+/// Ok(json(&updated)?.etag(&updated.version.to_string()))
+/// ```
+pub fn check_if_match(event: &Event, current: &ETag) -> Result<()> {
+    check_preconditions(event, Some(current), None)
+}
+
+/// Check `If-Match` / `If-Unmodified-Since` against the current resource
+///
+/// / 使用当前资源检查 `If-Match` / `If-Unmodified-Since`
+///
+/// Pass `None` for a resource that does not exist yet; `If-Match: *` then fails.
+/// `If-Unmodified-Since` is only consulted when `If-Match` is absent.
+///
+/// 资源尚不存在时传入 `None`，此时 `If-Match: *` 会失败。
+/// 只有在 `If-Match` 不存在时才会检查 `If-Unmodified-Since`。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::PreconditionFailed` (412) if a precondition does not hold.
+///
+/// 如果前置条件不成立，返回 `RouteError::PreconditionFailed` (412)。
+pub fn check_preconditions(
+    event: &Event,
+    current: Option<&ETag>,
+    last_modified: Option<SystemTime>,
+) -> Result<()> {
+    if preconditions_pass(event.headers(), current, last_modified) {
+        Ok(())
+    } else {
+        Err(RouteError::precondition_failed(
+            "The resource has been modified",
+        ))
+    }
+}
@@ -43,14 +43,26 @@
 //!
 //! # 可用的提取器
 //!
-//! - **Path parameters**: [`get_param`], [`get_param_required`]
-//!   **路径参数**：[`get_param`], [`get_param_required`]
-//! - **Query parameters**: [`get_query`], [`get_query_param`], [`get_query_param_required`]
-//!   **查询参数**：[`get_query`], [`get_query_param`], [`get_query_param_required`]
-//! - **Request body**: [`get_body`], [`get_body_bytes`], [`get_body_text`]
-//!   **请求体**：[`get_body`], [`get_body_bytes`], [`get_body_text`]
+//! - **Path parameters**: [`get_param`], [`get_param_required`], [`get_params_as`]
+//!   **路径参数**：[`get_param`], [`get_param_required`], [`get_params_as`]
+//! - **Query parameters**: [`get_query`], [`get_query_param`], [`get_query_param_required`], [`get_query_as`]
+//!   **查询参数**：[`get_query`], [`get_query_param`], [`get_query_param_required`], [`get_query_as`]
+//! - **Request body**: [`get_body`], [`get_body_any`], [`get_body_bytes`], [`get_body_text`],
+//!   [`get_body_stream`]
+//!   **请求体**：[`get_body`], [`get_body_any`], [`get_body_bytes`], [`get_body_text`],
+//!   [`get_body_stream`]
+//! - **Validated input**: [`get_valid_body`], [`get_valid_query`], [`get_valid_params`]
+//!   (see [`crate::validate`])
+//!   **验证后的输入**：[`get_valid_body`], [`get_valid_query`], [`get_valid_params`]
+//!   （见 [`crate::validate`]）
+//! - **Multipart forms**: [`get_multipart`], [`get_multipart_with`]
+//!   **Multipart 表单**：[`get_multipart`], [`get_multipart_with`]
 //! - **Headers**: [`get_header`], [`get_headers`]
 //!   **请求头**：[`get_header`], [`get_headers`]
+//! - **Preconditions**: [`check_if_match`], [`check_preconditions`]
+//!   **前置条件**：[`check_if_match`], [`check_preconditions`]
+//! - **Cookies**: [`get_cookie`], [`get_cookie_required`]
+//!   **Cookie**：[`get_cookie`], [`get_cookie_required`]
 //! - **Metadata**: [`get_method`], [`get_path`], [`get_uri`]
 //!   **元数据**：[`get_method`], [`get_path`], [`get_uri`]
 //! - **State**: [`get_state`]
@@ -60,8 +72,11 @@
 // Re-export 所有子模块
 
 pub mod body;
+pub mod cookies;
+mod de;
 pub mod headers;
 pub mod metadata;
+pub mod multipart;
 pub mod params;
 pub mod query;
 pub mod state;
@@ -72,9 +87,24 @@ mod tests;
 // Re-export public items from submodules for convenient access
 // Re-export 子模块的公共项以便便捷访问
 
-pub use body::{get_body, get_body_bytes, get_body_text};
-pub use headers::{get_header, get_headers};
+pub use body::{
+    BodyFormat, body_formats, get_body, get_body_any, get_body_bytes, get_body_stream,
+    get_body_text, get_valid_body, register_body_format,
+};
+#[cfg(any(feature = "signed-cookies", feature = "private-cookies"))]
+pub use cookies::cookie_key;
+#[cfg(feature = "private-cookies")]
+pub use cookies::get_private_cookie;
+#[cfg(feature = "signed-cookies")]
+pub use cookies::get_signed_cookie;
+pub use cookies::{get_cookie, get_cookie_required};
+pub use headers::{check_if_match, check_preconditions, get_header, get_headers};
 pub use metadata::{get_method, get_path, get_uri};
-pub use params::{get_param, get_param_required};
-pub use query::{get_query, get_query_param, get_query_param_required};
+pub use multipart::{
+    Field, Form, Multipart, MultipartConfig, Upload, get_multipart, get_multipart_with,
+};
+pub use params::{get_param, get_param_required, get_params_as, get_valid_params};
+pub use query::{
+    get_query, get_query_as, get_query_param, get_query_param_required, get_valid_query,
+};
 pub use state::get_state;
@@ -0,0 +1,633 @@
+//! Multipart/form-data extraction
+//!
+//! / Multipart/form-data 提取
+//!
+//! [`get_multipart`] parses the buffered request body as `multipart/form-data`.
+//! Fields can be read one by one with [`Multipart::next_field`], or collected
+//! into a [`Form`] with [`Multipart::collect`]. When collecting, uploads larger
+//! than the spill threshold are written to a temporary file instead of being
+//! kept in memory.
+//!
+//! [`get_multipart`] 将已缓冲的请求体解析为 `multipart/form-data`。
+//! 可以通过 [`Multipart::next_field`] 逐个读取字段，或通过 [`Multipart::collect`]
+//! 收集为 [`Form`]。收集时，超过溢出阈值的上传会写入临时文件而不是保存在内存中。
+//!
+//! # Example
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! #[route]
+//! pub async fn handler(event: Event) -> Result<Response> {
+//!     let config = MultipartConfig::new()
+//!         .field_limit(64 * 1024)
+//!         .limit_for("avatar", 5 * 1024 * 1024);
+//!     let form = get_multipart_with(&event, config)?.collect().await?;
+//!
+//!     let title = form.text("title").unwrap_or("untitled");
+//!     if let Some(avatar) = form.file("avatar") {
+//!         avatar.persist(format!("uploads/{}", avatar.file_name().unwrap_or("avatar"))).await?;
+//!     }
+//!     json(json!({ "title": title }))
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::http::header;
+use bytes::{Bytes, BytesMut};
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    Event,
+    error::{Result, RouteError},
+};
+
+/// Default per-field size limit (10 MiB)
+/// / 默认的单字段大小限制（10 MiB）
+const DEFAULT_FIELD_LIMIT: u64 = 10 * 1024 * 1024;
+
+/// Default spill threshold (1 MiB)
+/// / 默认的溢出阈值（1 MiB）
+const DEFAULT_SPILL_THRESHOLD: usize = 1024 * 1024;
+
+// ============================================================================
+// Configuration
+// 配置
+// ============================================================================
+
+/// Size limits and spill behaviour for multipart parsing
+///
+/// / Multipart 解析的大小限制和溢出行为
+///
+/// A field exceeding its limit makes parsing fail with `413 Payload Too Large`.
+///
+/// 字段超过限制时，解析失败并返回 `413 Payload Too Large`。
+#[derive(Debug, Clone)]
+pub struct MultipartConfig {
+    field_limit: u64,
+    field_limits: Vec<(String, u64)>,
+    spill_threshold: usize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        Self {
+            field_limit: DEFAULT_FIELD_LIMIT,
+            field_limits: Vec::new(),
+            spill_threshold: DEFAULT_SPILL_THRESHOLD,
+            temp_dir: None,
+        }
+    }
+}
+
+impl MultipartConfig {
+    /// Create a configuration with default limits
+    /// / 创建使用默认限制的配置
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the size limit (in bytes) applied to every field
+    /// / 设置应用于每个字段的大小限制（字节）
+    #[must_use]
+    pub fn field_limit(mut self, limit: u64) -> Self {
+        self.field_limit = limit;
+        self
+    }
+
+    /// Override the size limit (in bytes) for a single named field
+    /// / 为单个命名字段覆盖大小限制（字节）
+    #[must_use]
+    pub fn limit_for(mut self, name: impl Into<String>, limit: u64) -> Self {
+        self.field_limits.push((name.into(), limit));
+        self
+    }
+
+    /// Set the size (in bytes) above which collected uploads are written to disk
+    /// / 设置收集上传时写入磁盘的大小阈值（字节）
+    #[must_use]
+    pub fn spill_threshold(mut self, threshold: usize) -> Self {
+        self.spill_threshold = threshold;
+        self
+    }
+
+    /// Set the directory for spilled uploads (defaults to the system temp dir)
+    /// / 设置溢出上传的目录（默认为系统临时目录）
+    #[must_use]
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    fn constraints(&self, whole_limit: usize) -> multer::Constraints {
+        let mut limits = multer::SizeLimit::new()
+            .whole_stream(u64::try_from(whole_limit).unwrap_or(u64::MAX))
+            .per_field(self.field_limit);
+        for (name, limit) in &self.field_limits {
+            limits = limits.for_field(name.clone(), *limit);
+        }
+        multer::Constraints::new().size_limit(limits)
+    }
+}
+
+// ============================================================================
+// Extraction
+// 提取
+// ============================================================================
+
+/// Parse the request body as `multipart/form-data` with default limits
+///
+/// / 使用默认限制将请求体解析为 `multipart/form-data`
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the request is not `multipart/form-data`
+/// or has no boundary.
+///
+/// 如果请求不是 `multipart/form-data` 或缺少 boundary，返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let mut multipart = get_multipart(&event)?;
+/// while let Some(mut field) = multipart.next_field().await? {
+///     while let Some(chunk) = field.chunk().await? {
+///         // process chunk...
+///     }
+/// }
+/// ```
+pub fn get_multipart(event: &Event) -> Result<Multipart> {
+    get_multipart_with(event, MultipartConfig::default())
+}
+
+/// Parse the request body as `multipart/form-data` with custom limits
+///
+/// / 使用自定义限制将请求体解析为 `multipart/form-data`
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the request is not `multipart/form-data`,
+/// has no boundary, or its body stream was already taken.
+///
+/// 如果请求不是 `multipart/form-data`、缺少 boundary 或请求体流已被取出，
+/// 返回 `RouteError::BadRequest`。
+///
+/// On `#[route(body = "stream")]` routes the body is parsed as it arrives
+/// and never buffered as a whole.
+///
+/// 在 `#[route(body = "stream")]` 路由上，请求体会在到达时边读边解析，不会被整体缓冲。
+pub fn get_multipart_with(event: &Event, config: MultipartConfig) -> Result<Multipart> {
+    let content_type = event
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| RouteError::bad_request("Missing Content-Type header"))?;
+    let boundary = multer::parse_boundary(content_type)
+        .map_err(|_| RouteError::bad_request("Expected a multipart/form-data request"))?;
+
+    let (body, limit) = event
+        .take_body_stream()
+        .ok_or_else(|| RouteError::bad_request("Request body has already been read"))?
+        .into_parts();
+    Ok(Multipart {
+        inner: multer::Multipart::with_constraints(
+            body.into_data_stream(),
+            boundary,
+            config.constraints(limit),
+        ),
+        config,
+    })
+}
+
+/// A `multipart/form-data` request body
+///
+/// / `multipart/form-data` 请求体
+pub struct Multipart {
+    inner: multer::Multipart<'static>,
+    config: MultipartConfig,
+}
+
+impl Multipart {
+    /// Get the next field, or `None` when all fields have been read
+    ///
+    /// / 获取下一个字段，所有字段读取完毕时返回 `None`
+    ///
+    /// The previously returned field must be dropped before calling this again.
+    ///
+    /// 再次调用前必须先丢弃上一次返回的字段。
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `RouteError::BadRequest` if the body is malformed.
+    ///
+    /// 如果请求体格式错误，返回 `RouteError::BadRequest`。
+    pub async fn next_field(&mut self) -> Result<Option<Field>> {
+        let field = self.inner.next_field().await.map_err(map_multer_error)?;
+        Ok(field.map(|inner| Field { inner }))
+    }
+
+    /// Read all fields into a [`Form`], spilling large uploads to disk
+    ///
+    /// / 读取所有字段到 [`Form`]，并将大文件上传溢出到磁盘
+    ///
+    /// Fields without a filename are stored as text; fields with a filename
+    /// are stored as [`Upload`]s.
+    ///
+    /// 没有文件名的字段作为文本存储；带文件名的字段作为 [`Upload`] 存储。
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// - `RouteError::BadRequest` if the body is malformed or a text field is not UTF-8
+    ///   请求体格式错误或文本字段不是 UTF-8 时返回 `RouteError::BadRequest`
+    /// - `413 Payload Too Large` if a field exceeds its size limit
+    ///   字段超过大小限制时返回 `413 Payload Too Large`
+    /// - `RouteError::Internal` if a temporary file cannot be written
+    ///   无法写入临时文件时返回 `RouteError::Internal`
+    pub async fn collect(mut self) -> Result<Form> {
+        let mut form = Form::default();
+        while let Some(field) = self.next_field().await? {
+            let name = field.name().unwrap_or_default().to_string();
+            if field.is_file() {
+                form.files.push(field.save(&self.config).await?);
+            } else {
+                form.texts.push((name, field.text().await?));
+            }
+        }
+        Ok(form)
+    }
+}
+
+/// A single field of a multipart body
+///
+/// / Multipart 请求体的单个字段
+pub struct Field {
+    inner: multer::Field<'static>,
+}
+
+impl Field {
+    /// Form field name
+    /// / 表单字段名
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    /// Original filename, for file parts
+    /// / 原始文件名（文件部分）
+    #[must_use]
+    pub fn file_name(&self) -> Option<&str> {
+        self.inner.file_name()
+    }
+
+    /// Declared content type of this part
+    /// / 此部分声明的内容类型
+    #[must_use]
+    pub fn content_type(&self) -> Option<&str> {
+        self.inner.content_type().map(|m| m.essence_str())
+    }
+
+    /// Whether this part is a file upload (has a filename)
+    /// / 此部分是否为文件上传（带文件名）
+    #[must_use]
+    pub fn is_file(&self) -> bool {
+        self.inner.file_name().is_some()
+    }
+
+    /// Read the next chunk of data, or `None` at the end of the field
+    ///
+    /// / 读取下一个数据块，字段结束时返回 `None`
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `413 Payload Too Large` if the field exceeds its size limit.
+    ///
+    /// 字段超过大小限制时返回 `413 Payload Too Large`。
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>> {
+        self.inner.chunk().await.map_err(map_multer_error)
+    }
+
+    /// Read the whole field into memory
+    ///
+    /// / 将整个字段读入内存
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `413 Payload Too Large` if the field exceeds its size limit.
+    ///
+    /// 字段超过大小限制时返回 `413 Payload Too Large`。
+    pub async fn bytes(self) -> Result<Bytes> {
+        self.inner.bytes().await.map_err(map_multer_error)
+    }
+
+    /// Read the whole field as UTF-8 text
+    ///
+    /// / 将整个字段读取为 UTF-8 文本
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `RouteError::BadRequest` if the field is not valid UTF-8.
+    ///
+    /// 字段不是有效的 UTF-8 时返回 `RouteError::BadRequest`。
+    pub async fn text(self) -> Result<String> {
+        let name = self.name().unwrap_or_default().to_string();
+        let bytes = self.bytes().await?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| RouteError::bad_request(format!("Field `{name}` is not valid UTF-8")))
+    }
+
+    /// Read the field into an [`Upload`], spilling to disk above the threshold
+    ///
+    /// / 将字段读取为 [`Upload`]，超过阈值时溢出到磁盘
+    async fn save(mut self, config: &MultipartConfig) -> Result<Upload> {
+        let mut upload = Upload {
+            name: self.name().unwrap_or_default().to_string(),
+            file_name: self.file_name().map(str::to_string),
+            content_type: self.content_type().map(str::to_string),
+            size: 0,
+            data: UploadData::Memory(Bytes::new()),
+        };
+
+        let mut buffer = BytesMut::new();
+        let mut file: Option<(tokio::fs::File, TempPath)> = None;
+        while let Some(chunk) = self.chunk().await? {
+            upload.size += chunk.len() as u64;
+            match &mut file {
+                Some((f, _)) => f.write_all(&chunk).await.map_err(io_error)?,
+                None if buffer.len() + chunk.len() > config.spill_threshold => {
+                    let dir = config.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+                    let (mut f, path) = TempPath::create(&dir).await.map_err(io_error)?;
+                    f.write_all(&buffer).await.map_err(io_error)?;
+                    f.write_all(&chunk).await.map_err(io_error)?;
+                    buffer.clear();
+                    file = Some((f, path));
+                }
+                None => buffer.extend_from_slice(&chunk),
+            }
+        }
+
+        upload.data = match file {
+            Some((mut f, path)) => {
+                f.flush().await.map_err(io_error)?;
+                UploadData::Disk(path)
+            }
+            None => UploadData::Memory(buffer.freeze()),
+        };
+        Ok(upload)
+    }
+}
+
+// ============================================================================
+// Collected form
+// 收集的表单
+// ============================================================================
+
+/// All fields of a multipart body, collected by [`Multipart::collect`]
+///
+/// / 由 [`Multipart::collect`] 收集的 Multipart 请求体所有字段
+#[derive(Debug, Default)]
+pub struct Form {
+    texts: Vec<(String, String)>,
+    files: Vec<Upload>,
+}
+
+impl Form {
+    /// Get the first text field with the given name
+    /// / 获取给定名称的第一个文本字段
+    #[must_use]
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.texts
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get all text fields, in request order
+    /// / 按请求顺序获取所有文本字段
+    #[must_use]
+    pub fn texts(&self) -> &[(String, String)] {
+        &self.texts
+    }
+
+    /// Get the first file upload with the given field name
+    /// / 获取给定字段名的第一个文件上传
+    #[must_use]
+    pub fn file(&self, name: &str) -> Option<&Upload> {
+        self.files.iter().find(|f| f.name == name)
+    }
+
+    /// Get all file uploads, in request order
+    /// / 按请求顺序获取所有文件上传
+    #[must_use]
+    pub fn files(&self) -> &[Upload] {
+        &self.files
+    }
+}
+
+/// A file upload, held in memory or in a temporary file
+///
+/// / 文件上传，保存在内存或临时文件中
+///
+/// Temporary files are deleted when the `Upload` is dropped unless
+/// [`persist`](Upload::persist) moved them elsewhere.
+///
+/// 除非已通过 [`persist`](Upload::persist) 移走，临时文件会在 `Upload` 被丢弃时删除。
+#[derive(Debug)]
+pub struct Upload {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+    size: u64,
+    data: UploadData,
+}
+
+#[derive(Debug)]
+enum UploadData {
+    Memory(Bytes),
+    Disk(TempPath),
+}
+
+impl Upload {
+    /// Form field name
+    /// / 表单字段名
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Original filename sent by the client
+    /// / 客户端发送的原始文件名
+    #[must_use]
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Declared content type
+    /// / 声明的内容类型
+    #[must_use]
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Size in bytes
+    /// / 字节大小
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Path of the temporary file, if the upload was spilled to disk
+    /// / 临时文件路径（如果上传已溢出到磁盘）
+    #[must_use]
+    pub fn temp_path(&self) -> Option<&Path> {
+        match &self.data {
+            UploadData::Disk(path) => Some(&path.0),
+            UploadData::Memory(_) => None,
+        }
+    }
+
+    /// Read the upload contents into memory
+    ///
+    /// / 将上传内容读入内存
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `RouteError::Internal` if the temporary file cannot be read.
+    ///
+    /// 无法读取临时文件时返回 `RouteError::Internal`。
+    pub async fn bytes(&self) -> Result<Bytes> {
+        match &self.data {
+            UploadData::Memory(bytes) => Ok(bytes.clone()),
+            UploadData::Disk(path) => tokio::fs::read(&path.0)
+                .await
+                .map(Bytes::from)
+                .map_err(io_error),
+        }
+    }
+
+    /// Write the upload to `dest`, moving the temporary file when possible
+    ///
+    /// / 将上传写入 `dest`，尽可能直接移动临时文件
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `RouteError::Internal` if the file cannot be written.
+    ///
+    /// 无法写入文件时返回 `RouteError::Internal`。
+    pub async fn persist(&self, dest: impl AsRef<Path>) -> Result<()> {
+        let dest = dest.as_ref();
+        match &self.data {
+            UploadData::Memory(bytes) => tokio::fs::write(dest, bytes).await.map_err(io_error),
+            UploadData::Disk(path) => {
+                // Rename fails across filesystems; fall back to copying
+                // 跨文件系统时重命名会失败；回退为复制
+                if tokio::fs::rename(&path.0, dest).await.is_err() {
+                    tokio::fs::copy(&path.0, dest).await.map_err(io_error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Temporary file path, removed on drop
+/// / 临时文件路径，丢弃时删除
+#[derive(Debug)]
+struct TempPath(PathBuf);
+
+impl TempPath {
+    /// Create a new, owner-only file in `dir`
+    ///
+    /// / 在 `dir` 中创建仅所有者可访问的新文件
+    ///
+    /// The file is opened with `create_new`, so a file or symlink planted at
+    /// the name is never followed or truncated; the name is retried instead.
+    /// The path is only removed on drop once this process created it.
+    ///
+    /// 文件以 `create_new` 打开，因此不会跟随或截断预先放置在该名称上的文件或符号链接，
+    /// 而是换一个名称重试。只有在本进程创建文件之后，丢弃时才会删除该路径。
+    async fn create(dir: &Path) -> std::io::Result<(tokio::fs::File, Self)> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let mut attempts = 0;
+        loop {
+            let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let random = std::hash::BuildHasher::hash_one(
+                &std::collections::hash_map::RandomState::new(),
+                id,
+            );
+            let path = dir.join(format!(
+                "astrea-upload-{}-{id}-{random:016x}",
+                std::process::id()
+            ));
+            let mut options = tokio::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            options.mode(0o600);
+            match options.open(&path).await {
+                Ok(file) => return Ok((file, Self(path))),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempts < 16 => {
+                    attempts += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+// ============================================================================
+// Error mapping
+// 错误映射
+// ============================================================================
+
+fn map_multer_error(err: multer::Error) -> RouteError {
+    match err {
+        multer::Error::FieldSizeExceeded { limit, field_name } => {
+            RouteError::payload_too_large(format!(
+                "Field `{}` exceeds the size limit of {limit} bytes",
+                field_name.unwrap_or_default()
+            ))
+        }
+        multer::Error::StreamSizeExceeded { limit } => RouteError::payload_too_large(format!(
+            "Multipart body exceeds the size limit of {limit} bytes"
+        )),
+        multer::Error::StreamReadFailed(source)
+            if crate::event::body::is_length_limit(&*source) =>
+        {
+            RouteError::payload_too_large("Request body exceeds the limit set by DefaultBodyLimit")
+        }
+        other => RouteError::bad_request(format!("Invalid multipart body: {other}")),
+    }
+}
+
+fn io_error(err: std::io::Error) -> RouteError {
+    RouteError::Internal(anyhow::Error::new(err).context("Failed to store multipart upload"))
+}
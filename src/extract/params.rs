@@ -2,9 +2,12 @@
 //!
 //! / 路径参数提取
 
+use serde::de::DeserializeOwned;
+
 use crate::{
     Event,
     error::{Result, RouteError},
+    validate::Validate,
 };
 
 /// Get a path parameter by key
@@ -63,3 +66,58 @@ pub fn get_param_required<'a>(event: &'a Event, key: &str) -> Result<&'a str> {
     get_param(event, key)
         .ok_or_else(|| RouteError::bad_request(format!("Missing required parameter: {key}")))
 }
+
+/// Deserialize all path parameters into a typed struct
+///
+/// / 将所有路径参数反序列化为类型化结构体
+///
+/// Field names match the bracketed segments of the route file path.
+/// Numeric and boolean fields are parsed from the raw segment.
+///
+/// 字段名与路由文件路径中的方括号段一致。数字和布尔字段从原始段解析。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::Validation` naming the offending parameter if it
+/// cannot be parsed.
+///
+/// 如果参数无法解析，返回包含出错参数名的 `RouteError::Validation`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// // Route: /users/[id]/posts/[post_id]
+/// #[derive(Deserialize)]
+/// struct PostPath {
+///     id: u64,
+///     post_id: u64,
+/// }
+///
+/// let path: PostPath = get_params_as(&event)?;
+/// ```
+pub fn get_params_as<T: DeserializeOwned>(event: &Event) -> Result<T> {
+    super::de::from_pairs(event.params().iter().map(|(k, v)| (k, v.clone())))
+        .map_err(|e| RouteError::validation(super::de::error_message("path parameter", &e)))
+}
+
+/// Deserialize the path parameters like [`get_params_as`], then validate them
+///
+/// / 像 [`get_params_as`] 一样反序列化路径参数，然后进行验证
+///
+/// # Errors
+///
+/// # 错误
+///
+/// - `RouteError::Validation` if deserialization fails (see [`get_params_as`])
+///   反序列化失败时返回 `RouteError::Validation`（见 [`get_params_as`]）
+/// - `RouteError::ValidationFailed` listing every rule that failed
+///   返回列出所有未通过规则的 `RouteError::ValidationFailed`
+pub fn get_valid_params<T: DeserializeOwned + Validate>(event: &Event) -> Result<T> {
+    let value: T = get_params_as(event)?;
+    value.validate()?;
+    Ok(value)
+}
@@ -2,9 +2,12 @@
 //!
 //! / 查询参数提取
 
+use serde::de::DeserializeOwned;
+
 use crate::{
     Event,
     error::{Result, RouteError},
+    validate::Validate,
 };
 
 /// Get all query parameters
@@ -84,3 +87,80 @@ pub fn get_query_param_required(event: &Event, key: &str) -> Result<String> {
     get_query_param(event, key)
         .ok_or_else(|| RouteError::bad_request(format!("Missing required query parameter: {key}")))
 }
+
+/// Deserialize all query parameters into a typed struct
+///
+/// / 将所有查询参数反序列化为类型化结构体
+///
+/// Unlike [`get_query`], repeated keys are preserved:
+///
+/// 与 [`get_query`] 不同，重复的键会被保留：
+///
+/// - `?tag=a&tag=b` or `?tag[]=a&tag[]=b` → `tag: Vec<String>`
+/// - `?filter[name]=x&filter[min]=3` → `filter: Filter` (nested struct)
+/// - `?page=2` → `page: u32`; `?page=` → `page: Option<u32>` is `None`
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::Validation` naming the offending field if a value
+/// cannot be parsed or a required field is missing.
+///
+/// 如果值无法解析或缺少必需字段，返回包含出错字段名的 `RouteError::Validation`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// struct Search {
+///     q: String,
+///     #[serde(default)]
+///     tag: Vec<String>,
+///     page: Option<u32>,
+/// }
+///
+/// // URL: /search?q=rust&tag=web&tag=async&page=2
+/// let search: Search = get_query_as(&event)?;
+/// ```
+pub fn get_query_as<T: DeserializeOwned>(event: &Event) -> Result<T> {
+    let query = event.uri().query().unwrap_or_default();
+    let pairs: Vec<(String, String)> = serde_urlencoded::from_str(query)
+        .map_err(|e| RouteError::bad_request(format!("Invalid query string: {e}")))?;
+    super::de::from_pairs(pairs)
+        .map_err(|e| RouteError::validation(super::de::error_message("query parameter", &e)))
+}
+
+/// Deserialize the query parameters like [`get_query_as`], then validate them
+///
+/// / 像 [`get_query_as`] 一样反序列化查询参数，然后进行验证
+///
+/// # Errors
+///
+/// # 错误
+///
+/// - `RouteError::Validation` if deserialization fails (see [`get_query_as`])
+///   反序列化失败时返回 `RouteError::Validation`（见 [`get_query_as`]）
+/// - `RouteError::ValidationFailed` listing every rule that failed
+///   返回列出所有未通过规则的 `RouteError::ValidationFailed`
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, Validate)]
+/// struct Page {
+///     #[validate(range(min = 1, max = 100))]
+///     per_page: u32,
+/// }
+///
+/// let page: Page = get_valid_query(&event)?;
+/// ```
+pub fn get_valid_query<T: DeserializeOwned + Validate>(event: &Event) -> Result<T> {
+    let value: T = get_query_as(event)?;
+    value.validate()?;
+    Ok(value)
+}
@@ -70,18 +70,31 @@
 //!   [`response`] - 响应构建器和辅助函数
 //! - [`error`] - Error types and result handling
 //!   [`error`] - 错误类型和结果处理
+//! - [`validate`] - Declarative request validation
+//!   [`validate`] - 声明式请求验证
+//! - [`cache`] - In-process response cache
+//!   [`cache`] - 进程内响应缓存
+//! - [`static_files`] - Static files from a `public/` directory
+//!   [`static_files`] - 来自 `public/` 目录的静态文件
+//! - `metrics` - Prometheus metrics (feature `metrics`)
+//!   `metrics` - Prometheus 指标（`metrics` feature）
 //!
 //! [Nitro]: https://nitro.unjs.io/
 //! [H3]: https://h3.unjs.io/
 
+pub mod cache;
 pub mod error;
 pub mod event;
 pub mod extract;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod middleware;
 #[cfg(feature = "openapi")]
 pub mod openapi;
 pub mod response;
 pub mod router;
+pub mod static_files;
+pub mod validate;
 
 // ============================================================================
 // Re-export dependencies - users don't need to depend on these crates directly
@@ -98,6 +111,9 @@ pub use bytes;
 /// Re-export of `comfy-table` - used by route macros
 /// / Re-export comfy-table - 由路由宏使用
 pub use comfy_table;
+/// Re-export of `cookie`
+/// / Re-export cookie
+pub use cookie;
 /// Re-export of `serde`
 /// / Re-export serde
 pub use serde;
@@ -144,10 +160,24 @@ pub use astrea_macro::generate_routes;
 /// ```
 pub mod prelude {
     pub use crate::error::{Result, RouteError};
-    pub use crate::event::Event;
+    pub use crate::event::{Event, ForwardedHeader, TrustedProxies};
     pub use crate::extract::*;
     pub use crate::middleware::{Middleware, MiddlewareMode};
-    pub use crate::response::{Response, bytes, html, json, no_content, redirect, text};
+    #[cfg(feature = "templates")]
+    pub use crate::response::render;
+    pub use crate::response::{
+        CacheControl, ETag, Response, attachment, bytes, file, html, json, json_canonical,
+        json_pretty, json_stream, json_with_status, ndjson, negotiate, no_content, redirect,
+        redirect_moved, redirect_permanent, redirect_see_other, redirect_temporary,
+        resolve_location, safe_redirect, text, try_json_stream, try_ndjson,
+    };
+    pub use crate::validate::{Validate, ValidationErrors};
+
+    // Re-export cookie types
+    // Re-export Cookie 类型
+    #[cfg(any(feature = "signed-cookies", feature = "private-cookies"))]
+    pub use cookie::Key;
+    pub use cookie::{Cookie, SameSite};
 
     // Re-export common Axum types
     // Re-export 常用 Axum 类型
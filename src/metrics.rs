@@ -0,0 +1,344 @@
+//! Prometheus metrics keyed by file routes
+//!
+//! / 以文件路由为键的 Prometheus 指标
+//!
+//! Enable with the `metrics` feature flag. [`layer`] records, for every
+//! request, a counter and a latency histogram labeled by method, route pattern
+//! and status class, plus an in-flight gauge. [`router`] serves them at
+//! `/metrics` in the Prometheus text exposition format.
+//!
+//! 通过 `metrics` feature flag 启用。[`layer`] 为每个请求记录按方法、路由模式和
+//! 状态类别标记的计数器和延迟直方图，以及进行中请求数的仪表。[`router`] 以
+//! Prometheus 文本格式在 `/metrics` 提供这些指标。
+//!
+//! Routes are labeled by their pattern (`/users/{id}`), never the concrete
+//! path, so cardinality is bounded by the route files. `create_router()`
+//! pre-registers every file route, so each series exists from startup.
+//!
+//! 路由以其模式（`/users/{id}`）而非具体路径标记，因此基数受路由文件数量限制。
+//! `create_router()` 会预先注册所有文件路由，使每个序列在启动时即存在。
+//!
+//! # Example
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! let app = routes::create_router()
+//!     .merge(astrea::metrics::router())
+//!     .layer(astrea::metrics::layer());
+//!
+//! // GET /metrics
+//! // astrea_http_requests_total{method="GET",route="/users/{id}",status="2xx"} 42
+//! ```
+
+use axum::extract::{MatchedPath, Request};
+use axum::http::{Method, header};
+use axum::response::Response as AxumResponse;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+/// Histogram bucket upper bounds in seconds (the Prometheus client defaults)
+/// / 直方图桶上界（秒），与 Prometheus 客户端默认值相同
+pub const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Route label for requests that matched no route
+/// / 未匹配任何路由的请求所使用的路由标签
+pub const UNMATCHED: &str = "unmatched";
+
+const STATUS_CLASSES: [&str; 5] = ["1xx", "2xx", "3xx", "4xx", "5xx"];
+
+// ============================================================================
+// 指标存储
+// ============================================================================
+
+#[derive(Default)]
+struct ClassSeries {
+    count: AtomicU64,
+    sum_nanos: AtomicU64,
+    buckets: [AtomicU64; BUCKETS.len()],
+}
+
+#[derive(Default)]
+struct RouteSeries {
+    in_flight: AtomicI64,
+    classes: [ClassSeries; STATUS_CLASSES.len()],
+}
+
+type Registry = RwLock<BTreeMap<(String, String), Arc<RouteSeries>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::default)
+}
+
+fn series(method: &str, route: &str) -> Arc<RouteSeries> {
+    let key = (method.to_string(), route.to_string());
+    if let Some(series) = registry()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get(&key)
+    {
+        return Arc::clone(series);
+    }
+    Arc::clone(
+        registry()
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .entry(key)
+            .or_default(),
+    )
+}
+
+/// Pre-register a route so its series are exported before the first request
+///
+/// / 预先注册路由，使其序列在第一个请求之前就被导出
+///
+/// Called by `create_router()` for every file route.
+///
+/// 由 `create_router()` 为每个文件路由调用。
+pub fn register_route(method: &str, route: &str) {
+    let _ = series(&method.to_ascii_uppercase(), route);
+}
+
+/// Keep the method label bounded: unknown methods are reported as `OTHER`
+/// / 限制方法标签的取值：未知方法记为 `OTHER`
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        Method::PATCH => "PATCH",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        Method::CONNECT => "CONNECT",
+        Method::TRACE => "TRACE",
+        _ => "OTHER",
+    }
+}
+
+impl RouteSeries {
+    fn observe(&self, status: u16, elapsed: std::time::Duration) {
+        let class = usize::from(status / 100)
+            .saturating_sub(1)
+            .min(STATUS_CLASSES.len() - 1);
+        let series = &self.classes[class];
+        let seconds = elapsed.as_secs_f64();
+        series.count.fetch_add(1, Ordering::Relaxed);
+        series.sum_nanos.fetch_add(
+            u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        for (bound, bucket) in BUCKETS.iter().zip(&series.buckets) {
+            if seconds <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Decrements the in-flight gauge even if the request future is dropped
+/// / 即使请求 future 被丢弃，也会递减进行中请求数
+struct InFlight(Arc<RouteSeries>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// ============================================================================
+// 中间件
+// ============================================================================
+
+/// Create the metrics layer
+///
+/// / 创建指标中间件层
+///
+/// Apply it to the whole application, or to a scope through
+/// [`Middleware::wrap`](crate::middleware::Middleware::wrap).
+///
+/// 可应用于整个应用，或通过 [`Middleware::wrap`](crate::middleware::Middleware::wrap)
+/// 应用于某个作用域。
+#[must_use]
+pub fn layer() -> MetricsLayer {
+    MetricsLayer
+}
+
+/// Tower layer recording request metrics, see [`layer`]
+/// / 记录请求指标的 Tower 层，参见 [`layer`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsLayer;
+
+impl<S> tower::Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService { inner }
+    }
+}
+
+/// Tower service produced by [`MetricsLayer`]
+/// / 由 [`MetricsLayer`] 生成的 Tower 服务
+#[derive(Debug, Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+}
+
+impl<S> tower::Service<Request> for MetricsService<S>
+where
+    S: tower::Service<Request, Response = AxumResponse>,
+    S::Future: Send + 'static,
+{
+    type Response = AxumResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<AxumResponse, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or(UNMATCHED, MatchedPath::as_str);
+        let series = series(method_label(request.method()), route);
+        series.in_flight.fetch_add(1, Ordering::Relaxed);
+        let guard = InFlight(series);
+        let start = Instant::now();
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let result = future.await;
+            if let Ok(response) = &result {
+                guard.0.observe(response.status().as_u16(), start.elapsed());
+            }
+            drop(guard);
+            result
+        })
+    }
+}
+
+// ============================================================================
+// 文本格式导出
+// ============================================================================
+
+/// Render every series in the Prometheus text exposition format
+/// / 以 Prometheus 文本格式渲染所有序列
+#[must_use]
+pub fn render() -> String {
+    let registry = registry()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let mut out = String::new();
+
+    out.push_str("# HELP astrea_http_requests_total Total number of HTTP requests.\n");
+    out.push_str("# TYPE astrea_http_requests_total counter\n");
+    for ((method, route), series) in registry.iter() {
+        let labels = labels(method, route);
+        for (class, data) in STATUS_CLASSES.iter().zip(&series.classes) {
+            let _ = writeln!(
+                out,
+                "astrea_http_requests_total{{{labels},status=\"{class}\"}} {}",
+                data.count.load(Ordering::Relaxed)
+            );
+        }
+    }
+
+    out.push_str("# HELP astrea_http_request_duration_seconds HTTP request latency.\n");
+    out.push_str("# TYPE astrea_http_request_duration_seconds histogram\n");
+    for ((method, route), series) in registry.iter() {
+        let labels = labels(method, route);
+        for (class, data) in STATUS_CLASSES.iter().zip(&series.classes) {
+            let count = data.count.load(Ordering::Relaxed);
+            if count == 0 {
+                continue;
+            }
+            let labels = format!("{labels},status=\"{class}\"");
+            for (bound, bucket) in BUCKETS.iter().zip(&data.buckets) {
+                let _ = writeln!(
+                    out,
+                    "astrea_http_request_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {}",
+                    bucket.load(Ordering::Relaxed)
+                );
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let sum = data.sum_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+            let _ = writeln!(
+                out,
+                "astrea_http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {count}"
+            );
+            let _ = writeln!(
+                out,
+                "astrea_http_request_duration_seconds_sum{{{labels}}} {sum}"
+            );
+            let _ = writeln!(
+                out,
+                "astrea_http_request_duration_seconds_count{{{labels}}} {count}"
+            );
+        }
+    }
+
+    out.push_str("# HELP astrea_http_requests_in_flight HTTP requests currently being handled.\n");
+    out.push_str("# TYPE astrea_http_requests_in_flight gauge\n");
+    for ((method, route), series) in registry.iter() {
+        let _ = writeln!(
+            out,
+            "astrea_http_requests_in_flight{{{}}} {}",
+            labels(method, route),
+            series.in_flight.load(Ordering::Relaxed)
+        );
+    }
+
+    out
+}
+
+fn labels(method: &str, route: &str) -> String {
+    format!("method=\"{}\",route=\"{}\"", escape(method), escape(route))
+}
+
+/// Escape a label value (`\`, `"` and newlines)
+/// / 转义标签值（`\`、`"` 和换行符）
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Create a router serving `GET /metrics`
+///
+/// / 创建提供 `GET /metrics` 的路由器
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let app = routes::create_router()
+///     .merge(astrea::metrics::router())
+///     .layer(astrea::metrics::layer());
+/// ```
+pub fn router<S: Clone + Send + Sync + 'static>() -> axum::Router<S> {
+    axum::Router::new().route(
+        "/metrics",
+        axum::routing::get(|| async {
+            (
+                [(
+                    header::CONTENT_TYPE,
+                    "text/plain; version=0.0.4; charset=utf-8",
+                )],
+                render(),
+            )
+        }),
+    )
+}
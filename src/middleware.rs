@@ -51,6 +51,30 @@
 //! 子中间件**替换**父中间件。仅子中间件生效。
 //! 适用于需要完全不同中间件的路由（如应跳过认证的公开端点）。
 //!
+//! # Names and Priorities — 名称与优先级
+//!
+//! # 名称与优先级
+//!
+//! Each scope may set a [`name`](Middleware::name) (shown in the startup table and
+//! returned by `routes::middleware_chain`) and a [`priority`](Middleware::priority).
+//! Higher priorities run further out regardless of directory depth; equal
+//! priorities (the default is `0`) keep the parent → child order. See
+//! [`crate::router`] for the exact resolution rules.
+//!
+//! 每个作用域可以设置 [`name`](Middleware::name)（显示在启动表格中，并由
+//! `routes::middleware_chain` 返回）和 [`priority`](Middleware::priority)。
+//! 优先级越高越靠外层，与目录深度无关；优先级相同（默认 `0`）时保持父 → 子顺序。
+//! 具体解析规则见 [`crate::router`]。
+//!
+//! ```rust,ignore
+//! // Inspect the resolved chain in tests (outermost first)
+//! // 在测试中查看解析后的中间件链（最外层在前）
+//! assert_eq!(
+//!     routes::middleware_chain("GET", "/api/users/42"),
+//!     Some(vec!["root".to_string(), "auth".to_string()]),
+//! );
+//! ```
+//!
 //! # Example
 //!
 //! # 示例
@@ -108,6 +132,8 @@
 //! }
 //! ```
 
+pub use crate::response::cache_control::CacheControl;
+
 // ============================================================================
 // MiddlewareMode
 // ============================================================================
@@ -152,6 +178,10 @@ pub enum MiddlewareMode {
 // 中间件配置
 // ============================================================================
 
+/// Boxed router wrapper function stored by [`Middleware::wrap`]
+/// / 由 [`Middleware::wrap`] 存储的装箱路由包装函数
+type RouterWrapper<S> = Box<dyn FnOnce(axum::Router<S>) -> axum::Router<S>>;
+
 /// Middleware configuration returned by `_middleware.rs` files
 ///
 /// / `_middleware.rs` 文件返回的中间件配置
@@ -180,9 +210,40 @@ pub struct Middleware<S = ()> {
     /// / 此中间件与父中间件的交互方式
     pub mode: MiddlewareMode,
 
+    /// Display name used in the startup table and `middleware_chain` queries
+    /// / 用于启动表格和 `middleware_chain` 查询的显示名称
+    ///
+    /// Defaults to the scope path (e.g., `"/api"`) when unset.
+    ///
+    /// 未设置时默认为作用域路径（如 `"/api"`）。
+    pub name: Option<String>,
+
+    /// Ordering priority — higher values run further out (default `0`)
+    /// / 排序优先级 — 值越高越靠外层（默认 `0`）
+    pub priority: i32,
+
+    /// Request body limit in bytes for routes in this scope
+    /// / 此作用域中路由的请求体大小限制（字节）
+    ///
+    /// Unset scopes inherit the nearest parent's limit, falling back to Axum's
+    /// `DefaultBodyLimit` ([`DEFAULT_BODY_LIMIT`](crate::event::body::DEFAULT_BODY_LIMIT)
+    /// when no such layer is installed).
+    ///
+    /// 未设置时继承最近父作用域的限制，否则使用 Axum 的 `DefaultBodyLimit`（未安装该层时为
+    /// [`DEFAULT_BODY_LIMIT`](crate::event::body::DEFAULT_BODY_LIMIT)）。
+    pub body_limit: Option<usize>,
+
+    /// Default `Cache-Control` for successful responses of routes in this scope
+    /// / 此作用域中路由成功响应的默认 `Cache-Control`
+    ///
+    /// Unset scopes inherit the nearest parent's policy.
+    ///
+    /// 未设置时继承最近父作用域的策略。
+    pub cache_control: Option<CacheControl>,
+
     /// Function that wraps a Router with middleware layers
     /// / 将中间件层应用到路由器的函数
-    wrapper: Option<Box<dyn FnOnce(axum::Router<S>) -> axum::Router<S>>>,
+    wrapper: Option<RouterWrapper<S>>,
 }
 
 impl<S> Default for Middleware<S> {
@@ -212,6 +273,10 @@ impl<S> Middleware<S> {
     pub fn new() -> Self {
         Self {
             mode: MiddlewareMode::Extend,
+            name: None,
+            priority: 0,
+            body_limit: None,
+            cache_control: None,
             wrapper: None,
         }
     }
@@ -242,6 +307,10 @@ impl<S> Middleware<S> {
     pub fn override_parent() -> Self {
         Self {
             mode: MiddlewareMode::Override,
+            name: None,
+            priority: 0,
+            body_limit: None,
+            cache_control: None,
             wrapper: None,
         }
     }
@@ -255,6 +324,102 @@ impl<S> Middleware<S> {
         self
     }
 
+    /// Set the display name of this middleware
+    ///
+    /// / 设置此中间件的显示名称
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// Middleware::new()
+    ///     .name("auth")
+    ///     .wrap(|router| router.layer(auth_layer))
+    /// ```
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the ordering priority
+    ///
+    /// / 设置排序优先级
+    ///
+    /// Within a route's middleware chain, scopes with a higher priority are
+    /// applied further out (they see the request earlier). Scopes with equal
+    /// priority keep the default parent → child order.
+    ///
+    /// 在路由的中间件链中，优先级更高的作用域位于更外层（更早看到请求）。
+    /// 优先级相同的作用域保持默认的父 → 子顺序。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// // routes/api/_middleware.rs — run request ID tagging before root middleware
+    /// Middleware::new()
+    ///     .name("request-id")
+    ///     .priority(100)
+    ///     .wrap(|router| router.layer(request_id_layer))
+    /// ```
+    #[must_use]
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the request body limit for routes in this scope
+    ///
+    /// / 设置此作用域中路由的请求体大小限制
+    ///
+    /// Requests whose body exceeds the limit are rejected with
+    /// `413 Payload Too Large`. A route's own `#[route(body_limit = ...)]`
+    /// takes precedence over this value.
+    ///
+    /// 请求体超过限制的请求将以 `413 Payload Too Large` 拒绝。
+    /// 路由自身的 `#[route(body_limit = ...)]` 优先于此值。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// // routes/uploads/_middleware.rs — allow 50 MiB uploads
+    /// Middleware::new().body_limit(50 * 1024 * 1024)
+    /// ```
+    #[must_use]
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = Some(limit);
+        self
+    }
+
+    /// Set the default `Cache-Control` for routes in this scope
+    ///
+    /// / 设置此作用域中路由的默认 `Cache-Control`
+    ///
+    /// Applied to successful responses that don't set their own
+    /// `Cache-Control`. A route's `#[route(cache = "...")]` takes precedence.
+    ///
+    /// 应用于未自行设置 `Cache-Control` 的成功响应。
+    /// 路由自身的 `#[route(cache = "...")]` 优先于此值。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// // routes/api/_middleware.rs — API responses are private by default
+    /// Middleware::new().cache_control(CacheControl::new().private().no_cache())
+    /// ```
+    #[must_use]
+    pub fn cache_control(mut self, policy: CacheControl) -> Self {
+        self.cache_control = Some(policy);
+        self
+    }
+
     /// Set the wrapper function that applies middleware layers to a Router
     ///
     /// / 设置将中间件层应用到路由器的包装函数
@@ -305,6 +470,10 @@ impl<S> std::fmt::Debug for Middleware<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Middleware")
             .field("mode", &self.mode)
+            .field("name", &self.name)
+            .field("priority", &self.priority)
+            .field("body_limit", &self.body_limit)
+            .field("cache_control", &self.cache_control)
             .field("has_wrapper", &self.wrapper.is_some())
             .finish()
     }
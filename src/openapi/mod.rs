@@ -35,11 +35,13 @@
 //! // And GET /swagger shows Swagger UI
 //! ```
 
+mod reflect;
 pub mod registry;
 mod spec;
 mod swagger;
 pub mod types;
 
+pub use reflect::{params_of, schema_of, validated_params_of};
 pub use registry::register;
 pub use types::*;
 
@@ -0,0 +1,535 @@
+//! Parameter discovery from `Deserialize` types
+//!
+//! / 从 `Deserialize` 类型发现参数
+//!
+//! The `#[route]` macro only sees the *name* of the type passed to
+//! `get_query_as::<T>` / `get_params_as::<T>`. Its fields are discovered at
+//! runtime by driving `T::deserialize` with a tracing deserializer that
+//! records every field name and the primitive type serde asks for.
+//!
+//! `#[route]` 宏只能看到传给 `get_query_as::<T>` / `get_params_as::<T>` 的类型*名*。
+//! 其字段在运行时通过一个跟踪反序列化器驱动 `T::deserialize` 来发现，
+//! 该反序列化器会记录每个字段名以及 serde 请求的基本类型。
+//!
+//! A field is documented as optional when `T` still deserializes without it
+//! (`Option<_>` or `#[serde(default)]`). Nested structs are flattened into
+//! bracketed names such as `filter[name]`, matching `get_query_as`.
+//!
+//! 如果缺少某字段时 `T` 仍能反序列化（`Option<_>` 或 `#[serde(default)]`），
+//! 该字段被记录为可选。嵌套结构体会展开为 `filter[name]` 形式的方括号名称，
+//! 与 `get_query_as` 一致。
+//!
+//! For types that implement [`Validate`], the declared rules are added as
+//! schema keywords (`minLength`, `maximum`, `pattern`, `format: email`, ...).
+//!
+//! 对于实现了 [`Validate`] 的类型，声明的规则会作为模式关键字
+//! （`minLength`、`maximum`、`pattern`、`format: email` 等）添加。
+
+use serde::de::value::{Error, StrDeserializer};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde_json::{Map, Value, json};
+
+use super::types::{ParamLocation, ParamMeta};
+use crate::validate::{Constraint, Validate};
+
+/// Derive OpenAPI parameter entries from the fields of `T`
+///
+/// / 从 `T` 的字段派生 OpenAPI 参数条目
+///
+/// Called from generated `__openapi_meta()` code when a handler uses
+/// `get_query_as::<T>` or `get_params_as::<T>`. Returns an empty list if `T`
+/// is not a plain struct.
+///
+/// 当处理函数使用 `get_query_as::<T>` 或 `get_params_as::<T>` 时，
+/// 由生成的 `__openapi_meta()` 代码调用。如果 `T` 不是普通结构体，返回空列表。
+pub fn params_of<T: DeserializeOwned>(location: ParamLocation) -> Vec<ParamMeta> {
+    let (traced, skippable) = trace::<T>();
+    traced
+        .into_iter()
+        .map(|t| ParamMeta {
+            required: !t.optional && !skippable.contains(&t.root),
+            name: t.name,
+            location: location.clone(),
+            schema_type: t.schema_type.to_string(),
+            schema_format: t.schema_format.map(str::to_string),
+            item_type: t.item_type.map(str::to_string),
+            schema_keywords: Map::new(),
+        })
+        .collect()
+}
+
+/// Trace every field of `T`, plus the top-level fields that can be left out
+///
+/// / 跟踪 `T` 的每个字段，以及可以省略的顶层字段
+fn trace<T: DeserializeOwned>() -> (Vec<Traced>, Vec<&'static str>) {
+    let mut fields = None;
+    let mut traced = Vec::new();
+    let _ = T::deserialize(RootTracer {
+        fields: &mut fields,
+        skip: None,
+        out: &mut traced,
+    });
+
+    // Which top-level fields can be left out entirely
+    // 哪些顶层字段可以完全省略
+    let skippable: Vec<&str> = fields
+        .unwrap_or_default()
+        .iter()
+        .copied()
+        .filter(|&field| {
+            T::deserialize(RootTracer {
+                fields: &mut None,
+                skip: Some(field),
+                out: &mut Vec::new(),
+            })
+            .is_ok()
+        })
+        .collect();
+
+    (traced, skippable)
+}
+
+/// Like [`params_of`], with `T`'s validation rules added as schema keywords
+///
+/// / 与 [`params_of`] 相同，并将 `T` 的验证规则添加为模式关键字
+///
+/// Called from generated `__openapi_meta()` code for `get_valid_query::<T>`
+/// and `get_valid_params::<T>`.
+///
+/// 由生成的 `__openapi_meta()` 代码为 `get_valid_query::<T>` 和
+/// `get_valid_params::<T>` 调用。
+pub fn validated_params_of<T: DeserializeOwned + Validate>(
+    location: ParamLocation,
+) -> Vec<ParamMeta> {
+    let mut params = params_of::<T>(location);
+    for (path, constraint) in T::constraints() {
+        if path.contains('[') {
+            continue;
+        }
+        // `filter.min` is documented as `filter[min]`
+        // `filter.min` 记录为 `filter[min]`
+        let mut segments = path.split('.');
+        let mut name = segments.next().unwrap_or_default().to_string();
+        for segment in segments {
+            name = format!("{name}[{segment}]");
+        }
+        if let Some(param) = params.iter_mut().find(|p| p.name == name) {
+            let keywords = schema_keywords(&constraint, &param.schema_type);
+            param.schema_keywords.extend(keywords);
+        }
+    }
+    params
+}
+
+/// Build an object schema for `T` from its fields and validation rules
+///
+/// / 根据字段和验证规则为 `T` 构建对象模式
+///
+/// Called from generated `__openapi_meta()` code for `get_valid_body::<T>`.
+/// The result is placed in `components/schemas`.
+///
+/// 由生成的 `__openapi_meta()` 代码为 `get_valid_body::<T>` 调用，
+/// 结果放在 `components/schemas` 中。
+pub fn schema_of<T: DeserializeOwned + Validate>() -> Value {
+    let mut root = json!({ "type": "object", "properties": {} });
+    let (traced, skippable) = trace::<T>();
+    for t in traced {
+        let segments = bracket_segments(&t.name);
+        let mut leaf = json!({ "type": t.schema_type });
+        if let Some(format) = t.schema_format {
+            leaf["format"] = json!(format);
+        }
+        if let Some(item_type) = t.item_type {
+            leaf["items"] = json!({ "type": item_type });
+        }
+
+        let mut node = &mut root;
+        for (i, segment) in segments.iter().enumerate() {
+            // Top-level fields are required unless `T` deserializes without them;
+            // nested ones unless they are `Option`
+            // 顶层字段除非 `T` 缺少它们也能反序列化，否则为必需；嵌套字段除非是 `Option`
+            let required = if i == 0 {
+                !skippable.contains(&t.root)
+            } else {
+                !t.optional
+            };
+            if required {
+                push_required(node, segment);
+            }
+            let child = if i + 1 == segments.len() {
+                leaf.clone()
+            } else {
+                json!({ "type": "object", "properties": {} })
+            };
+            node = node["properties"]
+                .as_object_mut()
+                .expect("object schema has properties")
+                .entry(segment.clone())
+                .or_insert(child);
+        }
+    }
+
+    for (path, constraint) in T::constraints() {
+        if path.contains('[') {
+            continue;
+        }
+        let mut node = Some(&mut root);
+        for segment in path.split('.') {
+            node = node
+                .and_then(|n| n.get_mut("properties"))
+                .and_then(|p| p.get_mut(segment));
+        }
+        if let Some(Value::Object(schema)) = node {
+            let schema_type = schema
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            schema.extend(schema_keywords(&constraint, &schema_type));
+        }
+    }
+    root
+}
+
+/// Split `a[b][c]` into `["a", "b", "c"]`
+/// / 将 `a[b][c]` 拆分为 `["a", "b", "c"]`
+fn bracket_segments(name: &str) -> Vec<String> {
+    name.split('[')
+        .map(|s| s.trim_end_matches(']').to_string())
+        .collect()
+}
+
+fn push_required(schema: &mut Value, name: &str) {
+    let required = schema
+        .as_object_mut()
+        .expect("object schema")
+        .entry("required")
+        .or_insert_with(|| json!([]));
+    if let Value::Array(names) = required
+        && !names.iter().any(|n| n == name)
+    {
+        names.push(json!(name));
+    }
+}
+
+/// Schema keywords for a validation rule on a field of the given schema type
+///
+/// / 给定模式类型字段上验证规则对应的模式关键字
+fn schema_keywords(constraint: &Constraint, schema_type: &str) -> Map<String, Value> {
+    let mut keywords = Map::new();
+    match constraint {
+        Constraint::Length { min, max } => {
+            let (min_key, max_key) = match schema_type {
+                "array" => ("minItems", "maxItems"),
+                "object" => ("minProperties", "maxProperties"),
+                _ => ("minLength", "maxLength"),
+            };
+            if let Some(min) = min {
+                keywords.insert(min_key.to_string(), json!(min));
+            }
+            if let Some(max) = max {
+                keywords.insert(max_key.to_string(), json!(max));
+            }
+        }
+        Constraint::Range { min, max } => {
+            if let Some(min) = min {
+                keywords.insert("minimum".to_string(), json!(min));
+            }
+            if let Some(max) = max {
+                keywords.insert("maximum".to_string(), json!(max));
+            }
+        }
+        Constraint::Regex(pattern) => {
+            keywords.insert("pattern".to_string(), json!(pattern));
+        }
+        Constraint::Email => {
+            keywords.insert("format".to_string(), json!("email"));
+        }
+    }
+    keywords
+}
+
+/// A single field recorded by the tracer
+/// / 跟踪器记录的单个字段
+struct Traced {
+    root: &'static str,
+    name: String,
+    optional: bool,
+    schema_type: &'static str,
+    schema_format: Option<&'static str>,
+    item_type: Option<&'static str>,
+}
+
+// ============================================================================
+// Root tracer (the struct itself)
+// 根跟踪器（结构体本身）
+// ============================================================================
+
+struct RootTracer<'a> {
+    fields: &'a mut Option<&'static [&'static str]>,
+    skip: Option<&'static str>,
+    out: &'a mut Vec<Traced>,
+}
+
+impl<'de> de::Deserializer<'de> for RootTracer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(de::Error::custom("only structs can be traced"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        *self.fields = Some(fields);
+        visitor.visit_map(FieldsAccess {
+            fields: fields.iter(),
+            skip: self.skip,
+            parent: None,
+            optional: false,
+            out: self.out,
+            current: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Feeds every field name (except `skip`) to a struct visitor
+/// / 将每个字段名（`skip` 除外）提供给结构体访问器
+struct FieldsAccess<'a> {
+    fields: std::slice::Iter<'static, &'static str>,
+    skip: Option<&'static str>,
+    /// `(root field, bracketed name)` of the enclosing struct field, if nested
+    /// / 外层结构体字段的 `(根字段, 方括号名称)`（嵌套时）
+    parent: Option<(&'static str, String)>,
+    optional: bool,
+    out: &'a mut Vec<Traced>,
+    current: Option<&'static str>,
+}
+
+impl<'de> de::MapAccess<'de> for FieldsAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let skip = self.skip;
+        let Some(&field) = self.fields.find(|&&f| Some(f) != skip) else {
+            return Ok(None);
+        };
+        self.current = Some(field);
+        let key: StrDeserializer<'_, Error> = field.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let field = self.current.take().unwrap_or_default();
+        let (root, name) = match &self.parent {
+            Some((root, prefix)) => (*root, format!("{prefix}[{field}]")),
+            None => (field, field.to_string()),
+        };
+        seed.deserialize(FieldTracer {
+            root,
+            name,
+            optional: self.optional,
+            out: self.out,
+        })
+    }
+}
+
+// ============================================================================
+// Field tracer
+// 字段跟踪器
+// ============================================================================
+
+struct FieldTracer<'a> {
+    root: &'static str,
+    name: String,
+    optional: bool,
+    out: &'a mut Vec<Traced>,
+}
+
+impl FieldTracer<'_> {
+    fn record(
+        self,
+        schema_type: &'static str,
+        schema_format: Option<&'static str>,
+        item_type: Option<&'static str>,
+    ) {
+        self.out.push(Traced {
+            root: self.root,
+            name: self.name,
+            optional: self.optional,
+            schema_type,
+            schema_format,
+            item_type,
+        });
+    }
+}
+
+macro_rules! trace_scalar {
+    ($($method:ident => $visit:ident($value:expr): $ty:literal, $format:expr;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.record($ty, $format, None);
+                visitor.$visit($value)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for FieldTracer<'_> {
+    type Error = Error;
+
+    trace_scalar! {
+        deserialize_bool => visit_bool(false): "boolean", None;
+        deserialize_i8 => visit_i8(0): "integer", Some("int8");
+        deserialize_i16 => visit_i16(0): "integer", Some("int16");
+        deserialize_i32 => visit_i32(0): "integer", Some("int32");
+        deserialize_i64 => visit_i64(0): "integer", Some("int64");
+        deserialize_i128 => visit_i128(0): "integer", Some("int128");
+        deserialize_u8 => visit_u8(0): "integer", Some("uint8");
+        deserialize_u16 => visit_u16(0): "integer", Some("uint16");
+        deserialize_u32 => visit_u32(0): "integer", Some("uint32");
+        deserialize_u64 => visit_u64(0): "integer", Some("uint64");
+        deserialize_u128 => visit_u128(0): "integer", Some("uint128");
+        deserialize_f32 => visit_f32(0.0): "number", Some("float");
+        deserialize_f64 => visit_f64(0.0): "number", Some("double");
+        deserialize_char => visit_char(' '): "string", None;
+        deserialize_any => visit_str(""): "string", None;
+        deserialize_str => visit_str(""): "string", None;
+        deserialize_string => visit_str(""): "string", None;
+        deserialize_identifier => visit_str(""): "string", None;
+        deserialize_bytes => visit_str(""): "string", None;
+        deserialize_byte_buf => visit_str(""): "string", None;
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.optional = true;
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut items = Vec::new();
+        let value = visitor.visit_seq(SingleItem {
+            tracer: Some(FieldTracer {
+                root: self.root,
+                name: self.name.clone(),
+                optional: self.optional,
+                out: &mut items,
+            }),
+        })?;
+        let item_type = items.first().map_or("string", |t| t.schema_type);
+        self.record("array", None, Some(item_type));
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_map(FieldsAccess {
+            fields: fields.iter(),
+            skip: None,
+            parent: Some((self.root, self.name)),
+            optional: self.optional,
+            out: self.out,
+            current: None,
+        })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.record("object", None, None);
+        visitor.visit_map(de::value::MapDeserializer::new(std::iter::empty::<(
+            &str,
+            &str,
+        )>()))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.record("string", None, None);
+        let variant: StrDeserializer<'_, Error> = variants
+            .first()
+            .copied()
+            .unwrap_or_default()
+            .into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+/// A sequence that yields exactly one traced element
+/// / 只产生一个被跟踪元素的序列
+struct SingleItem<'a> {
+    tracer: Option<FieldTracer<'a>>,
+}
+
+impl<'de> de::SeqAccess<'de> for SingleItem<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.tracer.take() {
+            Some(tracer) => seed.deserialize(tracer).map(Some),
+            None => Ok(None),
+        }
+    }
+}
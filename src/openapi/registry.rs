@@ -36,6 +36,8 @@ pub fn register(method: &str, path: &str, operation_id: &str, mut handler_meta:
                 required: true,
                 schema_type: "string".to_string(),
                 schema_format: None,
+                item_type: None,
+                schema_keywords: serde_json::Map::new(),
             });
         }
     }
@@ -58,10 +58,11 @@ pub fn generate_spec(title: &str, version: &str) -> Value {
 
     // Add request body type references as component schemas (placeholder)
     // 添加请求体类型引用作为组件模式（占位符）
-    let body_types: Vec<String> = entries
+    let body_types: Vec<(String, Option<Value>)> = entries
         .iter()
         .filter_map(|e| e.handler_meta.request_body.as_ref())
-        .map(|b| b.schema_type_name.clone())
+        .filter(|b| !b.schema_type_name.is_empty())
+        .map(|b| (b.schema_type_name.clone(), b.schema.clone()))
         .collect();
 
     if !body_types.is_empty() {
@@ -75,13 +76,22 @@ pub fn generate_spec(title: &str, version: &str) -> Value {
         };
 
         let mut schemas = serde_json::Map::new();
-        for type_name in body_types {
-            schemas.entry(type_name).or_insert_with(|| {
-                json!({
-                    "type": "object",
-                    "description": "Auto-detected request body type (schema details require manual definition or a derive macro)",
-                })
-            });
+        for (type_name, schema) in body_types {
+            // A reflected schema wins over the placeholder
+            // 反射得到的模式优先于占位符
+            match schema {
+                Some(schema) => {
+                    schemas.insert(type_name, schema);
+                }
+                None => {
+                    schemas.entry(type_name).or_insert_with(|| {
+                        json!({
+                            "type": "object",
+                            "description": "Auto-detected request body type (schema details require manual definition or a derive macro)",
+                        })
+                    });
+                }
+            }
         }
         components.insert("schemas".to_string(), Value::Object(schemas));
     }
@@ -128,6 +138,7 @@ fn build_operation(entry: &RouteEntry) -> Value {
                 let location = match p.location {
                     ParamLocation::Path => "path",
                     ParamLocation::Query => "query",
+                    ParamLocation::Cookie => "cookie",
                 };
 
                 let mut schema = serde_json::Map::new();
@@ -135,6 +146,10 @@ fn build_operation(entry: &RouteEntry) -> Value {
                 if let Some(fmt) = &p.schema_format {
                     schema.insert("format".to_string(), json!(fmt));
                 }
+                if let Some(item_type) = &p.item_type {
+                    schema.insert("items".to_string(), json!({ "type": item_type }));
+                }
+                schema.extend(p.schema_keywords.clone());
 
                 json!({
                     "name": p.name,
@@ -149,17 +164,44 @@ fn build_operation(entry: &RouteEntry) -> Value {
 
     // requestBody
     if let Some(body) = &meta.request_body {
+        let schema = if body.schema_type_name.is_empty() {
+            // Form bodies are described inline from the detected fields
+            // 表单请求体根据检测到的字段内联描述
+            let props: serde_json::Map<String, Value> = body
+                .form_fields
+                .iter()
+                .map(|f| {
+                    let field_schema = if f.binary {
+                        json!({ "type": "string", "format": "binary" })
+                    } else {
+                        json!({ "type": "string" })
+                    };
+                    (f.name.clone(), field_schema)
+                })
+                .collect();
+            json!({
+                "type": "object",
+                "properties": Value::Object(props),
+            })
+        } else {
+            json!({
+                "$ref": format!("#/components/schemas/{}", body.schema_type_name),
+            })
+        };
+        let mut content = serde_json::Map::new();
+        let content_types = if body.content_types.is_empty() {
+            std::slice::from_ref(&body.content_type)
+        } else {
+            body.content_types.as_slice()
+        };
+        for ct in content_types {
+            content.insert(ct.clone(), json!({ "schema": schema.clone() }));
+        }
         operation.insert(
             "requestBody".to_string(),
             json!({
                 "required": true,
-                "content": {
-                    &body.content_type: {
-                        "schema": {
-                            "$ref": format!("#/components/schemas/{}", body.schema_type_name),
-                        }
-                    }
-                }
+                "content": Value::Object(content),
             }),
         );
     }
@@ -13,6 +13,9 @@ pub enum ParamLocation {
     /// Query parameter (e.g., `?page=1`)
     /// / 查询参数（如 `?page=1`）
     Query,
+    /// Cookie parameter (e.g., `Cookie: session=abc`)
+    /// / Cookie 参数（如 `Cookie: session=abc`）
+    Cookie,
 }
 
 /// Metadata about a single operation parameter
@@ -35,6 +38,12 @@ pub struct ParamMeta {
     /// OpenAPI schema format: "uint32", "int64", "float", etc.
     /// / OpenAPI 模式格式
     pub schema_format: Option<String>,
+    /// OpenAPI type of the array items when `schema_type` is `"array"`
+    /// / 当 `schema_type` 为 `"array"` 时数组元素的 OpenAPI 类型
+    pub item_type: Option<String>,
+    /// Extra schema keywords from validation rules, e.g. `minLength`, `maximum`
+    /// / 来自验证规则的额外模式关键字，如 `minLength`、`maximum`
+    pub schema_keywords: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Metadata about a request body
@@ -42,12 +51,41 @@ pub struct ParamMeta {
 /// / 请求体元数据
 #[derive(Debug, Clone)]
 pub struct RequestBodyMeta {
-    /// Content type, e.g., "application/json"
-    /// / 内容类型
+    /// Primary content type, e.g., "application/json"
+    /// / 主要内容类型
     pub content_type: String,
+    /// Every accepted content type, `content_type` first
+    /// / 所有接受的内容类型，`content_type` 在前
+    pub content_types: Vec<String>,
     /// Rust type name used as schema reference, e.g., "CreateUserRequest"
     /// / 用作 schema 引用的 Rust 类型名
+    ///
+    /// Empty for `multipart/form-data` bodies, which are described inline
+    /// by `form_fields` instead.
+    /// / 对于 `multipart/form-data` 请求体为空，改由 `form_fields` 内联描述。
     pub schema_type_name: String,
+    /// Schema of `schema_type_name` reflected from a `get_valid_body::<T>` type
+    /// / 从 `get_valid_body::<T>` 类型反射得到的 `schema_type_name` 模式
+    ///
+    /// `None` leaves a placeholder object in `components/schemas`.
+    /// / 为 `None` 时在 `components/schemas` 中保留占位对象。
+    pub schema: Option<serde_json::Value>,
+    /// Form fields detected for `multipart/form-data` bodies
+    /// / 为 `multipart/form-data` 请求体检测到的表单字段
+    pub form_fields: Vec<FormFieldMeta>,
+}
+
+/// Metadata about a single `multipart/form-data` field
+///
+/// / 单个 `multipart/form-data` 字段的元数据
+#[derive(Debug, Clone)]
+pub struct FormFieldMeta {
+    /// Field name
+    /// / 字段名
+    pub name: String,
+    /// Whether the field is a file upload (`format: binary`)
+    /// / 字段是否为文件上传（`format: binary`）
+    pub binary: bool,
 }
 
 /// Metadata extracted from a handler function by the `#[route]` macro
@@ -49,8 +49,94 @@ use super::Response;
 /// - [`serde_json::json`] macro for creating JSON values
 ///   [`serde_json::json`] 宏 - 用于创建 JSON 值
 pub fn json<T: Serialize>(data: T) -> Result<Response> {
-    let body = serde_json::to_vec(&data)
-        .map_err(|e| RouteError::Internal(anyhow::anyhow!("Failed to serialize JSON: {e}")))?;
+    json_response(serde_json::to_vec(&data))
+}
+
+/// Create a JSON response with a status code
+///
+/// / 创建带状态码的 JSON 响应
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::Internal` if serialization fails.
+///
+/// 如果序列化失败，返回 `RouteError::Internal`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// json_with_status(StatusCode::CREATED, &user)
+/// ```
+pub fn json_with_status<T: Serialize>(status: StatusCode, data: T) -> Result<Response> {
+    Ok(json(data)?.status(status))
+}
+
+/// Create an indented JSON response
+///
+/// / 创建带缩进的 JSON 响应
+///
+/// In debug builds, any JSON response of a `#[route]` handler is also
+/// indented when the request has `?pretty=1`.
+///
+/// 在 debug 构建中，当请求带有 `?pretty=1` 时，`#[route]` 处理函数的任何 JSON 响应也会缩进。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::Internal` if serialization fails.
+///
+/// 如果序列化失败，返回 `RouteError::Internal`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// json_pretty(json!({ "status": "ok" }))
+/// ```
+pub fn json_pretty<T: Serialize>(data: T) -> Result<Response> {
+    json_response(serde_json::to_vec_pretty(&data))
+}
+
+/// Create a canonical JSON response: object keys sorted, no whitespace
+///
+/// / 创建规范化的 JSON 响应：对象键排序，无空白
+///
+/// The same data always produces the same bytes, whatever the field order of
+/// the source types or maps. Use it for snapshot tests and signed payloads.
+///
+/// 相同的数据总是产生相同的字节，与源类型或映射的字段顺序无关。
+/// 适用于快照测试和签名负载。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::Internal` if serialization fails.
+///
+/// 如果序列化失败，返回 `RouteError::Internal`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let response = json_canonical(&payload)?;
+/// let signature = sign(&response.body);
+/// response.header("X-Signature", &signature)
+/// ```
+pub fn json_canonical<T: Serialize>(data: T) -> Result<Response> {
+    json_response(serde_json::to_value(&data).and_then(|v| serde_json::to_vec(&sort_keys(v))))
+}
+
+fn json_response(body: serde_json::Result<Vec<u8>>) -> Result<Response> {
+    let body =
+        body.map_err(|e| RouteError::Internal(anyhow::anyhow!("Failed to serialize JSON: {e}")))?;
 
     let mut headers = HeaderMap::new();
     headers.insert(
@@ -62,9 +148,77 @@ pub fn json<T: Serialize>(data: T) -> Result<Response> {
         status: StatusCode::OK,
         headers,
         body,
+        streaming: None,
     })
 }
 
+/// Rebuild every object with its keys in sorted order
+/// / 以排序后的键重建每个对象
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_keys(v)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+        }
+        other => other,
+    }
+}
+
+/// Whether the query asks for indented JSON (`?pretty`, `?pretty=1`, `?pretty=true`)
+///
+/// / 查询参数是否请求缩进的 JSON（`?pretty`、`?pretty=1`、`?pretty=true`）
+///
+/// Used by the `#[route]` wrapper in debug builds.
+///
+/// 由 `#[route]` 包装函数在 debug 构建中使用。
+#[doc(hidden)]
+#[must_use]
+pub fn wants_pretty(query: &std::collections::HashMap<String, String>) -> bool {
+    query
+        .get("pretty")
+        .is_some_and(|v| matches!(v.as_str(), "" | "1" | "true"))
+}
+
+/// Re-indent a buffered JSON response, keeping its key order
+///
+/// / 重新缩进已缓冲的 JSON 响应，保留键的顺序
+///
+/// Other responses are returned unchanged.
+///
+/// 其他响应原样返回。
+#[doc(hidden)]
+#[must_use]
+pub fn prettify(mut response: Response) -> Response {
+    let is_json = response
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|v| {
+            let v = v.trim();
+            v.eq_ignore_ascii_case("application/json") || v.ends_with("+json")
+        });
+    if !is_json || response.streaming.is_some() {
+        return response;
+    }
+    let mut pretty = Vec::with_capacity(response.body.len() * 2);
+    let mut deserializer = serde_json::Deserializer::from_slice(&response.body);
+    let mut serializer = serde_json::Serializer::pretty(&mut pretty);
+    if serde_transcode::transcode(&mut deserializer, &mut serializer).is_ok() {
+        response.body = pretty;
+    }
+    response
+}
+
 /// Create a text response
 ///
 /// / 创建文本响应
@@ -93,6 +247,7 @@ pub fn text(content: impl Into<String>) -> Response {
         status: StatusCode::OK,
         headers,
         body: body.into_bytes(),
+        streaming: None,
     }
 }
 
@@ -124,6 +279,7 @@ pub fn html(content: impl Into<String>) -> Response {
         status: StatusCode::OK,
         headers,
         body: body.into_bytes(),
+        streaming: None,
     }
 }
 
@@ -152,6 +308,122 @@ pub fn html(content: impl Into<String>) -> Response {
 /// redirect("https://example.com")
 /// ```
 pub fn redirect(url: &str) -> Result<Response> {
+    redirect_with_status(StatusCode::FOUND, url)
+}
+
+/// Create a 308 Permanent Redirect response
+///
+/// / 创建 308 Permanent Redirect 响应
+///
+/// The client repeats the request with the same method and body at the new
+/// URL, and may remember the move. Use [`redirect_moved`] for links that
+/// should become a plain `GET`.
+///
+/// 客户端以相同的方法和请求体向新 URL 重复请求，并可能记住此迁移。
+/// 对应变为普通 `GET` 的链接请使用 [`redirect_moved`]。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the URL is invalid.
+///
+/// 如果 URL 无效，返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// redirect_permanent("/api/v2/users")
+/// ```
+pub fn redirect_permanent(url: &str) -> Result<Response> {
+    redirect_with_status(StatusCode::PERMANENT_REDIRECT, url)
+}
+
+/// Create a 301 Moved Permanently response
+///
+/// / 创建 301 Moved Permanently 响应
+///
+/// Browsers follow it with a `GET`; use it for moved pages.
+///
+/// 浏览器会以 `GET` 跟随；适用于已迁移的页面。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the URL is invalid.
+///
+/// 如果 URL 无效，返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// redirect_moved("/blog/new-slug")
+/// ```
+pub fn redirect_moved(url: &str) -> Result<Response> {
+    redirect_with_status(StatusCode::MOVED_PERMANENTLY, url)
+}
+
+/// Create a 303 See Other response
+///
+/// / 创建 303 See Other 响应
+///
+/// The client fetches the new URL with `GET`. This is the redirect to send
+/// after a successful form `POST` (Post/Redirect/Get).
+///
+/// 客户端使用 `GET` 获取新 URL。这是表单 `POST` 成功后应发送的重定向（Post/Redirect/Get）。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the URL is invalid.
+///
+/// 如果 URL 无效，返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// redirect_see_other(&format!("/orders/{id}"))
+/// ```
+pub fn redirect_see_other(url: &str) -> Result<Response> {
+    redirect_with_status(StatusCode::SEE_OTHER, url)
+}
+
+/// Create a 307 Temporary Redirect response
+///
+/// / 创建 307 Temporary Redirect 响应
+///
+/// Like [`redirect`], but the client must keep the method and body.
+///
+/// 与 [`redirect`] 类似，但客户端必须保留方法和请求体。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` if the URL is invalid.
+///
+/// 如果 URL 无效，返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// redirect_temporary("/maintenance")
+/// ```
+pub fn redirect_temporary(url: &str) -> Result<Response> {
+    redirect_with_status(StatusCode::TEMPORARY_REDIRECT, url)
+}
+
+pub(crate) fn redirect_with_status(status: StatusCode, url: &str) -> Result<Response> {
     let value = HeaderValue::try_from(url)
         .map_err(|_| RouteError::bad_request(format!("Invalid redirect URL: {url}")))?;
 
@@ -159,9 +431,10 @@ pub fn redirect(url: &str) -> Result<Response> {
     headers.insert(header::LOCATION, value);
 
     Ok(Response {
-        status: StatusCode::FOUND,
+        status,
         headers,
         body: Vec::new(),
+        streaming: None,
     })
 }
 
@@ -190,6 +463,7 @@ pub fn no_content() -> Response {
         status: StatusCode::NO_CONTENT,
         headers: HeaderMap::new(),
         body: Vec::new(),
+        streaming: None,
     }
 }
 
@@ -216,5 +490,6 @@ pub fn bytes(data: Vec<u8>) -> Response {
         status: StatusCode::OK,
         headers: HeaderMap::new(),
         body: data,
+        streaming: None,
     }
 }
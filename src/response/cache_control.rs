@@ -0,0 +1,295 @@
+//! `Cache-Control` policies and `Vary` management
+//!
+//! / `Cache-Control` 策略与 `Vary` 管理
+//!
+//! A policy can be set in three places; the first one found wins:
+//!
+//! 策略可以在三个位置设置，先找到的生效：
+//!
+//! 1. On the response: [`Response::cache_control`]
+//!    在响应上：[`Response::cache_control`]
+//! 2. On the route: `#[route(cache = "public, max-age=60")]`
+//!    在路由上：`#[route(cache = "public, max-age=60")]`
+//! 3. On the nearest scope: [`Middleware::cache_control`](crate::middleware::Middleware::cache_control)
+//!    在最近的作用域上：[`Middleware::cache_control`](crate::middleware::Middleware::cache_control)
+//!
+//! Route and scope policies only apply to successful (non-4xx/5xx) responses,
+//! so errors are never cached by accident.
+//!
+//! 路由和作用域策略只应用于成功（非 4xx/5xx）响应，因此错误不会被意外缓存。
+
+use axum::http::{Extensions, HeaderValue, header};
+use std::fmt;
+
+use super::Response;
+
+/// Typed `Cache-Control` builder
+///
+/// / 类型化的 `Cache-Control` 构建器
+///
+/// Durations are in seconds.
+///
+/// 时长以秒为单位。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let policy = CacheControl::new().public().max_age(60).stale_while_revalidate(30);
+/// assert_eq!(policy.to_string(), "public, max-age=60, stale-while-revalidate=30");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    public: bool,
+    private: bool,
+    no_cache: bool,
+    no_store: bool,
+    must_revalidate: bool,
+    immutable: bool,
+    max_age: Option<u64>,
+    s_maxage: Option<u64>,
+    stale_while_revalidate: Option<u64>,
+    stale_if_error: Option<u64>,
+}
+
+impl CacheControl {
+    /// Create an empty policy
+    /// / 创建空策略
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `public` — shared caches may store the response (clears `private`)
+    /// / `public` — 共享缓存可以存储响应（清除 `private`）
+    #[must_use]
+    pub fn public(mut self) -> Self {
+        self.public = true;
+        self.private = false;
+        self
+    }
+
+    /// `private` — only the client may store the response (clears `public`)
+    /// / `private` — 只有客户端可以存储响应（清除 `public`）
+    #[must_use]
+    pub fn private(mut self) -> Self {
+        self.private = true;
+        self.public = false;
+        self
+    }
+
+    /// `no-cache` — caches must revalidate before every reuse
+    /// / `no-cache` — 缓存每次复用前都必须重新验证
+    #[must_use]
+    pub fn no_cache(mut self) -> Self {
+        self.no_cache = true;
+        self
+    }
+
+    /// `no-store` — the response must not be stored at all
+    /// / `no-store` — 响应完全不能被存储
+    #[must_use]
+    pub fn no_store(mut self) -> Self {
+        self.no_store = true;
+        self
+    }
+
+    /// `must-revalidate` — stale responses must be revalidated
+    /// / `must-revalidate` — 过期的响应必须重新验证
+    #[must_use]
+    pub fn must_revalidate(mut self) -> Self {
+        self.must_revalidate = true;
+        self
+    }
+
+    /// `immutable` — the response never changes while fresh
+    /// / `immutable` — 响应在有效期内永不改变
+    #[must_use]
+    pub fn immutable(mut self) -> Self {
+        self.immutable = true;
+        self
+    }
+
+    /// `max-age` — freshness lifetime in seconds
+    /// / `max-age` — 有效期（秒）
+    #[must_use]
+    pub fn max_age(mut self, seconds: u64) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// `s-maxage` — freshness lifetime for shared caches in seconds
+    /// / `s-maxage` — 共享缓存的有效期（秒）
+    #[must_use]
+    pub fn s_maxage(mut self, seconds: u64) -> Self {
+        self.s_maxage = Some(seconds);
+        self
+    }
+
+    /// `stale-while-revalidate` — serve stale while revalidating, in seconds
+    /// / `stale-while-revalidate` — 重新验证期间可提供过期响应的时长（秒）
+    #[must_use]
+    pub fn stale_while_revalidate(mut self, seconds: u64) -> Self {
+        self.stale_while_revalidate = Some(seconds);
+        self
+    }
+
+    /// `stale-if-error` — serve stale when the origin fails, in seconds
+    /// / `stale-if-error` — 源站出错时可提供过期响应的时长（秒）
+    #[must_use]
+    pub fn stale_if_error(mut self, seconds: u64) -> Self {
+        self.stale_if_error = Some(seconds);
+        self
+    }
+
+    /// The header value / 响应头的值
+    #[must_use]
+    pub fn to_header_value(&self) -> HeaderValue {
+        HeaderValue::try_from(self.to_string()).unwrap_or_else(|_| HeaderValue::from_static(""))
+    }
+}
+
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            (self.public, "public"),
+            (self.private, "private"),
+            (self.no_cache, "no-cache"),
+            (self.no_store, "no-store"),
+            (self.must_revalidate, "must-revalidate"),
+            (self.immutable, "immutable"),
+        ];
+        let durations = [
+            (self.max_age, "max-age"),
+            (self.s_maxage, "s-maxage"),
+            (self.stale_while_revalidate, "stale-while-revalidate"),
+            (self.stale_if_error, "stale-if-error"),
+        ];
+        let directives = flags
+            .into_iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, name)| name.to_string())
+            .chain(
+                durations
+                    .into_iter()
+                    .filter_map(|(value, name)| value.map(|v| format!("{name}={v}"))),
+            );
+        for (i, directive) in directives.enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(&directive)?;
+        }
+        Ok(())
+    }
+}
+
+/// Cache policy of the nearest middleware scope, stored as a request extension
+///
+/// / 最近的中间件作用域的缓存策略，以请求扩展形式存储
+///
+/// Inserted by the generated router; read by the `#[route]` wrapper.
+///
+/// 由生成的路由器插入，由 `#[route]` 包装函数读取。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeCacheControl(pub CacheControl);
+
+/// Resolve the default `Cache-Control` for a request
+///
+/// / 解析请求的默认 `Cache-Control`
+///
+/// The route's own policy wins over the scope's.
+///
+/// 路由自身的策略优先于作用域策略。
+#[must_use]
+pub fn resolve(route_policy: Option<&'static str>, extensions: &Extensions) -> Option<HeaderValue> {
+    route_policy.map(HeaderValue::from_static).or_else(|| {
+        extensions
+            .get::<ScopeCacheControl>()
+            .map(|policy| policy.0.to_header_value())
+    })
+}
+
+/// Apply a default policy to a successful response without its own `Cache-Control`
+/// / 将默认策略应用到未设置 `Cache-Control` 的成功响应
+#[must_use]
+pub fn apply_default(mut response: Response, policy: Option<&HeaderValue>) -> Response {
+    if let Some(policy) = policy
+        && !response.status.is_client_error()
+        && !response.status.is_server_error()
+        && !response.headers.contains_key(header::CACHE_CONTROL)
+    {
+        response
+            .headers
+            .insert(header::CACHE_CONTROL, policy.clone());
+    }
+    response
+}
+
+impl Response {
+    /// Set the `Cache-Control` header (chainable)
+    ///
+    /// / 设置 `Cache-Control` 头（可链式调用）
+    ///
+    /// Overrides any route or scope policy.
+    ///
+    /// 覆盖路由或作用域上的策略。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json(&user)?.cache_control(CacheControl::new().private().max_age(300))
+    /// ```
+    #[must_use]
+    pub fn cache_control(mut self, policy: CacheControl) -> Self {
+        self.headers
+            .insert(header::CACHE_CONTROL, policy.to_header_value());
+        self
+    }
+
+    /// Add a request header to `Vary` (chainable)
+    ///
+    /// / 将请求头添加到 `Vary`（可链式调用）
+    ///
+    /// Merges with any existing `Vary` value, skipping duplicates
+    /// (case-insensitively); `*` absorbs everything else.
+    ///
+    /// 与已有的 `Vary` 值合并，跳过重复项（不区分大小写）；`*` 会吸收其他所有值。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// html(page).vary("Accept-Language").vary("Cookie")
+    /// ```
+    #[must_use]
+    pub fn vary(mut self, name: &str) -> Self {
+        let mut names: Vec<String> = self
+            .headers
+            .get_all(header::VARY)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        let name = name.trim();
+        if !names
+            .iter()
+            .any(|n| n.eq_ignore_ascii_case(name) || n == "*")
+        {
+            names.push(name.to_string());
+        }
+        if names.iter().any(|n| n == "*") {
+            names = vec!["*".to_string()];
+        }
+        if let Ok(value) = HeaderValue::try_from(names.join(", ")) {
+            self.headers.insert(header::VARY, value);
+        }
+        self
+    }
+}
@@ -0,0 +1,400 @@
+//! Conditional requests: `ETag`, `Last-Modified`, 304 and 412
+//!
+//! / 条件请求：`ETag`、`Last-Modified`、304 和 412
+//!
+//! Set validators on a response with [`Response::etag`], [`Response::weak_etag`],
+//! [`Response::auto_etag`] and [`Response::last_modified`]. For `GET` and
+//! `HEAD` the `#[route]` wrapper then answers `If-None-Match` /
+//! `If-Modified-Since` with `304 Not Modified` automatically.
+//!
+//! 使用 [`Response::etag`]、[`Response::weak_etag`]、[`Response::auto_etag`] 和
+//! [`Response::last_modified`] 在响应上设置验证器。对于 `GET` 和 `HEAD`，
+//! `#[route]` 包装函数会自动以 `304 Not Modified` 响应 `If-None-Match` /
+//! `If-Modified-Since`。
+//!
+//! `If-Match` has to be checked *before* an update is applied, so it is not
+//! evaluated on the response. Opt in with `#[route(if_match = "current_tag")]`,
+//! which calls `current_tag` for the resource's current tag before the handler
+//! body runs, or call [`check_if_match`](crate::extract::check_if_match) inside
+//! the handler. Either fails with `412 Precondition Failed`.
+//!
+//! `If-Match` 必须在执行更新*之前*检查，因此不会在响应上求值。使用
+//! `#[route(if_match = "current_tag")]` 启用，它会在处理函数体运行之前调用 `current_tag`
+//! 获取资源当前的标签；或在处理函数中调用 [`check_if_match`](crate::extract::check_if_match)。
+//! 两者不匹配时都返回 `412 Precondition Failed`。
+//!
+//! # Example
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! // GET — 304 when the client's copy is current
+//! json(&article)?.etag(&article.version.to_string())
+//!
+//! // PUT — 412 when the client edited a stale copy
+//! #[route(if_match = "current_tag")]
+//! pub async fn handler(event: Event) -> Result<Response> { /* ... */ }
+//!
+//! async fn current_tag(event: &Event) -> Result<Option<ETag>> {
+//!     Ok(db.article(get_param_required(event, "id")?).await?.map(|a| ETag::strong(a.version.to_string())))
+//! }
+//! ```
+
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use std::fmt;
+use std::time::SystemTime;
+
+use super::Response;
+
+/// An entity tag
+///
+/// / 实体标签
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// A strong tag: the representation is byte-for-byte identical
+    /// / 强标签：表示在字节级别完全相同
+    pub fn strong(tag: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            weak: false,
+        }
+    }
+
+    /// A weak tag (`W/"..."`): the representation is semantically equivalent
+    /// / 弱标签（`W/"..."`）：表示在语义上等价
+    pub fn weak(tag: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            weak: true,
+        }
+    }
+
+    /// A strong tag computed from a hash of `body`
+    /// / 根据 `body` 的哈希计算出的强标签
+    #[must_use]
+    pub fn from_body(body: &[u8]) -> Self {
+        // 64-bit FNV-1a: stable across processes and releases
+        // 64 位 FNV-1a：跨进程和版本保持稳定
+        let hash = body.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        Self::strong(format!("{:x}-{hash:016x}", body.len()))
+    }
+
+    /// The opaque tag without quotes or `W/`
+    /// / 不含引号和 `W/` 的不透明标签
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Whether this is a weak tag
+    /// / 是否为弱标签
+    #[must_use]
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Parse a single `ETag` header value (`"abc"` or `W/"abc"`)
+    /// / 解析单个 `ETag` 头的值（`"abc"` 或 `W/"abc"`）
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (weak, quoted) = match value.strip_prefix("W/") {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let tag = quoted.strip_prefix('"')?.strip_suffix('"')?;
+        (!tag.contains('"')).then(|| Self {
+            tag: tag.to_string(),
+            weak,
+        })
+    }
+
+    /// Strong comparison: both strong and equal (used by `If-Match`)
+    /// / 强比较：两者均为强标签且相等（用于 `If-Match`）
+    #[must_use]
+    pub fn strong_eq(&self, other: &Self) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Weak comparison: equal tags regardless of weakness (used by `If-None-Match`)
+    /// / 弱比较：忽略强弱，只比较标签（用于 `If-None-Match`）
+    #[must_use]
+    pub fn weak_eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/\"{}\"", self.tag)
+        } else {
+            write!(f, "\"{}\"", self.tag)
+        }
+    }
+}
+
+/// An `If-Match` / `If-None-Match` header: `*` or a list of tags
+/// / `If-Match` / `If-None-Match` 头：`*` 或标签列表
+#[derive(Debug, Clone)]
+enum TagList {
+    Any,
+    Tags(Vec<ETag>),
+}
+
+impl TagList {
+    fn from_headers(headers: &HeaderMap, name: &header::HeaderName) -> Option<Self> {
+        let mut tags = Vec::new();
+        let mut present = false;
+        for value in headers.get_all(name) {
+            present = true;
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            if value.trim() == "*" {
+                return Some(Self::Any);
+            }
+            tags.extend(split_tags(value).filter_map(ETag::parse));
+        }
+        present.then_some(Self::Tags(tags))
+    }
+
+    fn matches(&self, tag: Option<&ETag>, eq: fn(&ETag, &ETag) -> bool) -> bool {
+        match (self, tag) {
+            (Self::Any, Some(_)) => true,
+            (Self::Tags(tags), Some(tag)) => tags.iter().any(|t| eq(t, tag)),
+            (_, None) => false,
+        }
+    }
+}
+
+/// Split a tag list on commas outside quotes
+/// / 按引号外的逗号拆分标签列表
+fn split_tags(value: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    value
+        .split(move |c| {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            c == ',' && !in_quotes
+        })
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn header_time(headers: &HeaderMap, name: &header::HeaderName) -> Option<SystemTime> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| httpdate::parse_http_date(v).ok())
+}
+
+fn response_etag(headers: &HeaderMap) -> Option<ETag> {
+    headers
+        .get(header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .and_then(ETag::parse)
+}
+
+/// Check `If-Match` / `If-Unmodified-Since` against the current resource
+///
+/// / 使用当前资源检查 `If-Match` / `If-Unmodified-Since`
+///
+/// Returns `true` when the request may proceed. Requests without these
+/// headers always proceed.
+///
+/// 请求可以继续时返回 `true`。不含这些头的请求总是可以继续。
+#[must_use]
+pub fn preconditions_pass(
+    headers: &HeaderMap,
+    current: Option<&ETag>,
+    last_modified: Option<SystemTime>,
+) -> bool {
+    if let Some(list) = TagList::from_headers(headers, &header::IF_MATCH) {
+        return list.matches(current, ETag::strong_eq);
+    }
+    match (
+        header_time(headers, &header::IF_UNMODIFIED_SINCE),
+        last_modified,
+    ) {
+        (Some(since), Some(modified)) => truncate(modified) <= since,
+        _ => true,
+    }
+}
+
+/// Drop sub-second precision, which HTTP dates cannot represent
+/// / 丢弃 HTTP 日期无法表示的亚秒精度
+fn truncate(time: SystemTime) -> SystemTime {
+    httpdate::parse_http_date(&httpdate::fmt_http_date(time)).unwrap_or(time)
+}
+
+/// The conditional headers of a request, captured by the `#[route]` wrapper
+///
+/// / 由 `#[route]` 包装函数捕获的请求条件头
+#[derive(Debug, Clone, Default)]
+pub struct Conditions {
+    safe: bool,
+    if_none_match: Option<TagList>,
+    if_modified_since: Option<SystemTime>,
+    range: Option<String>,
+    if_range: Option<String>,
+}
+
+impl Conditions {
+    /// Capture the headers needed to answer with 304 or 206 later
+    /// / 捕获之后用于返回 304 或 206 所需的请求头
+    #[must_use]
+    pub fn from_request(method: &Method, headers: &HeaderMap) -> Self {
+        // `Range` is only defined for GET / `Range` 仅对 GET 有定义
+        let get = |name| {
+            (method == Method::GET)
+                .then(|| headers.get(name)?.to_str().ok().map(str::to_string))
+                .flatten()
+        };
+        Self {
+            safe: method == Method::GET || method == Method::HEAD,
+            if_none_match: TagList::from_headers(headers, &header::IF_NONE_MATCH),
+            if_modified_since: header_time(headers, &header::IF_MODIFIED_SINCE),
+            range: get(header::RANGE),
+            if_range: get(header::IF_RANGE),
+        }
+    }
+
+    /// Whether the client's cached copy is still current
+    ///
+    /// / 客户端缓存的副本是否仍然有效
+    ///
+    /// `If-None-Match` takes precedence; `If-Modified-Since` is only consulted
+    /// when it is absent.
+    ///
+    /// `If-None-Match` 优先；只有在它不存在时才会检查 `If-Modified-Since`。
+    #[must_use]
+    pub fn not_modified(&self, response_headers: &HeaderMap) -> bool {
+        if let Some(list) = &self.if_none_match {
+            return list.matches(response_etag(response_headers).as_ref(), ETag::weak_eq);
+        }
+        match (
+            self.if_modified_since,
+            header_time(response_headers, &header::LAST_MODIFIED),
+        ) {
+            (Some(since), Some(modified)) => modified <= since,
+            _ => false,
+        }
+    }
+
+    /// Turn a successful `GET`/`HEAD` response into `304 Not Modified` if the
+    /// client's copy is current, or into `206 Partial Content` / `416` for a
+    /// `Range` request on a response with `Accept-Ranges: bytes`
+    ///
+    /// / 如果客户端副本仍然有效，将成功的 `GET`/`HEAD` 响应转换为 `304 Not Modified`；
+    /// 对带有 `Accept-Ranges: bytes` 的响应的 `Range` 请求，转换为
+    /// `206 Partial Content` / `416`
+    #[must_use]
+    pub fn apply(&self, mut response: Response) -> Response {
+        if !self.safe || response.status != StatusCode::OK {
+            return response;
+        }
+        if !self.not_modified(&response.headers) {
+            return match &self.range {
+                Some(range) => super::file::apply_range(response, range, self.if_range.as_deref()),
+                None => response,
+            };
+        }
+        response.status = StatusCode::NOT_MODIFIED;
+        response.body.clear();
+        response.streaming = None;
+        response.headers.remove(header::CONTENT_TYPE);
+        response.headers.remove(header::CONTENT_LENGTH);
+        response
+    }
+}
+
+impl Response {
+    /// Set a strong `ETag` (chainable)
+    ///
+    /// / 设置强 `ETag`（可链式调用）
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json(&article)?.etag(&article.version.to_string())
+    /// ```
+    #[must_use]
+    pub fn etag(self, tag: &str) -> Self {
+        self.with_etag(&ETag::strong(tag))
+    }
+
+    /// Set a weak `ETag` (chainable)
+    /// / 设置弱 `ETag`（可链式调用）
+    #[must_use]
+    pub fn weak_etag(self, tag: &str) -> Self {
+        self.with_etag(&ETag::weak(tag))
+    }
+
+    /// Set a strong `ETag` computed from the body (chainable)
+    ///
+    /// / 设置根据响应体计算的强 `ETag`（可链式调用）
+    ///
+    /// Call it after the body is final. Streamed responses ([`file`](super::file),
+    /// [`json_stream`](super::json_stream), ...) have no body to hash, so they
+    /// are returned unchanged and keep any validator they already carry.
+    ///
+    /// 请在响应体确定后调用。流式响应（[`file`](super::file)、
+    /// [`json_stream`](super::json_stream) 等）没有可供哈希的响应体，因此会原样返回，
+    /// 并保留已有的验证器。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json(&articles)?.auto_etag()
+    /// ```
+    #[must_use]
+    pub fn auto_etag(self) -> Self {
+        if self.streaming.is_some() {
+            return self;
+        }
+        let tag = ETag::from_body(&self.body);
+        self.with_etag(&tag)
+    }
+
+    /// Set the `ETag` header from an [`ETag`] (chainable)
+    /// / 使用 [`ETag`] 设置 `ETag` 头（可链式调用）
+    #[must_use]
+    pub fn with_etag(mut self, tag: &ETag) -> Self {
+        if let Ok(value) = HeaderValue::try_from(tag.to_string()) {
+            self.headers.insert(header::ETAG, value);
+        }
+        self
+    }
+
+    /// Set the `Last-Modified` header (chainable)
+    ///
+    /// / 设置 `Last-Modified` 头（可链式调用）
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json(&article)?.last_modified(article.updated_at)
+    /// ```
+    #[must_use]
+    pub fn last_modified(mut self, time: SystemTime) -> Self {
+        if let Ok(value) = HeaderValue::try_from(httpdate::fmt_http_date(time)) {
+            self.headers.insert(header::LAST_MODIFIED, value);
+        }
+        self
+    }
+}
@@ -0,0 +1,153 @@
+//! Headers added to every response
+//!
+//! / 添加到每个响应的响应头
+//!
+//! By default every response Astrea builds carries `Server: Astrea`. Install
+//! a [`ResponseDefaults`] with [`set_response_defaults`] to rename or drop it
+//! and to add headers such as `X-Content-Type-Options`. They are applied the
+//! same way to handler responses, error responses, [`stream`](super::stream)
+//! responses and files served from `public/`. A header the response already
+//! has is never replaced.
+//!
+//! 默认情况下，Astrea 构建的每个响应都带有 `Server: Astrea`。通过
+//! [`set_response_defaults`] 安装 [`ResponseDefaults`] 可以重命名或移除它，
+//! 并添加 `X-Content-Type-Options` 等响应头。它们以相同方式应用于处理函数响应、
+//! 错误响应、[`stream`](super::stream) 响应以及 `public/` 中的文件。
+//! 响应中已有的响应头永远不会被替换。
+//!
+//! # Example
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! use astrea::response::defaults::{ResponseDefaults, set_response_defaults};
+//!
+//! set_response_defaults(
+//!     ResponseDefaults::new()
+//!         .no_server()
+//!         .security_headers()
+//!         .header("Permissions-Policy", "camera=()"),
+//! );
+//! ```
+
+use std::sync::{OnceLock, RwLock};
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, header};
+
+use super::headers;
+
+/// Headers added to responses that don't set them
+///
+/// / 添加到未设置这些响应头的响应中的响应头
+#[derive(Debug, Clone)]
+pub struct ResponseDefaults {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl Default for ResponseDefaults {
+    fn default() -> Self {
+        Self {
+            headers: vec![(header::SERVER, HeaderValue::from_static("Astrea"))],
+        }
+    }
+}
+
+impl ResponseDefaults {
+    /// The built-in defaults: only `Server: Astrea`
+    /// / 内置默认值：仅 `Server: Astrea`
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send another `Server` value
+    /// / 发送其他 `Server` 值
+    #[must_use]
+    pub fn server(self, value: &str) -> Self {
+        self.header(header::SERVER.as_str(), value)
+    }
+
+    /// Don't add a `Server` header
+    /// / 不添加 `Server` 响应头
+    #[must_use]
+    pub fn no_server(self) -> Self {
+        self.without(header::SERVER.as_str())
+    }
+
+    /// Add a default header, replacing an earlier default of the same name
+    ///
+    /// / 添加默认响应头，替换之前同名的默认值
+    ///
+    /// An invalid name or value is left out with a `tracing` warning.
+    ///
+    /// 无效的名称或值会被忽略并输出 `tracing` 警告。
+    #[must_use]
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        match headers::parse(key, value) {
+            Ok((name, value)) => {
+                self.headers.retain(|(n, _)| *n != name);
+                self.headers.push((name, value));
+            }
+            Err(reason) => headers::discarded(key, &reason),
+        }
+        self
+    }
+
+    /// Remove a default header
+    /// / 移除默认响应头
+    #[must_use]
+    pub fn without(mut self, key: &str) -> Self {
+        self.headers
+            .retain(|(name, _)| !name.as_str().eq_ignore_ascii_case(key));
+        self
+    }
+
+    /// Add common security headers
+    ///
+    /// / 添加常用的安全响应头
+    ///
+    /// - `X-Content-Type-Options: nosniff`
+    /// - `Referrer-Policy: strict-origin-when-cross-origin`
+    /// - `X-Frame-Options: SAMEORIGIN`
+    ///
+    /// Chain [`header`](Self::header) afterwards to change one of them.
+    ///
+    /// 之后可链式调用 [`header`](Self::header) 修改其中某一项。
+    #[must_use]
+    pub fn security_headers(self) -> Self {
+        self.header(header::X_CONTENT_TYPE_OPTIONS.as_str(), "nosniff")
+            .header(
+                header::REFERRER_POLICY.as_str(),
+                "strict-origin-when-cross-origin",
+            )
+            .header(header::X_FRAME_OPTIONS.as_str(), "SAMEORIGIN")
+    }
+}
+
+static DEFAULTS: OnceLock<RwLock<ResponseDefaults>> = OnceLock::new();
+
+fn current() -> &'static RwLock<ResponseDefaults> {
+    DEFAULTS.get_or_init(|| RwLock::new(ResponseDefaults::default()))
+}
+
+/// Replace the headers added to every response
+///
+/// / 替换添加到每个响应的响应头
+///
+/// Call it before serving requests; later calls apply to the responses built
+/// after them.
+///
+/// 请在处理请求之前调用；之后的调用会应用于其后构建的响应。
+pub fn set_response_defaults(defaults: ResponseDefaults) {
+    *current().write().unwrap() = defaults;
+}
+
+/// Add the default headers the response doesn't already have
+/// / 添加响应中尚不存在的默认响应头
+pub(crate) fn apply(headers: &mut HeaderMap) {
+    for (name, value) in &current().read().unwrap().headers {
+        if !headers.contains_key(name) {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+}
@@ -0,0 +1,578 @@
+//! File downloads and byte ranges
+//!
+//! / 文件下载与字节范围
+//!
+//! [`file`] streams a file from disk with its MIME type, `Last-Modified`, a
+//! strong `ETag` and `Accept-Ranges: bytes`. [`attachment`] streams any
+//! [`AsyncRead`] as a download.
+//!
+//! [`file`] 从磁盘流式传输文件，并设置 MIME 类型、`Last-Modified`、强 `ETag` 和
+//! `Accept-Ranges: bytes`。[`attachment`] 将任意 [`AsyncRead`] 作为下载流式传输。
+//!
+//! For `GET` requests the `#[route]` wrapper answers `Range` on every response
+//! that carries `Accept-Ranges: bytes`: one range gives `206 Partial Content`,
+//! several give a `multipart/byteranges` body, and ranges past the end give
+//! `416 Range Not Satisfiable`. `If-Range` is honored with either the `ETag`
+//! or the `Last-Modified` date, so a client resuming a download of a file that
+//! changed gets the whole new file.
+//!
+//! 对于 `GET` 请求，`#[route]` 包装函数会为所有带有 `Accept-Ranges: bytes` 的响应处理
+//! `Range`：单个范围返回 `206 Partial Content`，多个范围返回 `multipart/byteranges`
+//! 响应体，超出末尾的范围返回 `416 Range Not Satisfiable`。`If-Range` 也会被遵守，
+//! 因此客户端续传一个已变化的文件时会得到完整的新文件。
+//!
+//! # Example
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! #[route]
+//! pub async fn handler(event: Event) -> Result<Response> {
+//!     let name = get_param_required(&event, "name")?;
+//!     Ok(file(format!("uploads/{name}")).await?.download(&name))
+//! }
+//! ```
+
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use bytes::Bytes;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Mutex, PoisonError};
+use std::time::UNIX_EPOCH;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt};
+
+use super::Response;
+use super::conditional::ETag;
+use crate::error::{Result, RouteError};
+
+/// Size of the chunks read from disk / 从磁盘读取的块大小
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// More ranges than this in one request are ignored and the whole body is sent
+/// / 单个请求中超过此数量的范围会被忽略，并发送完整响应体
+const MAX_RANGES: usize = 16;
+
+// ============================================================================
+// 流式响应体
+// ============================================================================
+
+/// A piece of a streamed file body / 流式文件响应体的片段
+#[derive(Clone)]
+enum Segment {
+    Bytes(Bytes),
+    /// `(start, length)` of a byte range of the file
+    /// / 文件字节范围的 `(起始, 长度)`
+    File(u64, u64),
+}
+
+enum Source {
+    File {
+        file: tokio::fs::File,
+        len: u64,
+        segments: Vec<Segment>,
+    },
+    /// Bytes compiled into the binary, sent without copying
+    /// / 编译进二进制文件的字节，发送时不复制
+    Static {
+        bytes: &'static [u8],
+        segments: Vec<Segment>,
+    },
+    Reader(Pin<Box<dyn AsyncRead + Send>>),
+    /// A body built elsewhere, e.g. by [`json_stream`](super::json_stream)
+    /// / 在其他地方构建的响应体，如 [`json_stream`](super::json_stream)
+    Body(Body),
+    /// Left in a clone: the stream stays with the original response
+    /// / 留在克隆中：流仍归原响应所有
+    Cloned,
+}
+
+/// A body read at send time instead of held in [`Response::body`]
+///
+/// / 在发送时读取、而非保存在 [`Response::body`] 中的响应体
+///
+/// The stream can only be read once, so it is never shared: a clone of the
+/// response gets a body that aborts as soon as it is sent, while the original
+/// keeps the stream. Static bytes are the exception and are simply cloned.
+///
+/// 流只能读取一次，因此从不共享：响应的克隆得到一个一发送就中止的响应体，原响应保留该流。
+/// 静态字节是例外，会被直接克隆。
+pub(crate) struct Streaming(Mutex<Option<Source>>);
+
+impl Clone for Streaming {
+    fn clone(&self) -> Self {
+        let source = self.with(|source| match source {
+            Some(Source::Static { bytes, segments }) => Source::Static {
+                bytes,
+                segments: segments.clone(),
+            },
+            _ => Source::Cloned,
+        });
+        Self::new(source)
+    }
+}
+
+impl fmt::Debug for Streaming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Streaming")
+    }
+}
+
+impl Streaming {
+    fn new(source: Source) -> Self {
+        Self(Mutex::new(Some(source)))
+    }
+
+    /// Stream an already built body of unknown length
+    /// / 流式发送一个已构建、长度未知的响应体
+    pub(crate) fn from_body(body: Body) -> Self {
+        Self::new(Source::Body(body))
+    }
+
+    /// Send bytes compiled into the binary without copying them
+    /// / 发送编译进二进制文件的字节而不复制
+    pub(crate) fn from_static(bytes: &'static [u8]) -> Self {
+        Self::new(Source::Static {
+            bytes,
+            segments: vec![Segment::File(0, bytes.len() as u64)],
+        })
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut Option<Source>) -> T) -> T {
+        f(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Full length of a file or static body / 文件或静态响应体的完整长度
+    fn file_len(&self) -> Option<u64> {
+        self.with(|source| match source {
+            Some(Source::File { len, .. }) => Some(*len),
+            Some(Source::Static { bytes, .. }) => Some(bytes.len() as u64),
+            _ => None,
+        })
+    }
+
+    fn set_segments(&self, new: Vec<Segment>) {
+        self.with(|source| {
+            if let Some(Source::File { segments, .. } | Source::Static { segments, .. }) = source {
+                *segments = new;
+            }
+        });
+    }
+
+    /// Take the body and its length, if known
+    /// / 取出响应体及其长度（如果已知）
+    pub(crate) fn into_body(self) -> Option<(Body, Option<u64>)> {
+        match self.with(Option::take)? {
+            Source::File { file, segments, .. } => {
+                let length = segments_len(&segments);
+                Some((file_body(file, segments), Some(length)))
+            }
+            Source::Static { bytes, segments } => {
+                let length = segments_len(&segments);
+                let chunks = segments.into_iter().map(move |segment| match segment {
+                    Segment::Bytes(chunk) => Ok::<_, std::io::Error>(chunk),
+                    // Ranges are checked against the length before they become segments
+                    // 范围在成为片段之前已根据长度检查过
+                    Segment::File(start, len) => Ok(Bytes::from_static(
+                        &bytes[start as usize..(start + len) as usize],
+                    )),
+                });
+                Some((
+                    Body::from_stream(futures_util::stream::iter(chunks)),
+                    Some(length),
+                ))
+            }
+            Source::Reader(reader) => Some((reader_body(reader), None)),
+            Source::Body(body) => Some((body, None)),
+            Source::Cloned => Some((
+                Body::from_stream(futures_util::stream::once(async {
+                    Err::<Bytes, _>(std::io::Error::other(
+                        "a streamed response body cannot be sent from a clone",
+                    ))
+                })),
+                None,
+            )),
+        }
+    }
+}
+
+fn segments_len(segments: &[Segment]) -> u64 {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Bytes(bytes) => bytes.len() as u64,
+            Segment::File(_, len) => *len,
+        })
+        .sum()
+}
+
+fn file_body(file: tokio::fs::File, segments: Vec<Segment>) -> Body {
+    let state = (file, VecDeque::from(segments), None::<u64>);
+    Body::from_stream(futures_util::stream::unfold(
+        state,
+        |(mut file, mut segments, mut position)| async move {
+            loop {
+                match segments.front_mut()? {
+                    Segment::Bytes(bytes) => {
+                        let bytes = std::mem::take(bytes);
+                        segments.pop_front();
+                        return Some((Ok(bytes), (file, segments, position)));
+                    }
+                    Segment::File(_, 0) => {
+                        segments.pop_front();
+                    }
+                    Segment::File(start, len) => {
+                        if position != Some(*start)
+                            && let Err(error) = file.seek(SeekFrom::Start(*start)).await
+                        {
+                            segments.clear();
+                            return Some((Err(error), (file, segments, position)));
+                        }
+                        let want = usize::try_from(*len).unwrap_or(usize::MAX).min(CHUNK_SIZE);
+                        let mut buffer = vec![0; want];
+                        let result = match file.read(&mut buffer).await {
+                            Ok(0) => Err(std::io::ErrorKind::UnexpectedEof.into()),
+                            other => other,
+                        };
+                        let Ok(read) = result else {
+                            segments.clear();
+                            return Some((
+                                result.map(|_| Bytes::new()),
+                                (file, segments, position),
+                            ));
+                        };
+                        buffer.truncate(read);
+                        *start += read as u64;
+                        *len -= read as u64;
+                        position = Some(*start);
+                        return Some((Ok(Bytes::from(buffer)), (file, segments, position)));
+                    }
+                }
+            }
+        },
+    ))
+}
+
+fn reader_body(reader: Pin<Box<dyn AsyncRead + Send>>) -> Body {
+    Body::from_stream(futures_util::stream::unfold(
+        Some(reader),
+        |reader| async move {
+            let mut reader = reader?;
+            let mut buffer = vec![0; CHUNK_SIZE];
+            match reader.read(&mut buffer).await {
+                Ok(0) => None,
+                Ok(read) => {
+                    buffer.truncate(read);
+                    Some((Ok(Bytes::from(buffer)), Some(reader)))
+                }
+                Err(error) => Some((Err(error), None)),
+            }
+        },
+    ))
+}
+
+// ============================================================================
+// 响应构建器
+// ============================================================================
+
+/// Stream a file from disk
+///
+/// / 从磁盘流式传输文件
+///
+/// Sets the MIME type guessed from the extension, `Last-Modified`, a strong
+/// `ETag` from the size and the full-precision modification time, and
+/// `Accept-Ranges: bytes`. The tag is strong so that clients can resume with
+/// `If-Range`, which only accepts strong validators. A missing file is a
+/// `404 Not Found`.
+///
+/// 设置根据扩展名推断的 MIME 类型、`Last-Modified`、由大小和完整精度的修改时间生成的强 `ETag`
+/// 以及 `Accept-Ranges: bytes`。标签是强标签，因此客户端可以使用只接受强验证器的 `If-Range`
+/// 续传。文件不存在时返回 `404 Not Found`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// Ok(file("reports/2024.pdf").await?)
+/// ```
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `NotFound` if the file does not exist or is a directory, and
+/// `Internal` for other I/O errors.
+///
+/// 文件不存在或是目录时返回 `NotFound`，其他 I/O 错误返回 `Internal`。
+pub async fn file(path: impl AsRef<Path>) -> Result<Response> {
+    let path = path.as_ref();
+    let not_found = || RouteError::not_found(format!("File not found: {}", path.display()));
+    let file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Err(not_found()),
+        Err(error) => return Err(anyhow::Error::from(error).into()),
+    };
+    let metadata = file.metadata().await.map_err(anyhow::Error::from)?;
+    if !metadata.is_file() {
+        return Err(not_found());
+    }
+
+    let len = metadata.len();
+    let mime = mime_guess::from_path(path).first_raw();
+    let mut response = Response::new().content_type(mime.unwrap_or("application/octet-stream"));
+    response
+        .headers
+        .insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Ok(modified) = metadata.modified() {
+        // Nanoseconds, so a rewrite within the same second changes the tag
+        // 使用纳秒，同一秒内的重写也会改变标签
+        let nanos = modified
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        response = response
+            .last_modified(modified)
+            .with_etag(&ETag::strong(format!("{len:x}-{nanos:x}")));
+    }
+    response.streaming = Some(Streaming::new(Source::File {
+        file,
+        len,
+        segments: vec![Segment::File(0, len)],
+    }));
+    Ok(response)
+}
+
+/// Stream a reader as a download named `filename`
+///
+/// / 将读取器作为名为 `filename` 的下载流式传输
+///
+/// The MIME type is guessed from `filename`. The length is unknown, so the
+/// body is sent chunked and `Range` is not supported.
+///
+/// MIME 类型根据 `filename` 推断。由于长度未知，响应体以分块方式发送，且不支持 `Range`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let export = tokio::fs::File::open("export.csv").await.map_err(anyhow::Error::from)?;
+/// Ok(attachment("users.csv", export))
+/// ```
+#[must_use]
+pub fn attachment(filename: &str, reader: impl AsyncRead + Send + 'static) -> Response {
+    let mime = mime_guess::from_path(filename).first_raw();
+    let mut response = Response::new()
+        .content_type(mime.unwrap_or("application/octet-stream"))
+        .download(filename);
+    response.streaming = Some(Streaming::new(Source::Reader(Box::pin(reader))));
+    response
+}
+
+impl Response {
+    /// Mark the response as a download named `filename` (chainable)
+    ///
+    /// / 将响应标记为名为 `filename` 的下载（可链式调用）
+    ///
+    /// Sets `Content-Disposition: attachment`. Non-ASCII names are sent with
+    /// an RFC 5987 `filename*` parameter plus an ASCII fallback.
+    ///
+    /// 设置 `Content-Disposition: attachment`。非 ASCII 文件名会以 RFC 5987 的
+    /// `filename*` 参数发送，并附带 ASCII 回退名称。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// bytes(pdf).content_type("application/pdf").download("报告.pdf")
+    /// // Content-Disposition: attachment; filename="__.pdf"; filename*=UTF-8''%E6%8A%A5%E5%91%8A.pdf
+    /// ```
+    #[must_use]
+    pub fn download(mut self, filename: &str) -> Self {
+        self.headers.insert(
+            header::CONTENT_DISPOSITION,
+            content_disposition("attachment", filename),
+        );
+        self
+    }
+}
+
+/// Build a `Content-Disposition` value with an RFC 5987 encoded filename
+/// / 构建带有 RFC 5987 编码文件名的 `Content-Disposition` 值
+fn content_disposition(kind: &str, filename: &str) -> HeaderValue {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            if (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let mut value = format!("{kind}; filename=\"{fallback}\"");
+    if fallback != filename {
+        value.push_str("; filename*=UTF-8''");
+        for byte in filename.bytes() {
+            if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+                value.push(char::from(byte));
+            } else {
+                value.push_str(&format!("%{byte:02X}"));
+            }
+        }
+    }
+    HeaderValue::try_from(value).unwrap_or_else(|_| HeaderValue::from_static("attachment"))
+}
+
+// ============================================================================
+// Range 请求
+// ============================================================================
+
+/// Parse a `Range` header against a body of `len` bytes
+///
+/// / 根据长度为 `len` 字节的响应体解析 `Range` 头
+///
+/// Returns inclusive `(start, end)` pairs; `None` when the header is malformed
+/// or asks for too many ranges (it is then ignored), and an empty list when no
+/// range is satisfiable.
+///
+/// 返回闭区间 `(start, end)` 列表；头格式错误或请求的范围过多时返回 `None`（此时忽略该头），
+/// 没有可满足的范围时返回空列表。
+fn parse_ranges(value: &str, len: u64) -> Option<Vec<(u64, u64)>> {
+    let specs = value.trim().strip_prefix("bytes=")?;
+    let mut ranges = Vec::new();
+    for spec in specs.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (start, end) = spec.split_once('-')?;
+        let (start, end) = (start.trim(), end.trim());
+        let range = if start.is_empty() {
+            let suffix: u64 = end.parse().ok()?;
+            (suffix > 0 && len > 0).then(|| (len.saturating_sub(suffix), len - 1))
+        } else {
+            let start: u64 = start.parse().ok()?;
+            let end = if end.is_empty() {
+                u64::MAX
+            } else {
+                end.parse().ok()?
+            };
+            if end < start {
+                return None;
+            }
+            (start < len).then(|| (start, end.min(len - 1)))
+        };
+        ranges.extend(range);
+    }
+    (ranges.len() <= MAX_RANGES).then_some(ranges)
+}
+
+/// Whether an `If-Range` validator still matches the response
+/// / `If-Range` 验证器是否仍与响应匹配
+fn if_range_matches(value: &str, headers: &HeaderMap) -> bool {
+    let value = value.trim();
+    if value.starts_with('"') || value.starts_with("W/") {
+        let current = headers
+            .get(header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .and_then(ETag::parse);
+        return match (ETag::parse(value), current) {
+            (Some(wanted), Some(current)) => wanted.strong_eq(&current),
+            _ => false,
+        };
+    }
+    let modified = headers
+        .get(header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| httpdate::parse_http_date(v).ok());
+    match (httpdate::parse_http_date(value).ok(), modified) {
+        (Some(wanted), Some(modified)) => wanted == modified,
+        _ => false,
+    }
+}
+
+/// Answer a `Range` request on a response that accepts byte ranges
+///
+/// / 对接受字节范围的响应处理 `Range` 请求
+pub(crate) fn apply_range(mut response: Response, range: &str, if_range: Option<&str>) -> Response {
+    let accepts = response
+        .headers
+        .get(header::ACCEPT_RANGES)
+        .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"bytes"));
+    if !accepts || if_range.is_some_and(|v| !if_range_matches(v, &response.headers)) {
+        return response;
+    }
+    let len = match &response.streaming {
+        Some(streaming) => match streaming.file_len() {
+            Some(len) => len,
+            None => return response,
+        },
+        None => response.body.len() as u64,
+    };
+    let Some(ranges) = parse_ranges(range, len) else {
+        return response;
+    };
+
+    if ranges.is_empty() {
+        response.status = StatusCode::RANGE_NOT_SATISFIABLE;
+        response.body.clear();
+        response.streaming = None;
+        response.headers.remove(header::CONTENT_LENGTH);
+        if let Ok(value) = HeaderValue::try_from(format!("bytes */{len}")) {
+            response.headers.insert(header::CONTENT_RANGE, value);
+        }
+        return response;
+    }
+
+    response.status = StatusCode::PARTIAL_CONTENT;
+    response.headers.remove(header::CONTENT_LENGTH);
+    let segments = if let [(start, end)] = ranges[..] {
+        if let Ok(value) = HeaderValue::try_from(format!("bytes {start}-{end}/{len}")) {
+            response.headers.insert(header::CONTENT_RANGE, value);
+        }
+        vec![Segment::File(start, end - start + 1)]
+    } else {
+        let boundary = crate::event::request_id::generate().replace('-', "");
+        let part_type = response.headers.remove(header::CONTENT_TYPE);
+        if let Ok(value) =
+            HeaderValue::try_from(format!("multipart/byteranges; boundary={boundary}"))
+        {
+            response.headers.insert(header::CONTENT_TYPE, value);
+        }
+        let mut segments = Vec::new();
+        for (start, end) in &ranges {
+            let mut head = format!("--{boundary}\r\n");
+            if let Some(part_type) = part_type.as_ref().and_then(|v| v.to_str().ok()) {
+                head.push_str(&format!("Content-Type: {part_type}\r\n"));
+            }
+            head.push_str(&format!("Content-Range: bytes {start}-{end}/{len}\r\n\r\n"));
+            segments.push(Segment::Bytes(Bytes::from(head)));
+            segments.push(Segment::File(*start, end - start + 1));
+            segments.push(Segment::Bytes(Bytes::from_static(b"\r\n")));
+        }
+        segments.push(Segment::Bytes(Bytes::from(format!("--{boundary}--\r\n"))));
+        segments
+    };
+
+    match &response.streaming {
+        Some(streaming) => streaming.set_segments(segments),
+        None => {
+            let mut body = Vec::new();
+            for segment in segments {
+                match segment {
+                    Segment::Bytes(bytes) => body.extend_from_slice(&bytes),
+                    Segment::File(start, len) => {
+                        // In-memory bodies are small enough for usize offsets
+                        // 内存中的响应体足够小，可以使用 usize 偏移
+                        let start = usize::try_from(start).unwrap_or(usize::MAX);
+                        let end = start.saturating_add(usize::try_from(len).unwrap_or(0));
+                        body.extend_from_slice(&response.body[start..end]);
+                    }
+                }
+            }
+            response.body = body;
+        }
+    }
+    response
+}
@@ -0,0 +1,184 @@
+//! Checked and typed response headers
+//!
+//! / 带校验的类型化响应头
+//!
+//! The chainable setters ([`Response::header`], [`Response::append_header`]
+//! and the typed ones below) never fail: a header whose name or value is
+//! invalid is left out and a `tracing` warning names it. Use
+//! [`Response::try_header`] when the value comes from outside and the error
+//! should reach the handler.
+//!
+//! 可链式调用的设置方法（[`Response::header`]、[`Response::append_header`] 以及下面的类型化方法）
+//! 永远不会失败：名称或值无效的响应头会被忽略，并输出一条指明它的 `tracing` 警告。
+//! 当值来自外部且错误需要交给处理函数时，请使用 [`Response::try_header`]。
+
+use std::time::{Duration, SystemTime};
+
+use axum::http::{HeaderName, HeaderValue, header};
+
+use super::Response;
+use crate::error::{Result, RouteError};
+
+/// Parse a header name and value, or explain why they are invalid
+/// / 解析响应头名称和值，或说明它们无效的原因
+pub(crate) fn parse(
+    key: &str,
+    value: &str,
+) -> std::result::Result<(HeaderName, HeaderValue), String> {
+    let name = HeaderName::try_from(key).map_err(|_| "invalid header name".to_string())?;
+    let value = HeaderValue::try_from(value)
+        .map_err(|_| "value contains a character not allowed in headers".to_string())?;
+    Ok((name, value))
+}
+
+/// Report a header left out of a response / 报告被响应忽略的响应头
+pub(crate) fn discarded(key: &str, reason: &str) {
+    tracing::warn!(header = key, "Discarding response header: {reason}");
+}
+
+impl Response {
+    /// Set a response header, failing on an invalid name or value
+    ///
+    /// / 设置响应头，名称或值无效时返回错误
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `RouteError::Internal` naming the header if the name or value
+    /// is not allowed in HTTP headers (e.g. contains a newline).
+    ///
+    /// 如果名称或值不允许出现在 HTTP 头中（如包含换行符），返回指明该响应头的 `RouteError::Internal`。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json(data)?.try_header("X-Trace", &trace_id)?
+    /// ```
+    pub fn try_header(mut self, key: &str, value: &str) -> Result<Self> {
+        let (name, value) = parse(key, value).map_err(|reason| {
+            RouteError::Internal(anyhow::anyhow!("Invalid response header `{key}`: {reason}"))
+        })?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Add a response header without replacing existing values (chainable)
+    ///
+    /// / 添加响应头而不替换已有的值（可链式调用）
+    ///
+    /// For headers that may appear several times, like `Link` or `Warning`.
+    ///
+    /// 适用于可以出现多次的响应头，如 `Link` 或 `Warning`。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// text("ok")
+    ///     .append_header("X-Feature", "a")
+    ///     .append_header("X-Feature", "b")
+    /// ```
+    #[must_use]
+    pub fn append_header(mut self, key: &str, value: &str) -> Self {
+        match parse(key, value) {
+            Ok((name, value)) => {
+                self.headers.append(name, value);
+            }
+            Err(reason) => discarded(key, &reason),
+        }
+        self
+    }
+
+    /// Set the `Location` header (chainable)
+    ///
+    /// / 设置 `Location` 响应头（可链式调用）
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json_with_status(StatusCode::CREATED, &user)?.location(&format!("/users/{}", user.id))
+    /// ```
+    #[must_use]
+    pub fn location(self, url: &str) -> Self {
+        self.header(header::LOCATION.as_str(), url)
+    }
+
+    /// Add a `Link` header (`<uri>; rel="rel"`) (chainable)
+    ///
+    /// / 添加 `Link` 响应头（`<uri>; rel="rel"`）（可链式调用）
+    ///
+    /// Each call adds another `Link` header.
+    ///
+    /// 每次调用都会添加一个新的 `Link` 头。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json(&page)?
+    ///     .link("/users?page=3", "next")
+    ///     .link("/users?page=1", "prev")
+    /// ```
+    #[must_use]
+    pub fn link(self, uri: &str, rel: &str) -> Self {
+        self.append_header(header::LINK.as_str(), &format!("<{uri}>; rel=\"{rel}\""))
+    }
+
+    /// Set the `Content-Language` header (chainable)
+    ///
+    /// / 设置 `Content-Language` 响应头（可链式调用）
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// html(page).content_language("zh-CN")
+    /// ```
+    #[must_use]
+    pub fn content_language(self, language: &str) -> Self {
+        self.header(header::CONTENT_LANGUAGE.as_str(), language)
+    }
+
+    /// Set `Retry-After` to a delay, in whole seconds rounded up (chainable)
+    ///
+    /// / 将 `Retry-After` 设置为延迟时间，按整秒向上取整（可链式调用）
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// text("busy").status(StatusCode::SERVICE_UNAVAILABLE).retry_after(Duration::from_secs(30))
+    /// ```
+    #[must_use]
+    pub fn retry_after(mut self, delay: Duration) -> Self {
+        let seconds = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+        self.headers
+            .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        self
+    }
+
+    /// Set `Retry-After` to a point in time (chainable)
+    ///
+    /// / 将 `Retry-After` 设置为某个时间点（可链式调用）
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// text("maintenance").status(StatusCode::SERVICE_UNAVAILABLE).retry_after_date(window_end)
+    /// ```
+    #[must_use]
+    pub fn retry_after_date(self, time: SystemTime) -> Self {
+        self.header(header::RETRY_AFTER.as_str(), &httpdate::fmt_http_date(time))
+    }
+}
@@ -0,0 +1,213 @@
+//! Streaming JSON arrays and NDJSON
+//!
+//! / 流式 JSON 数组与 NDJSON
+//!
+//! [`json_stream`] and [`ndjson`] serialize the items of an async [`Stream`]
+//! one at a time as the client reads the body, so a large list is never held
+//! in memory. The next item is only pulled from the stream once the previous
+//! chunk has been handed to the connection: a slow client slows the producer
+//! down.
+//!
+//! [`json_stream`] 和 [`ndjson`] 在客户端读取响应体时逐个序列化异步 [`Stream`] 的元素，
+//! 因此大型列表永远不会整体保存在内存中。只有在上一个块交给连接之后才会从流中拉取下一个元素：
+//! 慢速客户端会让生产者随之变慢。
+//!
+//! # Errors
+//!
+//! # 错误
+//!
+//! The status line is sent before the first item, so a failure part-way
+//! cannot become an error response. Instead the body is aborted: the
+//! connection is closed without the end of the chunked body (or the HTTP/2
+//! stream is reset), and the client sees an incomplete response rather than a
+//! valid but shorter list. A JSON array is also left without its closing `]`.
+//!
+//! 状态行在第一个元素之前发送，因此中途失败无法变为错误响应。响应体会被中止：
+//! 连接在分块响应体结束之前关闭（或 HTTP/2 流被重置），客户端看到的是不完整的响应，
+//! 而不是一个有效但更短的列表。JSON 数组也会缺少结尾的 `]`。
+
+use std::pin::Pin;
+
+use axum::{BoxError, body::Body};
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+
+use super::{Response, bytes, file::Streaming};
+
+/// Media type for newline-delimited JSON / 换行分隔 JSON 的媒体类型
+const NDJSON: &str = "application/x-ndjson";
+
+/// Stream items as one JSON array (`[a,b,c]`)
+///
+/// / 将元素作为一个 JSON 数组（`[a,b,c]`）流式发送
+///
+/// An item that fails to serialize aborts the body.
+///
+/// 元素序列化失败会中止响应体。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[route]
+/// pub async fn handler(event: Event) -> Result<Response> {
+///     let users = futures_util::stream::iter(0..100_000).map(|id| json!({ "id": id }));
+///     Ok(json_stream(users))
+/// }
+/// ```
+#[must_use]
+pub fn json_stream<S>(items: S) -> Response
+where
+    S: Stream + Send + 'static,
+    S::Item: Serialize,
+{
+    try_json_stream(items.map(Ok::<_, BoxError>))
+}
+
+/// Stream items as newline-delimited JSON, one value per line
+///
+/// / 将元素作为换行分隔的 JSON 流式发送，每行一个值
+///
+/// Sets `Content-Type: application/x-ndjson`. An item that fails to serialize
+/// aborts the body.
+///
+/// 设置 `Content-Type: application/x-ndjson`。元素序列化失败会中止响应体。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// Ok(ndjson(events_since(cursor)))
+/// ```
+#[must_use]
+pub fn ndjson<S>(items: S) -> Response
+where
+    S: Stream + Send + 'static,
+    S::Item: Serialize,
+{
+    try_ndjson(items.map(Ok::<_, BoxError>))
+}
+
+/// Stream the items of a fallible stream as one JSON array
+///
+/// / 将可失败流的元素作为一个 JSON 数组流式发送
+///
+/// The first `Err` aborts the body, see the [module docs](self#errors).
+///
+/// 第一个 `Err` 会中止响应体，参见[模块文档](self#errors)。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// // sqlx: Stream<Item = Result<User, sqlx::Error>>
+/// Ok(try_json_stream(sqlx::query_as::<_, User>("SELECT * FROM users").fetch(&pool)))
+/// ```
+#[must_use]
+pub fn try_json_stream<S, T, E>(items: S) -> Response
+where
+    S: Stream<Item = std::result::Result<T, E>> + Send + 'static,
+    T: Serialize,
+    E: Into<BoxError>,
+{
+    streamed(items, Framing::Array, "application/json")
+}
+
+/// Stream the items of a fallible stream as newline-delimited JSON
+///
+/// / 将可失败流的元素作为换行分隔的 JSON 流式发送
+///
+/// The first `Err` aborts the body, see the [module docs](self#errors).
+///
+/// 第一个 `Err` 会中止响应体，参见[模块文档](self#errors)。
+#[must_use]
+pub fn try_ndjson<S, T, E>(items: S) -> Response
+where
+    S: Stream<Item = std::result::Result<T, E>> + Send + 'static,
+    T: Serialize,
+    E: Into<BoxError>,
+{
+    streamed(items, Framing::Lines, NDJSON)
+}
+
+// ============================================================================
+// 编码
+// ============================================================================
+
+#[derive(Clone, Copy)]
+enum Framing {
+    /// `[a,b,c]`
+    Array,
+    /// `a\nb\nc\n`
+    Lines,
+}
+
+struct State<S> {
+    items: Pin<Box<S>>,
+    framing: Framing,
+    started: bool,
+    done: bool,
+}
+
+fn streamed<S, T, E>(items: S, framing: Framing, content_type: &str) -> Response
+where
+    S: Stream<Item = std::result::Result<T, E>> + Send + 'static,
+    T: Serialize,
+    E: Into<BoxError>,
+{
+    let state = State {
+        items: Box::pin(items),
+        framing,
+        started: false,
+        done: false,
+    };
+    let body = Body::from_stream(futures_util::stream::unfold(
+        state,
+        |mut state| async move {
+            if state.done {
+                return None;
+            }
+            let chunk = match state.items.next().await {
+                Some(Ok(item)) => encode(&item, state.framing, !state.started),
+                Some(Err(error)) => Err(error.into()),
+                None => {
+                    state.done = true;
+                    return match (state.framing, state.started) {
+                        (Framing::Array, true) => Some((Ok(Bytes::from_static(b"]")), state)),
+                        (Framing::Array, false) => Some((Ok(Bytes::from_static(b"[]")), state)),
+                        (Framing::Lines, _) => None,
+                    };
+                }
+            };
+            state.started = true;
+            state.done = chunk.is_err();
+            Some((chunk, state))
+        },
+    ));
+
+    let mut response = bytes(Vec::new()).content_type(content_type);
+    response.streaming = Some(Streaming::from_body(body));
+    response
+}
+
+/// Serialize one item with the separator that precedes or follows it
+/// / 序列化一个元素及其前后的分隔符
+fn encode<T: Serialize>(
+    item: &T,
+    framing: Framing,
+    first: bool,
+) -> std::result::Result<Bytes, BoxError> {
+    let mut chunk = Vec::with_capacity(128);
+    if let Framing::Array = framing {
+        chunk.push(if first { b'[' } else { b',' });
+    }
+    serde_json::to_writer(&mut chunk, item)?;
+    if let Framing::Lines = framing {
+        chunk.push(b'\n');
+    }
+    Ok(Bytes::from(chunk))
+}
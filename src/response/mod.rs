@@ -29,38 +29,78 @@
 //!
 //! - [`json`] - JSON responses (application/json)
 //!   [`json`] - JSON 响应 (application/json)
+//! - [`json_with_status`] / [`json_pretty`] / [`json_canonical`] - JSON with a
+//!   status, indented, or with sorted keys
+//!   [`json_with_status`] / [`json_pretty`] / [`json_canonical`] - 带状态码、缩进或键排序的 JSON
 //! - [`text`] - Plain text responses (text/plain)
 //!   [`text`] - 纯文本响应 (text/plain)
 //! - [`html`] - HTML responses (text/html)
 //!   [`html`] - HTML 响应 (text/html)
 //! - [`redirect`] - HTTP redirects (302 Found)
 //!   [`redirect`] - HTTP 重定向 (302 Found)
+//! - [`redirect_permanent`] (308) / [`redirect_moved`] (301) / [`redirect_see_other`] (303) /
+//!   [`redirect_temporary`] (307) - Other redirect statuses
+//!   其他重定向状态码
+//! - [`safe_redirect`] - Redirects to client-supplied targets, refusing foreign hosts
+//!   [`safe_redirect`] - 重定向到客户端提供的目标，拒绝外部主机
 //! - [`no_content`] - Empty responses (204 No Content)
 //!   [`no_content`] - 空响应 (204 No Content)
 //! - [`bytes`] - Raw byte responses
 //!   [`bytes`] - 原始字节响应
 //! - [`stream`] - Streaming responses
 //!   [`stream`] - 流式响应
+//! - [`json_stream`] / [`ndjson`] - JSON arrays and NDJSON streamed from a `Stream`
+//!   [`json_stream`] / [`ndjson`] - 从 `Stream` 流式发送的 JSON 数组和 NDJSON
+//! - [`file`] / [`attachment`] - File downloads with `Range` support
+//!   [`file`] / [`attachment`] - 支持 `Range` 的文件下载
+//! - [`negotiate`] - JSON / MessagePack / CBOR chosen from `Accept`
+//!   [`negotiate`] - 根据 `Accept` 选择 JSON / MessagePack / CBOR
+//! - `render` - HTML templates from `templates/` (feature `templates`)
+//!   `render` - 来自 `templates/` 的 HTML 模板（`templates` 特性）
 //!
 //! # Server Header
 //!
 //! # Server 头
 //!
 //! All responses automatically include a `Server: Astrea` header unless
-//! explicitly overridden.
+//! explicitly overridden. Use [`defaults::set_response_defaults`] to change or
+//! remove it, or to add security headers to every response.
 //!
-//! 所有响应自动包含 `Server: Astrea` 头，除非明确覆盖。
+//! 所有响应自动包含 `Server: Astrea` 头，除非明确覆盖。使用
+//! [`defaults::set_response_defaults`] 修改或移除它，或为每个响应添加安全响应头。
 
 use axum::{
-    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
     response::{IntoResponse, Response as AxumResponse},
 };
+use cookie::Cookie;
 
 pub mod builders;
+pub mod cache_control;
+pub mod conditional;
+pub mod defaults;
+pub mod file;
+pub mod headers;
+pub mod json_stream;
+pub mod negotiate;
+pub mod redirect;
 pub mod stream;
+#[cfg(feature = "templates")]
+pub mod template;
 
-pub use builders::{bytes, html, json, no_content, redirect, text};
+pub use builders::{
+    bytes, html, json, json_canonical, json_pretty, json_with_status, no_content, redirect,
+    redirect_moved, redirect_permanent, redirect_see_other, redirect_temporary, text,
+};
+pub use cache_control::CacheControl;
+pub use conditional::ETag;
+pub use file::{attachment, file};
+pub use json_stream::{json_stream, ndjson, try_json_stream, try_ndjson};
+pub use negotiate::{ResponseFormat, negotiate};
+pub use redirect::{resolve_location, safe_redirect};
 pub use stream::stream;
+#[cfg(feature = "templates")]
+pub use template::render;
 
 /// HTTP response type
 ///
@@ -82,7 +122,23 @@ pub use stream::stream;
 ///     .status(StatusCode::CREATED)
 ///     .header("X-Request-Id", "abc123");
 /// ```
-
+///
+/// # Streamed Bodies
+///
+/// # 流式响应体
+///
+/// [`file`], [`attachment`], [`json_stream`] and [`ndjson`] keep their body in
+/// a private field and read it when the response is sent. Because of that
+/// field, `Response` can't be built with a struct literal outside this crate;
+/// start from [`Response::new`] or a builder and set the public fields.
+/// A stream can only be read once, so a clone of such a response doesn't
+/// share it: the clone's body aborts when sent, and the original streams as
+/// usual.
+///
+/// [`file`]、[`attachment`]、[`json_stream`] 和 [`ndjson`] 将响应体保存在私有字段中，
+/// 并在发送响应时读取。由于该字段，在本 crate 之外无法用结构体字面量构建 `Response`；
+/// 请从 [`Response::new`] 或构建函数开始，再设置公开字段。流只能读取一次，
+/// 因此这类响应的克隆不会共享它：克隆的响应体在发送时中止，原响应照常流式发送。
 #[derive(Debug, Clone)]
 pub struct Response {
     /// HTTP status code
@@ -94,6 +150,9 @@ pub struct Response {
     /// Response body
     /// / 响应体
     pub body: Vec<u8>,
+    /// Body streamed at send time instead of `body` (see [`file`])
+    /// / 在发送时流式传输、替代 `body` 的响应体（参见 [`file`]）
+    pub(crate) streaming: Option<file::Streaming>,
 }
 
 impl Response {
@@ -135,9 +194,15 @@ impl Response {
         self
     }
 
-    /// Add a response header (chainable)
+    /// Set a response header, replacing any previous value (chainable)
+    ///
+    /// / 设置响应头，替换之前的值（可链式调用）
     ///
-    /// / 添加响应头（可链式调用）
+    /// An invalid name or value is left out with a `tracing` warning; use
+    /// [`try_header`](Self::try_header) to get an error instead.
+    ///
+    /// 无效的名称或值会被忽略并输出 `tracing` 警告；如需返回错误，请使用
+    /// [`try_header`](Self::try_header)。
     ///
     /// # Example
     ///
@@ -151,10 +216,11 @@ impl Response {
     /// ```
     #[must_use]
     pub fn header(mut self, key: &str, value: &str) -> Self {
-        if let Ok(name) = HeaderName::try_from(key)
-            && let Ok(v) = HeaderValue::try_from(value)
-        {
-            self.headers.insert(name, v);
+        match headers::parse(key, value) {
+            Ok((name, value)) => {
+                self.headers.insert(name, value);
+            }
+            Err(reason) => headers::discarded(key, &reason),
         }
         self
     }
@@ -170,20 +236,114 @@ impl Response {
     /// ```rust,ignore
     /// bytes(data).content_type("image/png")
     /// ```
-    pub fn content_type(mut self, content_type: &str) -> Self {
-        if let Ok(v) = HeaderValue::try_from(content_type) {
-            self.headers.insert(header::CONTENT_TYPE, v);
-        }
-        self
+    pub fn content_type(self, content_type: &str) -> Self {
+        self.header(header::CONTENT_TYPE.as_str(), content_type)
+    }
+
+    /// Add a `Set-Cookie` header (chainable)
+    ///
+    /// / 添加 `Set-Cookie` 响应头（可链式调用）
+    ///
+    /// Multiple cookies can be set by chaining; each one becomes its own
+    /// `Set-Cookie` header. The value is percent-encoded.
+    ///
+    /// 可链式设置多个 Cookie，每个都会成为独立的 `Set-Cookie` 头。值会进行百分号编码。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// json(data)?
+    ///     .cookie(Cookie::build(("theme", "dark")).path("/").http_only(true).build())
+    /// ```
+    #[must_use]
+    pub fn cookie(self, cookie: impl Into<Cookie<'static>>) -> Self {
+        let cookie = cookie.into();
+        self.append_header(header::SET_COOKIE.as_str(), &cookie.encoded().to_string())
+    }
+
+    /// Remove a cookie on the client (chainable)
+    ///
+    /// / 在客户端删除 Cookie（可链式调用）
+    ///
+    /// Sends a `Set-Cookie` header with an empty value and an expiry in the past.
+    /// Pass a [`Cookie`] with the same `path`/`domain` that was used to set it if
+    /// those differ from the defaults.
+    ///
+    /// 发送值为空且已过期的 `Set-Cookie` 头。如果设置 Cookie 时使用了非默认的
+    /// `path`/`domain`，请传入带有相同属性的 [`Cookie`]。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// no_content().remove_cookie("session")
+    /// no_content().remove_cookie(Cookie::build("session").path("/admin"))
+    /// ```
+    #[must_use]
+    pub fn remove_cookie(self, cookie: impl Into<Cookie<'static>>) -> Self {
+        let mut cookie = cookie.into();
+        cookie.make_removal();
+        self.cookie(cookie)
+    }
+
+    /// Add a signed `Set-Cookie` header (chainable)
+    ///
+    /// / 添加签名的 `Set-Cookie` 响应头（可链式调用）
+    ///
+    /// The value stays readable by the client but is authenticated with `key`;
+    /// read it back with [`get_signed_cookie`](crate::extract::get_signed_cookie).
+    /// Requires the `signed-cookies` feature.
+    ///
+    /// 值对客户端可见，但使用 `key` 进行了签名认证；
+    /// 使用 [`get_signed_cookie`](crate::extract::get_signed_cookie) 读取。
+    /// 需要启用 `signed-cookies` feature。
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// let key = cookie_key::<AppState>(&event)?;
+    /// json(user)?.signed_cookie(&key, Cookie::new("user_id", user.id.to_string()))
+    /// ```
+    #[cfg(feature = "signed-cookies")]
+    #[must_use]
+    pub fn signed_cookie(self, key: &cookie::Key, cookie: impl Into<Cookie<'static>>) -> Self {
+        let mut jar = cookie::CookieJar::new();
+        jar.signed_mut(key).add(cookie.into());
+        jar.delta().cloned().fold(self, Self::cookie)
+    }
+
+    /// Add an encrypted `Set-Cookie` header (chainable)
+    ///
+    /// / 添加加密的 `Set-Cookie` 响应头（可链式调用）
+    ///
+    /// The value is encrypted and authenticated with `key`; read it back with
+    /// [`get_private_cookie`](crate::extract::get_private_cookie).
+    /// Requires the `private-cookies` feature.
+    ///
+    /// 值使用 `key` 进行加密和认证；使用
+    /// [`get_private_cookie`](crate::extract::get_private_cookie) 读取。
+    /// 需要启用 `private-cookies` feature。
+    #[cfg(feature = "private-cookies")]
+    #[must_use]
+    pub fn private_cookie(self, key: &cookie::Key, cookie: impl Into<Cookie<'static>>) -> Self {
+        let mut jar = cookie::CookieJar::new();
+        jar.private_mut(key).add(cookie.into());
+        jar.delta().cloned().fold(self, Self::cookie)
     }
 
     /// Convert to Axum Response
     ///
     /// / 转换为 Axum Response
     ///
-    /// Automatically adds `Server: Astrea` header if not already set.
+    /// Adds the [response defaults](defaults) (`Server: Astrea` unless
+    /// configured otherwise) that are not already set.
     ///
-    /// 如果未设置，自动添加 `Server: Astrea` 头。
+    /// 添加尚未设置的[响应默认值](defaults)（除非另行配置，否则为 `Server: Astrea`）。
     ///
     /// # Note
     ///
@@ -194,11 +354,15 @@ impl Response {
     ///
     /// 此方法由 `IntoResponse` trait 自动调用。通常不需要直接调用。
     pub fn into_axum_response(mut self) -> AxumResponse {
-        // Add Server header if not manually set
-        // 添加 Server 头（如果未手动设置）
-        if !self.headers.contains_key(header::SERVER) {
-            self.headers
-                .insert(header::SERVER, HeaderValue::from_static("Astrea"));
+        // Add Server and configured default headers if not manually set
+        // 添加 Server 及已配置的默认响应头（如果未手动设置）
+        defaults::apply(&mut self.headers);
+        if let Some((body, length)) = self.streaming.take().and_then(file::Streaming::into_body) {
+            if let Some(length) = length {
+                self.headers
+                    .insert(header::CONTENT_LENGTH, HeaderValue::from(length));
+            }
+            return (self.status, self.headers, body).into_response();
         }
         (self.status, self.headers, self.body).into_response()
     }
@@ -210,6 +374,7 @@ impl Default for Response {
             status: StatusCode::OK,
             headers: HeaderMap::new(),
             body: Vec::new(),
+            streaming: None,
         }
     }
 }
@@ -0,0 +1,369 @@
+//! Content negotiation for structured responses
+//!
+//! / 结构化响应的内容协商
+//!
+//! [`negotiate`] serializes the same data in the representation the client
+//! prefers according to its `Accept` header:
+//!
+//! [`negotiate`] 根据客户端的 `Accept` 头，以其偏好的表示形式序列化同一份数据：
+//!
+//! - `application/json` → JSON
+//! - `application/msgpack` (also `application/x-msgpack`, `application/vnd.msgpack`)
+//!   → MessagePack, with the `msgpack` feature
+//!   MessagePack，需要 `msgpack` 特性
+//! - `application/cbor` → CBOR, with the `cbor` feature
+//!   CBOR，需要 `cbor` 特性
+//! - any media type added with [`register_response_format`]
+//!   通过 [`register_response_format`] 添加的任意媒体类型
+//!
+//! Requests without `Accept` get JSON. When no format is acceptable the
+//! handler returns `406 Not Acceptable`. Negotiated responses carry
+//! `Vary: Accept` so caches keep one entry per representation.
+//!
+//! 没有 `Accept` 的请求返回 JSON。没有可接受的格式时返回 `406 Not Acceptable`。
+//! 协商后的响应带有 `Vary: Accept`，以便缓存为每种表示形式保留一个条目。
+
+use std::sync::{OnceLock, RwLock};
+
+use axum::http::{StatusCode, header};
+use serde::Serialize;
+
+use super::{Response, bytes};
+use crate::{
+    Event,
+    error::{Result, RouteError},
+};
+
+/// Media type for JSON responses
+/// / JSON 响应的媒体类型
+const JSON: &str = "application/json";
+
+/// Media type for MessagePack responses, followed by the aliases clients also send
+/// / MessagePack 响应的媒体类型，其后是客户端也会发送的别名
+#[cfg(feature = "msgpack")]
+const MSGPACK: &[&str] = &[
+    "application/msgpack",
+    "application/x-msgpack",
+    "application/vnd.msgpack",
+];
+
+/// Media type for CBOR responses
+/// / CBOR 响应的媒体类型
+#[cfg(feature = "cbor")]
+const CBOR: &str = "application/cbor";
+
+/// Encoder for a custom response format
+///
+/// / 自定义响应格式的编码器
+///
+/// Receives the data as a `serde_json::Value` and returns the encoded body.
+///
+/// 以 `serde_json::Value` 形式接收数据，返回编码后的响应体。
+pub type ResponseEncoder = fn(&serde_json::Value) -> anyhow::Result<Vec<u8>>;
+
+static CUSTOM_FORMATS: OnceLock<RwLock<Vec<(&'static str, ResponseEncoder)>>> = OnceLock::new();
+
+fn custom_formats() -> &'static RwLock<Vec<(&'static str, ResponseEncoder)>> {
+    CUSTOM_FORMATS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// A response format [`negotiate`] can produce
+///
+/// / [`negotiate`] 可以生成的响应格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// `application/json`
+    Json,
+    /// `application/msgpack`
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+    /// `application/cbor`
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// A media type added with [`register_response_format`]
+    /// / 通过 [`register_response_format`] 添加的媒体类型
+    Custom(&'static str),
+}
+
+impl ResponseFormat {
+    /// The media type sent in `Content-Type` for this format
+    /// / 此格式在 `Content-Type` 中发送的媒体类型
+    #[must_use]
+    pub fn media_type(&self) -> &'static str {
+        self.media_types()[0]
+    }
+
+    /// Every media type the client may ask for this format with
+    /// / 客户端可用于请求此格式的所有媒体类型
+    fn media_types(&self) -> &[&'static str] {
+        match self {
+            Self::Json => &[JSON],
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => MSGPACK,
+            #[cfg(feature = "cbor")]
+            Self::Cbor => &[CBOR],
+            Self::Custom(media_type) => std::slice::from_ref(media_type),
+        }
+    }
+
+    /// Quality the `Accept` ranges give this format, from its most specific matching range
+    /// / `Accept` 范围给予此格式的权重，取最具体的匹配范围
+    fn quality(&self, ranges: &[MediaRange]) -> Option<f32> {
+        self.media_types()
+            .iter()
+            .flat_map(|media_type| {
+                ranges
+                    .iter()
+                    .filter_map(|range| range.specificity(media_type).map(|s| (s, range.q)))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, q)| q)
+    }
+}
+
+/// Register an encoder for an additional response media type
+///
+/// / 为额外的响应媒体类型注册编码器
+///
+/// Registering the same media type again replaces its encoder.
+///
+/// 重复注册同一媒体类型会替换其编码器。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// fn encode_yaml(value: &serde_json::Value) -> anyhow::Result<Vec<u8>> {
+///     Ok(serde_yaml::to_string(value)?.into_bytes())
+/// }
+///
+/// astrea::response::negotiate::register_response_format("application/yaml", encode_yaml);
+/// ```
+pub fn register_response_format(media_type: &'static str, encoder: ResponseEncoder) {
+    let mut formats = custom_formats().write().unwrap();
+    match formats
+        .iter_mut()
+        .find(|(m, _)| m.eq_ignore_ascii_case(media_type))
+    {
+        Some(entry) => entry.1 = encoder,
+        None => formats.push((media_type, encoder)),
+    }
+}
+
+/// Get every format [`negotiate`] can produce, built-in formats first
+///
+/// / 获取 [`negotiate`] 可以生成的所有格式，内置格式在前
+#[must_use]
+pub fn response_formats() -> Vec<ResponseFormat> {
+    let mut formats = vec![ResponseFormat::Json];
+    #[cfg(feature = "msgpack")]
+    formats.push(ResponseFormat::MessagePack);
+    #[cfg(feature = "cbor")]
+    formats.push(ResponseFormat::Cbor);
+    formats.extend(
+        custom_formats()
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(m, _)| ResponseFormat::Custom(m)),
+    );
+    formats
+}
+
+/// Serialize `data` in the format preferred by the request's `Accept` header
+///
+/// / 以请求 `Accept` 头偏好的格式序列化 `data`
+///
+/// Chooses among every format in [`response_formats`]. On equal preference the
+/// earlier format wins, so `Accept: */*` gets JSON.
+///
+/// 在 [`response_formats`] 的所有格式中选择。偏好相同时靠前的格式优先，
+/// 因此 `Accept: */*` 得到 JSON。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// - `406 Not Acceptable` if the client accepts none of the formats
+///   客户端不接受任何格式时返回 `406 Not Acceptable`
+/// - `RouteError::Internal` if serialization fails
+///   序列化失败时返回 `RouteError::Internal`
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[route]
+/// pub async fn handler(event: Event) -> Result<Response> {
+///     let user = find_user(&event).await?;
+///     negotiate(&event, &user) // JSON for browsers, MessagePack for mobile clients
+/// }
+/// ```
+pub fn negotiate<T: Serialize>(event: &Event, data: T) -> Result<Response> {
+    negotiate_any(event, data, &response_formats())
+}
+
+/// Serialize `data` like [`negotiate`], choosing only among the given formats
+///
+/// / 像 [`negotiate`] 一样序列化 `data`，但仅在给定的格式中选择
+///
+/// Requests without `Accept` get the first format.
+///
+/// 没有 `Accept` 的请求得到第一个格式。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Same as [`negotiate`].
+///
+/// 与 [`negotiate`] 相同。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// negotiate_any(&event, &report, &[ResponseFormat::Custom("text/csv"), ResponseFormat::Json])
+/// ```
+pub fn negotiate_any<T: Serialize>(
+    event: &Event,
+    data: T,
+    formats: &[ResponseFormat],
+) -> Result<Response> {
+    let accept = event
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok());
+    let Some(format) = select_format(accept, formats) else {
+        let available: Vec<&str> = formats.iter().map(ResponseFormat::media_type).collect();
+        return Err(RouteError::custom(
+            StatusCode::NOT_ACCEPTABLE,
+            format!(
+                "Not acceptable: `{}`, available: {}",
+                accept.unwrap_or_default(),
+                available.join(", ")
+            ),
+        ));
+    };
+
+    let body = encode(format, &data).map_err(|e| {
+        RouteError::Internal(anyhow::anyhow!(
+            "Failed to serialize {}: {e}",
+            format.media_type()
+        ))
+    })?;
+    Ok(bytes(body).content_type(format.media_type()).vary("Accept"))
+}
+
+/// Pick the format an `Accept` header value prefers
+///
+/// / 选择 `Accept` 头的值所偏好的格式
+///
+/// Returns the first format when `accept` is `None`, and `None` when every
+/// format is refused (not listed, or listed with `q=0`).
+///
+/// `accept` 为 `None` 时返回第一个格式；所有格式都被拒绝（未列出或 `q=0`）时返回 `None`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let format = select_format(Some("application/cbor, */*;q=0.1"), &response_formats());
+/// ```
+#[must_use]
+pub fn select_format(accept: Option<&str>, formats: &[ResponseFormat]) -> Option<ResponseFormat> {
+    let Some(accept) = accept.filter(|a| !a.trim().is_empty()) else {
+        return formats.first().copied();
+    };
+    let ranges = parse_accept(accept);
+
+    let mut best: Option<(ResponseFormat, f32)> = None;
+    for format in formats {
+        if let Some(q) = format.quality(&ranges)
+            && q > 0.0
+            && best.is_none_or(|(_, best_q)| q > best_q)
+        {
+            best = Some((*format, q));
+        }
+    }
+    best.map(|(format, _)| format)
+}
+
+// ============================================================================
+// 编码与 Accept 解析
+// ============================================================================
+
+fn encode<T: Serialize>(format: ResponseFormat, data: &T) -> anyhow::Result<Vec<u8>> {
+    match format {
+        ResponseFormat::Json => Ok(serde_json::to_vec(data)?),
+        #[cfg(feature = "msgpack")]
+        ResponseFormat::MessagePack => Ok(rmp_serde::to_vec_named(data)?),
+        #[cfg(feature = "cbor")]
+        ResponseFormat::Cbor => {
+            let mut body = Vec::new();
+            ciborium::into_writer(data, &mut body)?;
+            Ok(body)
+        }
+        ResponseFormat::Custom(media_type) => {
+            let encoder = custom_formats()
+                .read()
+                .unwrap()
+                .iter()
+                .find(|(m, _)| m.eq_ignore_ascii_case(media_type))
+                .map(|(_, e)| *e)
+                .ok_or_else(|| anyhow::anyhow!("no encoder registered"))?;
+            encoder(&serde_json::to_value(data)?)
+        }
+    }
+}
+
+/// One entry of an `Accept` header
+/// / `Accept` 头中的一项
+struct MediaRange {
+    /// Lowercase `type/subtype`, either part possibly `*`
+    /// / 小写的 `type/subtype`，任一部分都可能是 `*`
+    essence: String,
+    q: f32,
+}
+
+impl MediaRange {
+    /// How specifically this range matches a media type (`*/*` < `type/*` < exact)
+    /// / 此范围匹配媒体类型的具体程度（`*/*` < `type/*` < 精确匹配）
+    fn specificity(&self, media_type: &str) -> Option<u8> {
+        if self.essence.eq_ignore_ascii_case(media_type) {
+            return Some(2);
+        }
+        if self.essence == "*/*" {
+            return Some(0);
+        }
+        let (kind, subtype) = self.essence.split_once('/')?;
+        let (media_kind, _) = media_type.split_once('/')?;
+        (subtype == "*" && kind.eq_ignore_ascii_case(media_kind)).then_some(1)
+    }
+}
+
+fn parse_accept(accept: &str) -> Vec<MediaRange> {
+    accept
+        .split(',')
+        .filter_map(|entry| {
+            let mut params = entry.split(';');
+            let essence = params.next()?.trim().to_ascii_lowercase();
+            if !essence.contains('/') {
+                return None;
+            }
+            let mut q = 1.0;
+            for param in params {
+                if let Some((name, value)) = param.split_once('=')
+                    && name.trim().eq_ignore_ascii_case("q")
+                {
+                    q = value.trim().parse::<f32>().ok()?.clamp(0.0, 1.0);
+                }
+            }
+            Some(MediaRange { essence, q })
+        })
+        .collect()
+}
@@ -0,0 +1,231 @@
+//! Redirects resolved against the request, and open-redirect protection
+//!
+//! / 基于请求解析的重定向，以及开放重定向防护
+//!
+//! Redirect targets often come from the client (`?next=/account`). Passing
+//! them straight to [`redirect`] lets anyone build a link on your domain that
+//! forwards to a phishing site. [`safe_redirect`] only follows targets on the
+//! request's own host or on a host added with [`allow_redirect_hosts`], and
+//! answers everything else with `400 Bad Request`.
+//!
+//! 重定向目标经常来自客户端（`?next=/account`）。直接将其传给 [`redirect`]
+//! 会让任何人都能在你的域名上构造一个转发到钓鱼网站的链接。[`safe_redirect`]
+//! 只跟随请求自身主机或通过 [`allow_redirect_hosts`] 添加的主机上的目标，
+//! 其他目标一律返回 `400 Bad Request`。
+
+use std::sync::{OnceLock, RwLock};
+
+use axum::http::{Uri, uri::Authority};
+
+use super::{Response, redirect};
+use crate::{
+    Event,
+    error::{Result, RouteError},
+};
+
+static ALLOWED_HOSTS: OnceLock<RwLock<Vec<String>>> = OnceLock::new();
+
+fn allowed_hosts() -> &'static RwLock<Vec<String>> {
+    ALLOWED_HOSTS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Allow [`safe_redirect`] to send clients to other hosts
+///
+/// / 允许 [`safe_redirect`] 将客户端重定向到其他主机
+///
+/// Entries are host names without scheme or port. `*.example.com` allows
+/// every subdomain of `example.com`, but not `example.com` itself.
+///
+/// 条目是不含协议和端口的主机名。`*.example.com` 允许 `example.com` 的所有子域名，
+/// 但不包括 `example.com` 本身。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// astrea::response::redirect::allow_redirect_hosts(["accounts.example.com", "*.example.org"]);
+/// ```
+pub fn allow_redirect_hosts<I, H>(hosts: I)
+where
+    I: IntoIterator<Item = H>,
+    H: Into<String>,
+{
+    let mut allowed = allowed_hosts().write().unwrap();
+    for host in hosts {
+        let host = host.into().to_ascii_lowercase();
+        if !allowed.contains(&host) {
+            allowed.push(host);
+        }
+    }
+}
+
+/// Resolve a redirect target against the request URI
+///
+/// / 基于请求 URI 解析重定向目标
+///
+/// Relative references (`edit`, `../list`, `?page=2`) become absolute paths,
+/// with `.` and `..` segments removed. Absolute paths are normalized the same
+/// way; URLs with a scheme or host are returned unchanged.
+///
+/// 相对引用（`edit`、`../list`、`?page=2`）变为绝对路径，并移除 `.` 和 `..` 段。
+/// 绝对路径以同样方式规范化；带有协议或主机的 URL 原样返回。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// // GET /users/42/profile
+/// assert_eq!(resolve_location(&event, "../43/profile"), "/users/43/profile");
+/// ```
+#[must_use]
+pub fn resolve_location(event: &Event, target: &str) -> String {
+    if is_absolute(target) {
+        return target.to_string();
+    }
+    let base = event.uri();
+    resolve_reference(base.path(), base.query(), target)
+}
+
+/// Redirect (302 Found) to a target that stays on a trusted host
+///
+/// / 重定向（302 Found）到位于可信主机上的目标
+///
+/// The target is first resolved with [`resolve_location`]. A result with a
+/// scheme or host must use `http` or `https` and point at the request's host
+/// or at a host allowed with [`allow_redirect_hosts`]. Chain `.status(..)`
+/// for another redirect status.
+///
+/// 目标首先通过 [`resolve_location`] 解析。带有协议或主机的结果必须使用 `http` 或 `https`，
+/// 并指向请求的主机或通过 [`allow_redirect_hosts`] 允许的主机。
+/// 需要其他重定向状态码时可链式调用 `.status(..)`。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::BadRequest` for a foreign host, another scheme
+/// (`javascript:`), or a target containing backslashes or control characters.
+///
+/// 对外部主机、其他协议（`javascript:`），或包含反斜杠、控制字符的目标，
+/// 返回 `RouteError::BadRequest`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let next = get_query_param(&event, "next").unwrap_or_else(|| "/".into());
+/// Ok(safe_redirect(&event, &next)?.status(StatusCode::SEE_OTHER))
+/// ```
+pub fn safe_redirect(event: &Event, target: &str) -> Result<Response> {
+    let rejected = || RouteError::bad_request(format!("Unsafe redirect target: {target}"));
+
+    // Browsers treat `\` as `/` and drop tabs and newlines, which turns
+    // `/\evil.com` into a protocol-relative URL
+    // 浏览器将 `\` 视为 `/` 并丢弃制表符和换行符，这会把 `/\evil.com` 变为协议相对 URL
+    if target
+        .chars()
+        .any(|c| c == '\\' || c.is_control() || c.is_whitespace())
+    {
+        return Err(rejected());
+    }
+    // Check the resolved location: dot segments can turn `/.//evil.com` into
+    // the protocol-relative `//evil.com`
+    // 检查解析后的位置：点段可能把 `/.//evil.com` 变为协议相对的 `//evil.com`
+    let location = resolve_location(event, target);
+    if !is_absolute(&location) {
+        return redirect(&location);
+    }
+
+    let uri = match location.strip_prefix("//") {
+        Some(rest) => Uri::try_from(format!("{}://{rest}", event.scheme())),
+        None => Uri::try_from(location.as_str()),
+    }
+    .map_err(|_| rejected())?;
+    if !matches!(uri.scheme_str(), Some("http" | "https")) {
+        return Err(rejected());
+    }
+    match uri.host() {
+        Some(host) if host_allowed(event, host) => redirect(&location),
+        _ => Err(rejected()),
+    }
+}
+
+// ============================================================================
+// URL 解析
+// ============================================================================
+
+/// Whether the target has a scheme (`https:`) or a host (`//example.com`)
+/// / 目标是否带有协议（`https:`）或主机（`//example.com`）
+fn is_absolute(target: &str) -> bool {
+    if target.starts_with("//") {
+        return true;
+    }
+    let Some(colon) = target.find(':') else {
+        return false;
+    };
+    let scheme = &target[..colon];
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+fn host_allowed(event: &Event, host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    let own = event
+        .host()
+        .and_then(|h| h.parse::<Authority>().ok())
+        .is_some_and(|own| own.host().eq_ignore_ascii_case(&host));
+    own || allowed_hosts().read().unwrap().iter().any(|allowed| {
+        allowed == &host
+            || allowed
+                .strip_prefix('*')
+                .is_some_and(|suffix| suffix.starts_with('.') && host.ends_with(suffix))
+    })
+}
+
+/// Resolve a relative reference against a base path (RFC 3986 §5.2)
+/// / 基于基础路径解析相对引用（RFC 3986 §5.2）
+fn resolve_reference(base_path: &str, base_query: Option<&str>, target: &str) -> String {
+    let split = target.find(['?', '#']).unwrap_or(target.len());
+    let (path, rest) = target.split_at(split);
+
+    if path.is_empty() {
+        // `?q` keeps the path, `#frag` (or nothing) also keeps the query
+        // `?q` 保留路径，`#frag`（或空）同时保留查询
+        let query = match base_query {
+            Some(query) if !rest.starts_with('?') => format!("?{query}"),
+            _ => String::new(),
+        };
+        return format!("{base_path}{query}{rest}");
+    }
+    let merged = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        let dir = &base_path[..base_path.rfind('/').map_or(0, |i| i + 1)];
+        format!("/{}{path}", dir.trim_start_matches('/'))
+    };
+    format!("{}{rest}", remove_dot_segments(&merged))
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/').skip(1) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut resolved = format!("/{}", segments.join("/"));
+    // A trailing `.` or `..` names a directory / 结尾的 `.` 或 `..` 表示目录
+    if (path.ends_with("/.") || path.ends_with("/..")) && !resolved.ends_with('/') {
+        resolved.push('/');
+    }
+    resolved
+}
@@ -30,5 +30,7 @@ use axum::{
 /// ```
 #[must_use]
 pub fn stream(body: Body) -> AxumResponse {
-    body.into_response()
+    let mut response = body.into_response();
+    super::defaults::apply(response.headers_mut());
+    response
 }
@@ -0,0 +1,188 @@
+//! Server-side HTML templates (feature `templates`)
+//!
+//! / 服务端 HTML 模板（`templates` 特性）
+//!
+//! [`render`] renders a [MiniJinja] template from the `templates/` directory
+//! into an HTML response. Templates use Jinja2 syntax, so layouts are plain
+//! `{% extends "layout.html" %}` / `{% block %}` inheritance.
+//!
+//! [`render`] 将 `templates/` 目录中的 [MiniJinja] 模板渲染为 HTML 响应。
+//! 模板使用 Jinja2 语法，因此布局就是普通的 `{% extends "layout.html" %}` / `{% block %}` 继承。
+//!
+//! Compiled templates are cached after their first use. In debug builds the
+//! cache is dropped whenever a file in the directory changes, so edits show up
+//! on the next request without a restart.
+//!
+//! 编译后的模板在首次使用后被缓存。在 debug 构建中，目录中的文件一旦变化就会丢弃缓存，
+//! 因此修改无需重启即可在下一次请求中生效。
+//!
+//! [MiniJinja]: https://docs.rs/minijinja
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{OnceLock, RwLock},
+    time::SystemTime,
+};
+
+pub use minijinja::Environment;
+use serde::Serialize;
+
+use super::{Response, html};
+use crate::error::{Result, RouteError};
+
+/// Default templates directory, relative to the working directory
+/// / 默认模板目录，相对于工作目录
+const DEFAULT_DIR: &str = "templates";
+
+struct Engine {
+    dir: PathBuf,
+    env: Environment<'static>,
+    /// Newest modification time seen in `dir`, for auto-reload in debug builds
+    /// / `dir` 中见到的最新修改时间，用于 debug 构建中的自动重载
+    stamp: Option<SystemTime>,
+}
+
+impl Engine {
+    fn new(dir: PathBuf) -> Self {
+        let mut env = Environment::new();
+        env.set_loader(minijinja::path_loader(&dir));
+        Self {
+            stamp: newest_mtime(&dir),
+            dir,
+            env,
+        }
+    }
+}
+
+static ENGINE: OnceLock<RwLock<Engine>> = OnceLock::new();
+
+fn engine() -> &'static RwLock<Engine> {
+    ENGINE.get_or_init(|| RwLock::new(Engine::new(PathBuf::from(DEFAULT_DIR))))
+}
+
+/// Load templates from another directory instead of `templates/`
+///
+/// / 从其他目录而不是 `templates/` 加载模板
+///
+/// Drops every cached template. Settings made with [`configure`] are kept.
+///
+/// 丢弃所有已缓存的模板。通过 [`configure`] 进行的设置会被保留。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// astrea::response::template::set_templates_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/views"));
+/// ```
+pub fn set_templates_dir(dir: impl Into<PathBuf>) {
+    let mut engine = engine().write().unwrap();
+    engine.dir = dir.into();
+    let loader = minijinja::path_loader(&engine.dir);
+    engine.env.set_loader(loader);
+    engine.env.clear_templates();
+    engine.stamp = newest_mtime(&engine.dir);
+}
+
+/// Customize the template environment (filters, functions, globals)
+///
+/// / 自定义模板环境（过滤器、函数、全局变量）
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// astrea::response::template::configure(|env| {
+///     env.add_global("site_name", "Astrea");
+///     env.add_filter("shout", |s: String| s.to_uppercase());
+/// });
+/// ```
+pub fn configure(f: impl FnOnce(&mut Environment<'static>)) {
+    f(&mut engine().write().unwrap().env);
+}
+
+/// Render a template into an HTML response
+///
+/// / 将模板渲染为 HTML 响应
+///
+/// `name` is the path of the template inside the templates directory. Values
+/// are HTML-escaped in `.html` templates.
+///
+/// `name` 是模板在模板目录中的路径。在 `.html` 模板中，值会进行 HTML 转义。
+///
+/// # Errors
+///
+/// # 错误
+///
+/// Returns `RouteError::Internal` naming the template and line when the
+/// template is missing, has a syntax error or fails to render.
+///
+/// 模板不存在、存在语法错误或渲染失败时，返回包含模板名称和行号的 `RouteError::Internal`。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// // templates/users/show.html: {% extends "layout.html" %}{% block body %}{{ user.name }}{% endblock %}
+/// let user = find_user(&event).await?;
+/// render("users/show.html", json!({ "user": user }))
+/// ```
+pub fn render<S: Serialize>(name: &str, ctx: S) -> Result<Response> {
+    if cfg!(debug_assertions) {
+        reload_if_changed();
+    }
+    let engine = engine().read().unwrap();
+    engine
+        .env
+        .get_template(name)
+        .and_then(|template| template.render(ctx))
+        .map(html)
+        .map_err(|e| template_error(name, &e))
+}
+
+// ============================================================================
+// 自动重载与错误
+// ============================================================================
+
+/// Drop the cached templates when a file changed since they were loaded
+/// / 当文件在模板加载后发生变化时，丢弃已缓存的模板
+fn reload_if_changed() {
+    let dir = engine().read().unwrap().dir.clone();
+    let stamp = newest_mtime(&dir);
+    if engine().read().unwrap().stamp == stamp {
+        return;
+    }
+    let mut engine = engine().write().unwrap();
+    engine.env.clear_templates();
+    engine.stamp = stamp;
+}
+
+/// Newest modification time of the files and directories below `dir`
+/// / `dir` 下文件和目录的最新修改时间
+fn newest_mtime(dir: &Path) -> Option<SystemTime> {
+    let mut newest = std::fs::metadata(dir).and_then(|m| m.modified()).ok();
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let modified = if path.is_dir() {
+            newest_mtime(&path)
+        } else {
+            entry.metadata().and_then(|m| m.modified()).ok()
+        };
+        newest = newest.max(modified);
+    }
+    newest
+}
+
+fn template_error(name: &str, error: &minijinja::Error) -> RouteError {
+    let template = error.name().unwrap_or(name);
+    let location = match error.line() {
+        Some(line) => format!("{template}:{line}"),
+        None => template.to_string(),
+    };
+    let detail = error
+        .detail()
+        .map_or_else(|| error.kind().to_string(), str::to_string);
+    RouteError::Internal(anyhow::anyhow!("Template error in {location}: {detail}"))
+}
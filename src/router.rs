@@ -1,4 +1,437 @@
-//! 文件路由器
+//! File router runtime support
 //!
-//! 路由生成由 `generate_routes!` 过程宏在编译时完成，
-//! 此模块保留用于未来扩展（中间件注册、路由分组等）。
+//! / 文件路由器运行时支持
+//!
+//! Route discovery happens at compile time in `generate_routes!`. The macro
+//! emits a static description of every route and middleware scope, and the
+//! functions in this module turn that description into an `axum::Router`
+//! with a deterministic middleware order.
+//!
+//! 路由发现由 `generate_routes!` 在编译时完成。宏会生成所有路由和中间件作用域的
+//! 静态描述，本模块中的函数将其组装为具有确定性中间件顺序的 `axum::Router`。
+//!
+//! # Middleware Ordering
+//!
+//! # 中间件顺序
+//!
+//! For every route, the middleware chain is resolved as follows:
+//!
+//! 对于每个路由，中间件链按如下方式解析：
+//!
+//! 1. Walk from the route's nearest scope up to the root, stopping after the
+//!    first scope in [`MiddlewareMode::Override`] mode.
+//!    从路由最近的作用域向根作用域回溯，遇到第一个覆盖模式的作用域后停止。
+//! 2. Order the collected scopes from root to leaf (parent outside, child inside).
+//!    按从根到叶的顺序排列（父级在外，子级在内）。
+//! 3. Stable-sort by [`Middleware::priority`] descending — higher priorities
+//!    run further out. Equal priorities keep the proximity order of step 2.
+//!    按 [`Middleware::priority`] 降序稳定排序 — 优先级越高越靠外层。
+//!    优先级相同时保持第 2 步的就近顺序。
+//!
+//! The first entry of the chain is the outermost layer: it sees the request
+//! first and the response last.
+//!
+//! 链中第一个条目是最外层：最先看到请求，最后看到响应。
+//!
+//! # Body Limits
+//!
+//! # 请求体限制
+//!
+//! A route's scope body limit is the [`Middleware::body_limit`] of the nearest
+//! scope that sets one, walking up the same way as step 1. It is attached to
+//! the request as a [`BodyLimit`] extension inside all middleware layers.
+//!
+//! 路由的作用域请求体限制来自最近一个设置了 [`Middleware::body_limit`] 的作用域，
+//! 回溯方式与第 1 步相同。它以 [`BodyLimit`] 扩展的形式附加到请求上，位于所有中间件层之内。
+//!
+//! [`Middleware::cache_control`] is resolved the same way and attached as a
+//! [`ScopeCacheControl`] extension.
+//!
+//! [`Middleware::cache_control`] 以相同方式解析，并以 [`ScopeCacheControl`] 扩展的形式附加。
+//!
+//! # Layer Instances
+//!
+//! # 中间件层实例
+//!
+//! Each scope's `middleware()` is called once and its layers wrap every route
+//! that uses them, so stateful layers (rate limits, [`cache::layer`](crate::cache::layer))
+//! share one state across the scope. The exception is a [`Middleware::priority`]
+//! that moves a scope outside one of its ancestors: the ancestor then runs
+//! inside that scope for some routes and outside it for others, and gets one
+//! more instance for each such position.
+//!
+//! 每个作用域的 `middleware()` 只调用一次，其中间件层包裹所有使用它的路由，
+//! 因此有状态的中间件层（限流、[`cache::layer`](crate::cache::layer)）在整个作用域内共享同一状态。
+//! 例外情况是 [`Middleware::priority`] 将某个作用域移到其祖先之外：此时祖先对部分路由位于该作用域之内，
+//! 对其他路由位于其外，每多一个这样的位置就多一个实例。
+
+use axum::Router;
+use axum::routing::MethodRouter;
+
+use crate::event::body::BodyLimit;
+use crate::middleware::{Middleware, MiddlewareMode};
+use crate::response::cache_control::{CacheControl, ScopeCacheControl};
+
+/// Static description of a route emitted by `generate_routes!`
+///
+/// / 由 `generate_routes!` 生成的路由静态描述
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteDef {
+    /// HTTP method in upper case (e.g., `"GET"`)
+    /// / 大写的 HTTP 方法（如 `"GET"`）
+    pub method: &'static str,
+    /// Axum route path (e.g., `/users/{id}`)
+    /// / Axum 路由路径（如 `/users/{id}`）
+    pub path: &'static str,
+    /// Index of the nearest middleware scope in the scope table, if any
+    /// / 最近的中间件作用域在作用域表中的索引（如果有）
+    pub scope: Option<usize>,
+}
+
+/// The file-derived identity of a route, attached to each request as an extension
+///
+/// / 由文件派生的路由标识，以扩展形式附加到每个请求上
+///
+/// Inserted by `generate_routes!` on every endpoint and exposed through
+/// [`Event::route_pattern`](crate::Event::route_pattern) and
+/// [`Event::route_source_file`](crate::Event::route_source_file).
+///
+/// 由 `generate_routes!` 插入到每个端点上，并通过
+/// [`Event::route_pattern`](crate::Event::route_pattern) 和
+/// [`Event::route_source_file`](crate::Event::route_source_file) 暴露。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// Axum route pattern (e.g., `/users/{id}`)
+    /// / Axum 路由模式（如 `/users/{id}`）
+    pub pattern: &'static str,
+    /// Route file relative to the crate root (e.g., `src/routes/users/[id].get.rs`)
+    /// / 相对于 crate 根目录的路由文件（如 `src/routes/users/[id].get.rs`）
+    pub source_file: &'static str,
+}
+
+/// Static description of a `_middleware.rs` scope emitted by `generate_routes!`
+///
+/// / 由 `generate_routes!` 生成的 `_middleware.rs` 作用域静态描述
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScopeDef {
+    /// Display path of the scope (e.g., `"/"` or `"/api"`)
+    /// / 作用域显示路径（如 `"/"` 或 `"/api"`）
+    pub path: &'static str,
+    /// Index of the parent middleware scope, if any
+    /// / 父中间件作用域的索引（如果有）
+    pub parent: Option<usize>,
+}
+
+/// Mode, priority, name and per-scope settings of a scope, read once from its `middleware()` function
+///
+/// / 作用域的模式、优先级、名称和作用域设置，从其 `middleware()` 函数读取一次
+struct ScopeProbe {
+    mode: MiddlewareMode,
+    priority: i32,
+    name: String,
+    body_limit: Option<usize>,
+    cache_control: Option<CacheControl>,
+}
+
+/// Every scope's middleware, loaded once and shared by the startup listing and the router
+///
+/// / 每个作用域的中间件，只加载一次，由启动表和路由器共享
+///
+/// `middleware()` functions may register state (e.g. [`cache::layer`](crate::cache::layer)),
+/// so the generated `create_router()` loads the scopes once with
+/// [`LoadedScopes::load`], lists the routes with [`LoadedScopes::chain`] and
+/// hands the same instances to [`build_router_with`].
+///
+/// `middleware()` 函数可能会注册状态（例如 [`cache::layer`](crate::cache::layer)），
+/// 因此生成的 `create_router()` 只通过 [`LoadedScopes::load`] 加载一次作用域，
+/// 使用 [`LoadedScopes::chain`] 列出路由，并将同一批实例交给 [`build_router_with`]。
+pub struct LoadedScopes<S> {
+    middleware: Vec<Option<Middleware<S>>>,
+    probes: Vec<ScopeProbe>,
+}
+
+impl<S> LoadedScopes<S> {
+    /// Call every scope's `middleware()` once
+    /// / 调用每个作用域的 `middleware()` 一次
+    pub fn load(scopes: &[ScopeDef], load: &dyn Fn(usize) -> Middleware<S>) -> Self {
+        let middleware: Vec<_> = (0..scopes.len()).map(load).collect();
+        let probes = scopes
+            .iter()
+            .zip(&middleware)
+            .map(|(def, mw)| ScopeProbe {
+                mode: mw.mode,
+                priority: mw.priority,
+                body_limit: mw.body_limit,
+                cache_control: mw.cache_control.clone(),
+                name: mw.name.clone().unwrap_or_else(|| def.path.to_string()),
+            })
+            .collect();
+        Self {
+            middleware: middleware.into_iter().map(Some).collect(),
+            probes,
+        }
+    }
+
+    /// The loaded middleware of scope `index`, until the router takes it
+    /// / 作用域 `index` 已加载的中间件，在路由器取走之前可用
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&Middleware<S>> {
+        self.middleware.get(index)?.as_ref()
+    }
+
+    /// Get the middleware chain applied to a route, see [`middleware_chain`]
+    /// / 获取作用于某个路由的中间件链，参见 [`middleware_chain`]
+    #[must_use]
+    pub fn chain(
+        &self,
+        routes: &[RouteDef],
+        scopes: &[ScopeDef],
+        method: &str,
+        path: &str,
+    ) -> Option<Vec<String>> {
+        let route = find_route(routes, method, path)?;
+        Some(
+            resolve_chain(scopes, &self.probes, route.scope)
+                .into_iter()
+                .map(|i| self.probes[i].name.clone())
+                .collect(),
+        )
+    }
+}
+
+/// Resolve the ordered chain of scope indices for a route, outermost first
+///
+/// / 解析路由的有序作用域索引链，最外层在前
+fn resolve_chain(scopes: &[ScopeDef], probes: &[ScopeProbe], leaf: Option<usize>) -> Vec<usize> {
+    let mut chain = Vec::new();
+    let mut current = leaf;
+    while let Some(i) = current {
+        chain.push(i);
+        if probes[i].mode == MiddlewareMode::Override {
+            break;
+        }
+        current = scopes[i].parent;
+    }
+    chain.reverse();
+    chain.sort_by_key(|&i| std::cmp::Reverse(probes[i].priority));
+    chain
+}
+
+/// Find the value of the nearest scope that sets one
+///
+/// / 查找最近一个设置了该值的作用域的值
+fn resolve_nearest<T: Clone>(
+    scopes: &[ScopeDef],
+    probes: &[ScopeProbe],
+    leaf: Option<usize>,
+    get: impl Fn(&ScopeProbe) -> Option<&T>,
+) -> Option<T> {
+    let mut current = leaf;
+    while let Some(i) = current {
+        if let Some(value) = get(&probes[i]) {
+            return Some(value.clone());
+        }
+        if probes[i].mode == MiddlewareMode::Override {
+            break;
+        }
+        current = scopes[i].parent;
+    }
+    None
+}
+
+/// Build a router from the generated route and scope tables
+///
+/// / 根据生成的路由表和作用域表构建路由器
+///
+/// `endpoints` must be in the same order as `routes`. Each scope's layers are
+/// applied once around all routes below it, see [Layer Instances](self#layer-instances).
+///
+/// `endpoints` 必须与 `routes` 顺序一致。每个作用域的中间件层只应用一次，包裹其下的所有路由，
+/// 参见[中间件层实例](self#layer-instances)。
+///
+/// The generated `create_router()` calls [`build_router_with`] with the
+/// scopes it already loaded. You typically don't need to call either directly.
+///
+/// 生成的 `create_router()` 会使用已加载的作用域调用 [`build_router_with`]。
+/// 通常不需要直接调用这两个函数。
+pub fn build_router<S>(
+    routes: &[RouteDef],
+    scopes: &[ScopeDef],
+    endpoints: Vec<MethodRouter<S>>,
+    load: &dyn Fn(usize) -> Middleware<S>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let loaded = LoadedScopes::load(scopes, load);
+    build_router_with(routes, scopes, endpoints, loaded, load)
+}
+
+/// Build a router from scopes loaded with [`LoadedScopes::load`]
+///
+/// / 使用通过 [`LoadedScopes::load`] 加载的作用域构建路由器
+///
+/// `load` is only called again for a scope needed in a second position, see
+/// [Layer Instances](self#layer-instances).
+///
+/// 只有需要出现在第二个位置的作用域才会再次调用 `load`，参见[中间件层实例](self#layer-instances)。
+pub fn build_router_with<S>(
+    routes: &[RouteDef],
+    scopes: &[ScopeDef],
+    endpoints: Vec<MethodRouter<S>>,
+    loaded: LoadedScopes<S>,
+    load: &dyn Fn(usize) -> Middleware<S>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let LoadedScopes {
+        middleware: mut loaded,
+        probes,
+    } = loaded;
+
+    #[cfg(feature = "metrics")]
+    for def in routes {
+        crate::metrics::register_route(def.method, def.path);
+    }
+
+    // Scope settings travel with each route, inside all middleware layers
+    // 作用域设置随每个路由附加，位于所有中间件层之内
+    let entries = routes
+        .iter()
+        .zip(endpoints)
+        .map(|(def, mut endpoint)| {
+            let limit = resolve_nearest(scopes, &probes, def.scope, |p| p.body_limit.as_ref());
+            if let Some(limit) = limit {
+                endpoint = endpoint.layer(axum::Extension(BodyLimit(limit)));
+            }
+            let policy = resolve_nearest(scopes, &probes, def.scope, |p| p.cache_control.as_ref());
+            if let Some(policy) = policy {
+                endpoint = endpoint.layer(axum::Extension(ScopeCacheControl(policy)));
+            }
+            let chain = resolve_chain(scopes, &probes, def.scope);
+            (chain, Router::new().route(def.path, endpoint))
+        })
+        .collect();
+
+    // The loaded instance serves the first application; only a scope needed
+    // in a second position is loaded again
+    // 已加载的实例用于第一次应用；只有需要出现在第二个位置的作用域才会再次加载
+    let mut instantiate = |i: usize| loaded[i].take().unwrap_or_else(|| load(i));
+    wrap_scopes(entries, &mut instantiate)
+}
+
+/// A route's router and the scopes still to wrap it, outermost first
+/// / 路由的路由器及仍需包裹它的作用域，最外层在前
+type ChainedRoute<S> = (Vec<usize>, Router<S>);
+
+/// Apply each chain head once around every route that shares it, then recurse
+/// into the rest of the chains
+///
+/// / 每个链头只应用一次，包裹共享它的所有路由，然后递归处理链的剩余部分
+fn wrap_scopes<S>(
+    entries: Vec<ChainedRoute<S>>,
+    instantiate: &mut dyn FnMut(usize) -> Middleware<S>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Group by outermost scope, keeping first-seen order
+    // 按最外层作用域分组，保持首次出现的顺序
+    let mut router = Router::new();
+    let mut groups: Vec<(usize, Vec<ChainedRoute<S>>)> = Vec::new();
+    for (mut chain, route) in entries {
+        if chain.is_empty() {
+            router = router.merge(route);
+            continue;
+        }
+        let head = chain.remove(0);
+        match groups.iter_mut().find(|(h, _)| *h == head) {
+            Some((_, members)) => members.push((chain, route)),
+            None => groups.push((head, vec![(chain, route)])),
+        }
+    }
+    for (head, members) in groups {
+        let inner = wrap_scopes(members, instantiate);
+        router = router.merge(instantiate(head).apply(inner));
+    }
+    router
+}
+
+/// Get the middleware chain applied to a route, outermost first
+///
+/// / 获取作用于某个路由的中间件链，最外层在前
+///
+/// `path` may be the route pattern (`/users/{id}`) or a concrete request path
+/// (`/users/42`). A concrete path picks the route Axum would: static segments
+/// win over `{param}`, which wins over `{*rest}`, so `/users/me` reports the
+/// chain of `/users/me` even when `/users/{id}` is listed first. Returns `None`
+/// if no route matches. Each entry is the middleware's
+/// [`name`](Middleware::name), or its scope path when unnamed.
+///
+/// `path` 可以是路由模式（`/users/{id}`）或具体请求路径（`/users/42`）。具体路径会选中
+/// Axum 实际匹配的路由：静态段优先于 `{param}`，`{param}` 优先于 `{*rest}`，因此即使
+/// `/users/{id}` 排在前面，`/users/me` 也会报告 `/users/me` 的链。没有匹配路由时返回 `None`。
+/// 每个条目是中间件的 [`name`](Middleware::name)，未命名时为其作用域路径。
+///
+/// Generated routers expose this as `routes::middleware_chain(method, path)`.
+///
+/// 生成的路由模块将其暴露为 `routes::middleware_chain(method, path)`。
+pub fn middleware_chain<S>(
+    routes: &[RouteDef],
+    scopes: &[ScopeDef],
+    load: &dyn Fn(usize) -> Middleware<S>,
+    method: &str,
+    path: &str,
+) -> Option<Vec<String>> {
+    LoadedScopes::load(scopes, load).chain(routes, scopes, method, path)
+}
+
+/// Find the route for a method and a pattern or concrete path
+///
+/// / 根据方法和路由模式或具体路径查找路由
+fn find_route<'a>(routes: &'a [RouteDef], method: &str, path: &str) -> Option<&'a RouteDef> {
+    let candidates = || {
+        routes
+            .iter()
+            .filter(move |r| r.method.eq_ignore_ascii_case(method))
+    };
+    candidates().find(|r| r.path == path).or_else(|| {
+        candidates()
+            .filter_map(|r| Some((match_rank(r.path, path)?, r)))
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, r)| r)
+    })
+}
+
+/// Match a concrete path against an Axum route pattern, ranking the match
+///
+/// / 将具体路径与 Axum 路由模式匹配，并给出匹配的排名
+///
+/// Returns the kind of each matched segment (`0` static, `1` `{param}`, `2`
+/// `{*rest}`). Comparing ranks segment by segment gives Axum's precedence.
+///
+/// 返回每个匹配段的类型（`0` 静态、`1` `{param}`、`2` `{*rest}`）。
+/// 逐段比较排名即得到 Axum 的优先级。
+fn match_rank(pattern: &str, path: &str) -> Option<Vec<u8>> {
+    let mut pattern_segs = pattern.trim_matches('/').split('/');
+    let mut path_segs = path.trim_matches('/').split('/');
+    let mut rank = Vec::new();
+    loop {
+        match (pattern_segs.next(), path_segs.next()) {
+            (None, None) => return Some(rank),
+            (Some(p), rest) if p.starts_with("{*") => {
+                rank.push(2);
+                return rest.is_some_and(|s| !s.is_empty()).then_some(rank);
+            }
+            (Some(p), Some(s)) if p.starts_with('{') && p.ends_with('}') => {
+                if s.is_empty() {
+                    return None;
+                }
+                rank.push(1);
+            }
+            (Some(p), Some(s)) if p == s => rank.push(0),
+            _ => return None,
+        }
+    }
+}
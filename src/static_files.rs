@@ -0,0 +1,394 @@
+//! Static files from a `public/` directory
+//!
+//! / 来自 `public/` 目录的静态文件
+//!
+//! `generate_routes!(public = "public")` serves the files of that directory
+//! next to the file routes. Route files take precedence: a file is only
+//! served when no route matches the request path.
+//!
+//! `generate_routes!(public = "public")` 会在文件路由之外提供该目录中的文件。
+//! 路由文件优先：只有当没有路由匹配请求路径时才会提供文件。
+//!
+//! Files are served with their MIME type, `Last-Modified` and a weak `ETag`
+//! (`304 Not Modified` is handled for both), `Range` requests, precompressed
+//! `.br` / `.gz` siblings when the client accepts them, and `index.html` for
+//! directories.
+//!
+//! 文件以对应的 MIME 类型、`Last-Modified` 和弱 `ETag` 提供（两者都支持
+//! `304 Not Modified`），支持 `Range` 请求，在客户端接受时提供预压缩的 `.br` / `.gz`
+//! 文件，并为目录提供 `index.html`。
+//!
+//! The generated router picks the directory with [`PublicDir::locate`] when it
+//! is created:
+//!
+//! 生成的路由器在创建时通过 [`PublicDir::locate`] 选择目录：
+//!
+//! 1. the `ASTREA_PUBLIC_DIR` environment variable ([`PUBLIC_DIR_ENV`]), if set;
+//!    如果设置了 `ASTREA_PUBLIC_DIR` 环境变量（[`PUBLIC_DIR_ENV`]），使用它；
+//! 2. the directory the macro scanned, `CARGO_MANIFEST_DIR/<public>`, if it
+//!    still exists — so `cargo run` works from any working directory;
+//!    宏扫描过的目录 `CARGO_MANIFEST_DIR/<public>`（如果仍然存在）——因此 `cargo run`
+//!    可以在任意工作目录下运行；
+//! 3. otherwise `<public>` relative to the working directory, for binaries
+//!    deployed away from the build machine (e.g. a Docker multi-stage build
+//!    that copies `public/` next to the binary's working directory).
+//!    否则使用相对于工作目录的 `<public>`，用于部署到构建机器之外的二进制文件
+//!    （例如将 `public/` 复制到二进制工作目录下的 Docker 多阶段构建）。
+//!
+//! Scoped `_middleware.rs` layers do not apply to static files; layers added
+//! to the whole router do.
+//!
+//! 作用域 `_middleware.rs` 的中间件层不作用于静态文件；作用于整个路由器的中间件层会作用于静态文件。
+//!
+//! With `generate_routes!(public = "public", embed = true)` the files are
+//! embedded into the binary instead (see [`EmbeddedDir`]).
+//!
+//! 使用 `generate_routes!(public = "public", embed = true)` 时，文件会被嵌入到二进制文件中
+//! （参见 [`EmbeddedDir`]）。
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use axum::response::Response as AxumResponse;
+use std::convert::Infallible;
+use std::ffi::OsStr;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::ServiceExt;
+use tower_http::services::ServeDir;
+
+use crate::response::conditional::Conditions;
+use crate::response::defaults;
+use crate::response::file::Streaming;
+use crate::response::{CacheControl, ETag, Response};
+
+/// Tower service serving a `public/` directory
+///
+/// / 提供 `public/` 目录的 Tower 服务
+///
+/// Installed as the router fallback by `generate_routes!(public = "...")`; it
+/// can also be mounted by hand.
+///
+/// 由 `generate_routes!(public = "...")` 安装为路由器的 fallback；也可以手动挂载。
+///
+/// # Example
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let app = routes::create_router().nest_service("/assets", PublicDir::new("assets"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PublicDir {
+    serve: ServeDir,
+}
+
+/// Environment variable that overrides the directory of `generate_routes!(public = ...)`
+/// / 覆盖 `generate_routes!(public = ...)` 目录的环境变量
+pub const PUBLIC_DIR_ENV: &str = "ASTREA_PUBLIC_DIR";
+
+impl PublicDir {
+    /// Serve the files under `path`
+    /// / 提供 `path` 下的文件
+    #[must_use]
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            serve: ServeDir::new(path)
+                .precompressed_br()
+                .precompressed_gzip()
+                .append_index_html_on_directories(true),
+        }
+    }
+
+    /// Serve the directory chosen by [`public_dir_path`], reading [`PUBLIC_DIR_ENV`]
+    ///
+    /// / 提供由 [`public_dir_path`] 选择的目录，并读取 [`PUBLIC_DIR_ENV`]
+    ///
+    /// Called by the generated router with the configured `public` path and the
+    /// absolute path the macro scanned.
+    ///
+    /// 由生成的路由器使用配置的 `public` 路径和宏扫描过的绝对路径调用。
+    #[must_use]
+    pub fn locate(relative: &str, scanned: &str) -> Self {
+        let env = std::env::var_os(PUBLIC_DIR_ENV);
+        Self::new(public_dir_path(env.as_deref(), relative, scanned))
+    }
+}
+
+/// Pick the directory served for `generate_routes!(public = ...)`
+///
+/// / 选择 `generate_routes!(public = ...)` 提供的目录
+///
+/// A non-empty `env` override wins, then `scanned` if it is a directory, then
+/// `relative` against the working directory. See the [module docs](self).
+///
+/// 非空的 `env` 覆盖优先，其次是 `scanned`（如果是目录），最后是相对于工作目录的
+/// `relative`。参见[模块文档](self)。
+#[must_use]
+pub fn public_dir_path(env: Option<&OsStr>, relative: &str, scanned: &str) -> PathBuf {
+    match env {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ if Path::new(scanned).is_dir() => PathBuf::from(scanned),
+        _ => PathBuf::from(relative),
+    }
+}
+
+impl tower::Service<Request> for PublicDir {
+    type Response = AxumResponse;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<AxumResponse, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let serve = self.serve.clone();
+        Box::pin(async move {
+            let conditions = Conditions::from_request(request.method(), request.headers());
+            let response = serve.oneshot(request).await?;
+            let (mut parts, body) = response.into_parts();
+            defaults::apply(&mut parts.headers);
+
+            if parts.status == StatusCode::OK
+                && let Some(etag) = weak_etag(&parts.headers)
+            {
+                parts.headers.insert(header::ETAG, etag);
+                if conditions.not_modified(&parts.headers) {
+                    parts.status = StatusCode::NOT_MODIFIED;
+                    parts.headers.remove(header::CONTENT_TYPE);
+                    parts.headers.remove(header::CONTENT_LENGTH);
+                    return Ok(AxumResponse::from_parts(parts, Body::empty()));
+                }
+            }
+            Ok(AxumResponse::from_parts(parts, Body::new(body)))
+        })
+    }
+}
+
+/// Weak `ETag` derived from the file's size and modification time
+///
+/// / 由文件大小和修改时间生成的弱 `ETag`
+///
+/// A precompressed variant has its own size, so each encoding gets its own tag.
+///
+/// 预压缩变体的大小不同，因此每种编码都有各自的标签。
+fn weak_etag(headers: &HeaderMap) -> Option<HeaderValue> {
+    let length: u64 = headers
+        .get(header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()?;
+    let modified = headers
+        .get(header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| httpdate::parse_http_date(v).ok())
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let seconds = modified
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    HeaderValue::try_from(format!("W/\"{length:x}-{seconds:x}\"")).ok()
+}
+
+// ============================================================================
+// 嵌入的静态文件
+// ============================================================================
+
+/// A file embedded by `generate_routes!(public = "...", embed = true)`
+///
+/// / 由 `generate_routes!(public = "...", embed = true)` 嵌入的文件
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedAsset {
+    /// URL path, e.g. `/css/app.css`
+    /// / URL 路径，如 `/css/app.css`
+    pub path: &'static str,
+    /// File contents / 文件内容
+    pub body: &'static [u8],
+    /// Content hash computed at compile time, used as the strong `ETag`
+    /// / 编译时计算的内容哈希，用作强 `ETag`
+    pub etag: &'static str,
+    /// Precompressed `.br` sibling / 预压缩的 `.br` 副本
+    pub br: Option<&'static [u8]>,
+    /// Precompressed `.gz` sibling / 预压缩的 `.gz` 副本
+    pub gzip: Option<&'static [u8]>,
+    /// The file name carries a content hash, so it is cached forever
+    /// / 文件名包含内容哈希，因此可以永久缓存
+    pub immutable: bool,
+}
+
+impl EmbeddedAsset {
+    /// Build the response for this file, picking an encoding the client accepts
+    /// / 为此文件构建响应，选择客户端接受的编码
+    fn response(&self, request_headers: &HeaderMap) -> Response {
+        let mime = mime_guess::from_path(self.path).first_raw();
+        let accepted = |coding: &str| accepts_encoding(request_headers, coding);
+        let (body, encoding) = match (self.br, self.gzip) {
+            (Some(br), _) if accepted("br") => (br, Some("br")),
+            (_, Some(gzip)) if accepted("gzip") => (gzip, Some("gzip")),
+            _ => (self.body, None),
+        };
+
+        let mut response = Response::new().content_type(mime.unwrap_or("application/octet-stream"));
+        response.streaming = Some(Streaming::from_static(body));
+        response = match encoding {
+            Some(encoding) => response
+                .header("content-encoding", encoding)
+                .with_etag(&ETag::strong(format!("{}-{encoding}", self.etag))),
+            None => response.with_etag(&ETag::strong(self.etag)),
+        };
+        if self.br.is_some() || self.gzip.is_some() {
+            response = response.vary("Accept-Encoding");
+        }
+        if self.immutable {
+            response = response
+                .cache_control(CacheControl::new().public().max_age(31_536_000).immutable());
+        }
+        response
+    }
+}
+
+/// Whether `Accept-Encoding` allows a content coding (`q=0` refuses it)
+/// / `Accept-Encoding` 是否允许某种内容编码（`q=0` 表示拒绝）
+fn accepts_encoding(headers: &HeaderMap, coding: &str) -> bool {
+    headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|item| {
+            let mut parts = item.split(';').map(str::trim);
+            parts
+                .next()
+                .is_some_and(|name| name.eq_ignore_ascii_case(coding))
+                && parts.all(|param| {
+                    param
+                        .strip_prefix("q=")
+                        .is_none_or(|q| q.parse::<f32>().is_ok_and(|q| q > 0.0))
+                })
+        })
+}
+
+/// Tower service serving files embedded at compile time
+///
+/// / 提供编译时嵌入文件的 Tower 服务
+///
+/// Installed as the router fallback by `generate_routes!(public = "...",
+/// embed = true)`. Files are served through [`Response`] with a strong `ETag`
+/// computed at build time; files with a content hash in their name (such as
+/// `app.3f9a1c2b.js`) also get `Cache-Control: public, immutable, max-age=31536000`.
+///
+/// 由 `generate_routes!(public = "...", embed = true)` 安装为路由器的 fallback。
+/// 文件通过 [`Response`] 提供，带有构建时计算的强 `ETag`；文件名包含内容哈希的文件
+/// （如 `app.3f9a1c2b.js`）还会带有 `Cache-Control: public, immutable, max-age=31536000`。
+///
+/// Editing an embedded file recompiles the crate. To also pick up added or
+/// removed files, add a build script:
+///
+/// 修改嵌入的文件会触发重新编译。若还要感知新增或删除的文件，请添加构建脚本：
+///
+/// ```rust,ignore
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rerun-if-changed=public");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedDir {
+    assets: &'static [EmbeddedAsset],
+}
+
+impl EmbeddedDir {
+    /// Serve a table of embedded files
+    /// / 提供嵌入文件表
+    #[must_use]
+    pub fn new(assets: &'static [EmbeddedAsset]) -> Self {
+        Self { assets }
+    }
+
+    fn find(&self, path: &str) -> Option<&'static EmbeddedAsset> {
+        self.assets.iter().find(|asset| asset.path == path)
+    }
+
+    fn respond(&self, method: &Method, path: &str, headers: &HeaderMap) -> AxumResponse {
+        if method != Method::GET && method != Method::HEAD {
+            let mut response = AxumResponse::new(Body::empty());
+            *response.status_mut() = StatusCode::METHOD_NOT_ALLOWED;
+            response
+                .headers_mut()
+                .insert(header::ALLOW, HeaderValue::from_static("GET, HEAD"));
+            return response;
+        }
+
+        let path = percent_decode(path);
+        let asset = if path.ends_with('/') {
+            self.find(&format!("{path}index.html"))
+        } else {
+            self.find(&path)
+        };
+        if let Some(asset) = asset {
+            return Conditions::from_request(method, headers)
+                .apply(asset.response(headers))
+                .into_axum_response();
+        }
+
+        // `/docs` → `/docs/` when it has an index file, like `ServeDir`
+        // 存在索引文件时将 `/docs` 重定向到 `/docs/`，与 `ServeDir` 相同
+        let mut response = AxumResponse::new(Body::empty());
+        if self.find(&format!("{path}/index.html")).is_some()
+            && let Ok(location) = HeaderValue::try_from(format!("{path}/"))
+        {
+            *response.status_mut() = StatusCode::TEMPORARY_REDIRECT;
+            response.headers_mut().insert(header::LOCATION, location);
+        } else {
+            *response.status_mut() = StatusCode::NOT_FOUND;
+        }
+        response
+    }
+}
+
+impl tower::Service<Request> for EmbeddedDir {
+    type Response = AxumResponse;
+    type Error = Infallible;
+    type Future = std::future::Ready<Result<AxumResponse, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        std::future::ready(Ok(self.respond(
+            request.method(),
+            request.uri().path(),
+            request.headers(),
+        )))
+    }
+}
+
+/// Decode `%XX` escapes in a request path
+/// / 解码请求路径中的 `%XX` 转义
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
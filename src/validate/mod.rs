@@ -0,0 +1,363 @@
+//! Declarative request validation
+//!
+//! / 声明式请求验证
+//!
+//! Derive [`Validate`] on a request type and describe its rules with
+//! `#[validate(...)]` field attributes. The `get_valid_*` extractors
+//! deserialize the request and then validate it; failures become a
+//! `422 Unprocessable Entity` response listing every failing field.
+//!
+//! 在请求类型上派生 [`Validate`]，并用 `#[validate(...)]` 字段属性描述规则。
+//! `get_valid_*` 提取函数先反序列化请求再进行验证；验证失败会生成
+//! `422 Unprocessable Entity` 响应，列出每个未通过的字段。
+//!
+//! # Rules
+//!
+//! # 规则
+//!
+//! - `length(min = 1, max = 32)` — characters of a string, or items of a collection
+//!   字符串的字符数，或集合的元素数
+//! - `range(min = 0, max = 150)` — numeric bounds (inclusive)
+//!   数值范围（包含边界）
+//! - `regex = "^[a-z]+$"` — the string must match the pattern; an invalid
+//!   pattern is a compile error
+//!   字符串必须匹配该模式；无效的模式会导致编译错误
+//! - `email` — the string must look like an email address
+//!   字符串必须是邮箱地址格式
+//! - `nested` — validate a field that itself implements [`Validate`]
+//!   验证本身实现了 [`Validate`] 的字段
+//!
+//! Rules on an `Option<_>` field only apply when the value is present.
+//! Error paths use the serialized field names (`#[serde(rename)]` and
+//! `#[serde(rename_all)]` are honoured), joined like `address.city` or
+//! `items[0].name`.
+//!
+//! `Option<_>` 字段上的规则仅在值存在时生效。错误路径使用序列化后的字段名
+//! （支持 `#[serde(rename)]` 和 `#[serde(rename_all)]`），
+//! 以 `address.city` 或 `items[0].name` 的形式连接。
+//!
+//! # Example
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! use astrea::prelude::*;
+//!
+//! #[derive(Deserialize, Validate)]
+//! struct Signup {
+//!     #[validate(length(min = 3, max = 20), regex = "^[a-z0-9_]+$")]
+//!     username: String,
+//!     #[validate(email)]
+//!     email: String,
+//!     #[validate(range(min = 13))]
+//!     age: u32,
+//!     #[validate(nested)]
+//!     address: Option<Address>,
+//! }
+//!
+//! #[route]
+//! pub async fn handler(event: Event) -> Result<Response> {
+//!     let signup: Signup = get_valid_body(&event)?;
+//!     json(json!({ "username": signup.username }))
+//! }
+//! ```
+//!
+//! Patterns are compiled by the derive, so a typo fails the build instead of
+//! the request:
+//!
+//! 模式由派生宏编译，因此拼写错误会导致构建失败，而不是请求失败：
+//!
+//! ```compile_fail
+//! use astrea::prelude::*;
+//!
+//! #[derive(Validate)]
+//! struct Tag {
+//!     #[validate(regex = "^[a-z+$")]
+//!     name: String,
+//! }
+//! ```
+//!
+//! ```
+//! use astrea::prelude::*;
+//!
+//! #[derive(Validate)]
+//! struct Tag {
+//!     #[validate(regex = "^[a-z]+$")]
+//!     name: String,
+//! }
+//! ```
+//!
+//! A failing request produces:
+//!
+//! 验证失败的请求会得到：
+//!
+//! ```json
+//! {
+//!   "error": "email: must be a valid email address",
+//!   "status": 422,
+//!   "errors": [
+//!     { "path": "email", "code": "email", "message": "must be a valid email address" }
+//!   ]
+//! }
+//! ```
+
+pub mod rules;
+
+use std::fmt;
+
+pub use astrea_macro::Validate;
+
+/// A single failed validation rule
+///
+/// / 单条未通过的验证规则
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FieldError {
+    /// Path of the offending field, e.g. `email` or `items[0].name`
+    /// / 出错字段的路径，如 `email` 或 `items[0].name`
+    pub path: String,
+    /// Machine-readable code, e.g. `length`, `range`, `email`
+    /// / 机器可读的错误码，如 `length`、`range`、`email`
+    pub code: String,
+    /// Human-readable message, omitted from JSON when empty
+    /// / 人类可读的消息，为空时不序列化到 JSON
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub message: String,
+}
+
+/// Every validation failure collected from a value
+///
+/// / 从一个值收集到的所有验证失败
+///
+/// Converts into [`RouteError::ValidationFailed`](crate::RouteError::ValidationFailed)
+/// with `?`.
+///
+/// 可通过 `?` 转换为 [`RouteError::ValidationFailed`](crate::RouteError::ValidationFailed)。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    /// Create an empty error list
+    /// / 创建空的错误列表
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failure
+    ///
+    /// / 记录一条失败
+    ///
+    /// # Example
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// let mut errors = ValidationErrors::new();
+    /// if self.password != self.confirm {
+    ///     errors.add("confirm", "mismatch", "must match password");
+    /// }
+    /// errors.into_result()
+    /// ```
+    pub fn add(
+        &mut self,
+        path: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.errors.push(FieldError {
+            path: path.into(),
+            code: code.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Merge the errors of a nested value, prefixing their paths with `prefix`
+    ///
+    /// / 合并嵌套值的错误，并为其路径加上 `prefix` 前缀
+    pub fn merge(&mut self, prefix: &str, result: Result<(), ValidationErrors>) {
+        if let Err(nested) = result {
+            self.errors
+                .extend(nested.errors.into_iter().map(|e| FieldError {
+                    path: join_path(prefix, &e.path),
+                    ..e
+                }));
+        }
+    }
+
+    /// Whether no failures were recorded
+    /// / 是否没有记录任何失败
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The recorded failures, in field order
+    /// / 已记录的失败，按字段顺序排列
+    #[must_use]
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// `Ok(())` if empty, otherwise `Err(self)`
+    /// / 为空时返回 `Ok(())`，否则返回 `Err(self)`
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns `self` if any failure was recorded.
+    ///
+    /// 如果记录了任何失败，返回 `self`。
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, e) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            if e.path.is_empty() {
+                f.write_str(&e.message)?;
+            } else {
+                write!(f, "{}: {}", e.path, e.message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl IntoIterator for ValidationErrors {
+    type Item = FieldError;
+    type IntoIter = std::vec::IntoIter<FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+/// A declarative rule attached to a field, as reported by [`Validate::constraints`]
+///
+/// / 附加到字段上的声明式规则，由 [`Validate::constraints`] 报告
+///
+/// Used to reflect the rules into the generated OpenAPI schemas.
+///
+/// 用于将规则反映到生成的 OpenAPI 模式中。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    /// `length(min, max)`
+    Length {
+        /// Minimum length / 最小长度
+        min: Option<usize>,
+        /// Maximum length / 最大长度
+        max: Option<usize>,
+    },
+    /// `range(min, max)`
+    Range {
+        /// Minimum value / 最小值
+        min: Option<f64>,
+        /// Maximum value / 最大值
+        max: Option<f64>,
+    },
+    /// `regex = "..."`
+    Regex(&'static str),
+    /// `email`
+    Email,
+}
+
+/// A type whose values can be validated
+///
+/// / 其值可被验证的类型
+///
+/// Usually derived with `#[derive(Validate)]`; implement it by hand for
+/// cross-field rules.
+///
+/// 通常通过 `#[derive(Validate)]` 派生；需要跨字段规则时可以手动实现。
+pub trait Validate {
+    /// Check every rule, collecting all failures
+    ///
+    /// / 检查所有规则并收集全部失败
+    ///
+    /// # Errors
+    ///
+    /// # 错误
+    ///
+    /// Returns every failed rule.
+    ///
+    /// 返回所有未通过的规则。
+    fn validate(&self) -> Result<(), ValidationErrors>;
+
+    /// The declared rules as `(path, constraint)` pairs
+    ///
+    /// / 以 `(路径, 约束)` 对的形式返回声明的规则
+    ///
+    /// Paths of nested fields are joined with `.`; items of a collection
+    /// use `[]`, e.g. `items[].name`.
+    ///
+    /// 嵌套字段的路径以 `.` 连接；集合元素使用 `[]`，如 `items[].name`。
+    fn constraints() -> Vec<(String, Constraint)>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.as_ref().map_or(Ok(()), Validate::validate)
+    }
+
+    fn constraints() -> Vec<(String, Constraint)> {
+        T::constraints()
+    }
+}
+
+impl<T: Validate> Validate for Box<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        (**self).validate()
+    }
+
+    fn constraints() -> Vec<(String, Constraint)> {
+        T::constraints()
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        for (i, item) in self.iter().enumerate() {
+            errors.merge(&format!("[{i}]"), item.validate());
+        }
+        errors.into_result()
+    }
+
+    fn constraints() -> Vec<(String, Constraint)> {
+        T::constraints()
+            .into_iter()
+            .map(|(path, c)| (join_path("[]", &path), c))
+            .collect()
+    }
+}
+
+/// Join a field path prefix and a nested path (`a` + `b` → `a.b`, `a` + `[0]` → `a[0]`)
+///
+/// / 连接字段路径前缀和嵌套路径（`a` + `b` → `a.b`，`a` + `[0]` → `a[0]`）
+#[must_use]
+pub fn join_path(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        path.to_string()
+    } else if path.is_empty() {
+        prefix.to_string()
+    } else if path.starts_with('[') {
+        format!("{prefix}{path}")
+    } else {
+        format!("{prefix}.{path}")
+    }
+}
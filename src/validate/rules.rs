@@ -0,0 +1,235 @@
+//! Rule checks used by `#[derive(Validate)]`
+//!
+//! / `#[derive(Validate)]` 使用的规则检查
+//!
+//! Each check records a [`FieldError`](super::FieldError) when the value
+//! breaks the rule. `Option` values that are `None` always pass.
+//!
+//! 每个检查在值违反规则时记录一条 [`FieldError`](super::FieldError)。
+//! 值为 `None` 的 `Option` 总是通过。
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+use super::{Validate, ValidationErrors};
+
+/// Lazily compiled pattern for a `regex = "..."` rule
+/// / `regex = "..."` 规则的延迟编译模式
+pub type RegexCell = OnceLock<regex::Regex>;
+
+/// Values with a length: strings count characters, collections count items
+///
+/// / 具有长度的值：字符串按字符计数，集合按元素计数
+pub trait HasLength {
+    /// Length of the value, or `None` to skip the check
+    /// / 值的长度，返回 `None` 表示跳过检查
+    fn length(&self) -> Option<usize>;
+}
+
+impl HasLength for str {
+    fn length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl HasLength for String {
+    fn length(&self) -> Option<usize> {
+        self.as_str().length()
+    }
+}
+
+impl<T> HasLength for [T] {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> HasLength for Vec<T> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V, S> HasLength for HashMap<K, V, S> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V> HasLength for BTreeMap<K, V> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: HasLength> HasLength for Option<T> {
+    fn length(&self) -> Option<usize> {
+        self.as_ref().and_then(HasLength::length)
+    }
+}
+
+/// Numeric values checked by `range`
+///
+/// / 由 `range` 检查的数值
+pub trait AsNumber {
+    /// The value as `f64`, or `None` to skip the check
+    /// / 转换为 `f64` 的值，返回 `None` 表示跳过检查
+    fn as_number(&self) -> Option<f64>;
+}
+
+macro_rules! as_number {
+    ($($ty:ty),*) => {
+        $(
+            impl AsNumber for $ty {
+                #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+                fn as_number(&self) -> Option<f64> {
+                    Some(*self as f64)
+                }
+            }
+        )*
+    };
+}
+
+as_number!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+impl<T: AsNumber> AsNumber for Option<T> {
+    fn as_number(&self) -> Option<f64> {
+        self.as_ref().and_then(AsNumber::as_number)
+    }
+}
+
+/// String values checked by `email` and `regex`
+///
+/// / 由 `email` 和 `regex` 检查的字符串值
+pub trait AsText {
+    /// The value as `&str`, or `None` to skip the check
+    /// / 转换为 `&str` 的值，返回 `None` 表示跳过检查
+    fn as_text(&self) -> Option<&str>;
+}
+
+impl AsText for str {
+    fn as_text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl AsText for String {
+    fn as_text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: AsText> AsText for Option<T> {
+    fn as_text(&self) -> Option<&str> {
+        self.as_ref().and_then(AsText::as_text)
+    }
+}
+
+/// Check `length(min, max)`
+/// / 检查 `length(min, max)`
+pub fn length<T: HasLength + ?Sized>(
+    errors: &mut ValidationErrors,
+    path: &str,
+    value: &T,
+    min: Option<usize>,
+    max: Option<usize>,
+) {
+    let Some(len) = value.length() else {
+        return;
+    };
+    if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+        errors.add(path, "length", bounds_message("length", min, max));
+    }
+}
+
+/// Check `range(min, max)`
+/// / 检查 `range(min, max)`
+pub fn range<T: AsNumber + ?Sized>(
+    errors: &mut ValidationErrors,
+    path: &str,
+    value: &T,
+    min: Option<f64>,
+    max: Option<f64>,
+) {
+    let Some(n) = value.as_number() else {
+        return;
+    };
+    if min.is_some_and(|min| n < min) || max.is_some_and(|max| n > max) {
+        errors.add(path, "range", bounds_message("value", min, max));
+    }
+}
+
+/// Check `email`
+/// / 检查 `email`
+pub fn email<T: AsText + ?Sized>(errors: &mut ValidationErrors, path: &str, value: &T) {
+    let Some(text) = value.as_text() else {
+        return;
+    };
+    if !is_email(text) {
+        errors.add(path, "email", "must be a valid email address");
+    }
+}
+
+/// Check `regex = "..."`, compiling the pattern on first use
+///
+/// / 检查 `regex = "..."`，首次使用时编译模式
+///
+/// # Panics
+///
+/// # 恐慌
+///
+/// Panics if `pattern` is not a valid regular expression. `#[derive(Validate)]`
+/// rejects such patterns at compile time, so only hand-written calls can panic.
+///
+/// 如果 `pattern` 不是有效的正则表达式，会发生 panic。`#[derive(Validate)]` 会在编译时拒绝
+/// 这样的模式，因此只有手写的调用才可能 panic。
+pub fn regex<T: AsText + ?Sized>(
+    errors: &mut ValidationErrors,
+    path: &str,
+    value: &T,
+    cell: &RegexCell,
+    pattern: &str,
+) {
+    let Some(text) = value.as_text() else {
+        return;
+    };
+    let re = cell.get_or_init(|| {
+        regex::Regex::new(pattern)
+            .unwrap_or_else(|e| panic!("invalid #[validate(regex)] pattern `{pattern}`: {e}"))
+    });
+    if !re.is_match(text) {
+        errors.add(path, "regex", format!("must match the pattern `{pattern}`"));
+    }
+}
+
+/// Check `nested`
+/// / 检查 `nested`
+pub fn nested<T: Validate + ?Sized>(errors: &mut ValidationErrors, path: &str, value: &T) {
+    errors.merge(path, value.validate());
+}
+
+fn bounds_message<N: std::fmt::Display>(what: &str, min: Option<N>, max: Option<N>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{what} must be between {min} and {max}"),
+        (Some(min), None) => format!("{what} must be at least {min}"),
+        (None, Some(max)) => format!("{what} must be at most {max}"),
+        (None, None) => format!("{what} is out of bounds"),
+    }
+}
+
+/// A pragmatic email check: `local@domain.tld`, no whitespace
+/// / 实用的邮箱检查：`local@domain.tld`，不含空白字符
+fn is_email(text: &str) -> bool {
+    let Some((local, domain)) = text.rsplit_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !text.chars().any(char::is_whitespace)
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain.contains('.')
+        && !domain.contains('@')
+        && !domain.contains("..")
+}
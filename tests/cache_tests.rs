@@ -0,0 +1,216 @@
+//! 响应缓存测试 - 使用 `tests/fixtures/cache_routes` 测试进程内响应缓存
+//!
+//! Response cache tests against the `tests/fixtures/cache_routes` tree.
+
+use astrea::axum::{Router, body::Body, extract::Request};
+use astrea::prelude::*;
+use astrea::tower::ServiceExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+mod routes {
+    astrea::generate_routes!("tests/fixtures/cache_routes");
+}
+
+/// Global call counter, so responses produced by different handler calls differ
+pub fn next_call() -> usize {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    CALLS.fetch_add(1, Ordering::Relaxed)
+}
+
+fn app() -> Router {
+    routes::create_router::<()>().layer(astrea::cache::layer().query_keys(["page"]))
+}
+
+async fn send(app: &Router, request: Request) -> (StatusCode, Option<String>, String) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let cache = response
+        .headers()
+        .get("x-cache")
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, cache, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn get(app: &Router, path: &str) -> (Option<String>, String) {
+    let (status, cache, body) = send(app, Request::get(path).body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    (cache, body)
+}
+
+// ============================================================================
+// 命中与缓存键
+// ============================================================================
+
+#[tokio::test]
+async fn test_cache_hit_and_miss() {
+    let app = app();
+    let (cache, first) = get(&app, "/users/1").await;
+    assert_eq!(cache.as_deref(), Some("MISS"));
+    let (cache, second) = get(&app, "/users/1").await;
+    assert_eq!(cache.as_deref(), Some("HIT"));
+    assert_eq!(first, second);
+
+    // Different path parameters are different entries
+    let (cache, other) = get(&app, "/users/2").await;
+    assert_eq!(cache.as_deref(), Some("MISS"));
+    assert!(other.starts_with("2:"));
+}
+
+#[tokio::test]
+async fn test_cache_key_uses_selected_query_keys() {
+    let app = app();
+    let (_, first) = get(&app, "/users/10?page=1&utm=a").await;
+    let (cache, same) = get(&app, "/users/10?utm=b&page=1").await;
+    assert_eq!(cache.as_deref(), Some("HIT"));
+    assert_eq!(first, same);
+
+    let (cache, _) = get(&app, "/users/10?page=2").await;
+    assert_eq!(cache.as_deref(), Some("MISS"));
+}
+
+#[tokio::test]
+async fn test_cache_respects_vary() {
+    let app = app();
+    let request = |lang: &str| {
+        Request::get("/greeting")
+            .header("accept-language", lang)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let (_, _, en) = send(&app, request("en")).await;
+    let (_, _, fr) = send(&app, request("fr")).await;
+    assert!(en.starts_with("en:"));
+    assert!(fr.starts_with("fr:"));
+
+    let (_, cache, again) = send(&app, request("en")).await;
+    assert_eq!(cache.as_deref(), Some("HIT"));
+    assert_eq!(again, en);
+}
+
+#[tokio::test]
+async fn test_cache_skips_no_store() {
+    let app = app();
+    let (cache, first) = get(&app, "/private").await;
+    assert_eq!(cache, None);
+    let (_, second) = get(&app, "/private").await;
+    assert_ne!(first, second);
+}
+
+#[tokio::test]
+async fn test_cache_hit_honors_if_none_match() {
+    let app = app();
+    get(&app, "/tagged").await;
+    let request = Request::get("/tagged")
+        .header("if-none-match", "\"t1\"")
+        .body(Body::empty())
+        .unwrap();
+    let (status, cache, body) = send(&app, request).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(cache.as_deref(), Some("HIT"));
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn test_cache_keeps_credentialed_responses_apart() {
+    let app = app();
+    let request = |auth: &str| {
+        Request::get("/me")
+            .header("authorization", auth)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let (_, cache, alice) = send(&app, request("Bearer alice")).await;
+    assert_eq!(cache, None);
+    assert!(alice.starts_with("Bearer alice:"));
+    let (_, cache, bob) = send(&app, request("Bearer bob")).await;
+    assert_eq!(cache, None);
+    assert!(bob.starts_with("Bearer bob:"));
+
+    // An anonymous response isn't served to a credentialed request either
+    get(&app, "/me").await;
+    let cookie = Request::get("/me")
+        .header("cookie", "session=alice")
+        .body(Body::empty())
+        .unwrap();
+    let (_, cache, body) = send(&app, cookie).await;
+    assert_eq!(cache, None);
+    assert!(body.starts_with(':'));
+}
+
+#[tokio::test]
+async fn test_cache_shares_public_responses_with_credentialed_requests() {
+    let app = app();
+    let request = |auth: &str| {
+        Request::get("/public")
+            .header("authorization", auth)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let (_, cache, first) = send(&app, request("Bearer alice")).await;
+    assert_eq!(cache.as_deref(), Some("MISS"));
+    let (_, cache, second) = send(&app, request("Bearer bob")).await;
+    assert_eq!(cache.as_deref(), Some("HIT"));
+    assert_eq!(first, second);
+}
+
+// ============================================================================
+// 过期、容量与失效
+// ============================================================================
+
+#[tokio::test]
+async fn test_cache_entries_expire() {
+    let app =
+        routes::create_router::<()>().layer(astrea::cache::layer().ttl(Duration::from_millis(20)));
+    let (_, first) = get(&app, "/users/30").await;
+    astrea::tokio::time::sleep(Duration::from_millis(40)).await;
+    let (cache, second) = get(&app, "/users/30").await;
+    assert_eq!(cache.as_deref(), Some("MISS"));
+    assert_ne!(first, second);
+}
+
+#[tokio::test]
+async fn test_cache_evicts_oldest_entry() {
+    let app = routes::create_router::<()>().layer(astrea::cache::layer().max_entries(2));
+    get(&app, "/users/40").await;
+    get(&app, "/users/41").await;
+    get(&app, "/users/42").await;
+    assert_eq!(get(&app, "/users/40").await.0.as_deref(), Some("MISS"));
+    assert_eq!(get(&app, "/users/42").await.0.as_deref(), Some("HIT"));
+}
+
+#[tokio::test]
+async fn test_cache_invalidate_from_handler() {
+    let app = app();
+    let (_, first) = get(&app, "/users/50").await;
+    get(&app, "/users/51").await;
+
+    let (status, _, _) = send(&app, Request::put("/users/50").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (cache, second) = get(&app, "/users/50").await;
+    assert_eq!(cache.as_deref(), Some("MISS"));
+    assert_ne!(first, second);
+    assert_eq!(get(&app, "/users/51").await.0.as_deref(), Some("HIT"));
+}
+
+// ============================================================================
+// 防击穿（single-flight）
+// ============================================================================
+
+#[tokio::test]
+async fn test_concurrent_misses_call_handler_once() {
+    let app = routes::create_router::<()>().layer(astrea::cache::layer());
+    let requests = (0..8).map(|_| {
+        let app = app.clone();
+        astrea::tokio::spawn(async move { get(&app, "/users/60?slow=1").await.1 })
+    });
+    let mut bodies = Vec::new();
+    for handle in requests.collect::<Vec<_>>() {
+        bodies.push(handle.await.unwrap());
+    }
+    assert!(bodies.iter().all(|body| *body == bodies[0]));
+}
@@ -0,0 +1,100 @@
+//! 响应默认值测试 - 全局 `Server` 头与安全响应头
+//!
+//! Response defaults tests: the global `Server` header and security headers.
+
+use astrea::axum::{body::Body, extract::Request, http::HeaderMap, response::IntoResponse};
+use astrea::prelude::*;
+use astrea::response::defaults::{ResponseDefaults, set_response_defaults};
+use astrea::static_files::PublicDir;
+use astrea::tower::ServiceExt;
+use tokio::sync::{Mutex, MutexGuard};
+
+/// The defaults are global, so tests take turns
+/// / 默认值是全局的，因此测试轮流执行
+static LOCK: Mutex<()> = Mutex::const_new(());
+
+async fn with_defaults(defaults: ResponseDefaults) -> MutexGuard<'static, ()> {
+    let guard = LOCK.lock().await;
+    set_response_defaults(defaults);
+    guard
+}
+
+/// Headers of a success, an error (with and without request id), a stream and a static file
+/// / 成功、错误（带与不带请求 ID）、流式响应以及静态文件的响应头
+async fn all_kinds() -> Vec<HeaderMap> {
+    let file = PublicDir::new("tests/fixtures/public")
+        .oneshot(Request::get("/app.css").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    vec![
+        text("ok").into_axum_response().headers().clone(),
+        RouteError::not_found("x").into_response().headers().clone(),
+        RouteError::bad_request("x")
+            .into_response_with_request_id("req-1")
+            .headers()
+            .clone(),
+        astrea::response::stream(Body::from("chunk"))
+            .headers()
+            .clone(),
+        file.headers().clone(),
+    ]
+}
+
+// ============================================================================
+// Server 头
+// ============================================================================
+
+#[tokio::test]
+async fn test_server_header_on_every_kind_of_response() {
+    let _guard = with_defaults(ResponseDefaults::new()).await;
+    for headers in all_kinds().await {
+        assert_eq!(headers["server"], "Astrea");
+        assert!(!headers.contains_key("x-content-type-options"));
+    }
+}
+
+#[tokio::test]
+async fn test_custom_and_removed_server_header() {
+    let _guard = with_defaults(ResponseDefaults::new().server("edge/1.0")).await;
+    for headers in all_kinds().await {
+        assert_eq!(headers["server"], "edge/1.0");
+    }
+
+    set_response_defaults(ResponseDefaults::new().no_server());
+    for headers in all_kinds().await {
+        assert!(!headers.contains_key("server"));
+    }
+    set_response_defaults(ResponseDefaults::new());
+}
+
+// ============================================================================
+// 安全响应头
+// ============================================================================
+
+#[tokio::test]
+async fn test_security_headers() {
+    let _guard = with_defaults(
+        ResponseDefaults::new()
+            .security_headers()
+            .header("X-Frame-Options", "DENY")
+            .header("bad name", "ignored"),
+    )
+    .await;
+    for headers in all_kinds().await {
+        assert_eq!(headers["x-content-type-options"], "nosniff");
+        assert_eq!(
+            headers["referrer-policy"],
+            "strict-origin-when-cross-origin"
+        );
+        assert_eq!(headers["x-frame-options"], "DENY");
+    }
+
+    // Headers set by the handler win
+    let response = html("<p>embed me</p>")
+        .header("X-Frame-Options", "ALLOWALL")
+        .header("Server", "custom")
+        .into_axum_response();
+    assert_eq!(response.headers()["x-frame-options"], "ALLOWALL");
+    assert_eq!(response.headers()["server"], "custom");
+    set_response_defaults(ResponseDefaults::new());
+}
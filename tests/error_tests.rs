@@ -548,3 +548,93 @@ fn test_resource_conflict_scenario() {
     // 用户名可用
     assert!(create_user("charlie", &users).is_ok());
 }
+
+// ============================================================================
+// 结构化错误信息测试
+// ============================================================================
+
+async fn response_json(error: RouteError) -> (StatusCode, serde_json::Value) {
+    let response = error.into_response();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[test]
+fn test_structured_error_keeps_status_and_message() {
+    let error = RouteError::validation("bad input")
+        .code("invalid_input")
+        .field("email", "invalid_format");
+
+    assert_eq!(error.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error.message(), "bad input");
+    assert_eq!(error.to_string(), "Validation error: bad input");
+    assert_eq!(error.error_code(), Some("invalid_input"));
+    assert!(matches!(error.kind(), RouteError::Validation(_)));
+}
+
+#[test]
+fn test_structured_error_accumulates_fields() {
+    let error = RouteError::validation("bad input")
+        .field("email", "invalid_format")
+        .field_with_message("age", "too_young", "must be at least 13");
+
+    let fields = error.field_errors();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].path, "email");
+    assert_eq!(fields[0].code, "invalid_format");
+    assert_eq!(fields[1].message, "must be at least 13");
+}
+
+#[test]
+fn test_plain_error_has_no_structure() {
+    let error = RouteError::not_found("missing");
+
+    assert_eq!(error.error_code(), None);
+    assert!(error.field_errors().is_empty());
+    assert!(error.error_details().is_none());
+    assert!(matches!(error.kind(), RouteError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_structured_error_response_body() {
+    let error = RouteError::validation("bad input")
+        .code("invalid_input")
+        .field("email", "invalid_format")
+        .details(serde_json::json!({ "hint": "check the form" }));
+
+    let (status, body) = response_json(error).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "error": "bad input",
+            "status": 422,
+            "code": "invalid_input",
+            "errors": [{ "path": "email", "code": "invalid_format" }],
+            "details": { "hint": "check the form" },
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_plain_error_response_body_unchanged() {
+    let (_, body) = response_json(RouteError::conflict("taken")).await;
+
+    assert_eq!(body, serde_json::json!({ "error": "taken", "status": 409 }));
+}
+
+#[tokio::test]
+async fn test_structured_error_extends_validation_errors() {
+    let mut errors = astrea::validate::ValidationErrors::new();
+    errors.add("name", "length", "must not be empty");
+    let error = RouteError::from(errors).field("email", "taken");
+
+    let (status, body) = response_json(error).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["path"], "name");
+    assert_eq!(body["errors"][1]["path"], "email");
+    assert!(body.get("code").is_none());
+}
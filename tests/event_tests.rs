@@ -1,6 +1,7 @@
 //! 全面测试 Event 模块的功能
 
 use astrea::Event;
+use astrea::event::ConnectionInfo;
 use astrea::prelude::*;
 use axum::http::{HeaderMap, HeaderValue, Method, Uri};
 use std::collections::HashMap;
@@ -464,3 +465,145 @@ fn test_event_complex_query_params() {
     // 验证至少有一个键值对被解析
     assert!(!query.is_empty());
 }
+
+// ============================================================================
+// 连接信息测试
+// ============================================================================
+
+fn event_with_headers(headers: &[(&'static str, &'static str)]) -> Event {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.append(*name, HeaderValue::from_static(value));
+    }
+    Event::new(
+        Method::GET,
+        "/".to_string(),
+        "/".parse().unwrap(),
+        map,
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::new(),
+    )
+}
+
+#[test]
+fn test_event_connection_defaults() {
+    let event = event_with_headers(&[]);
+
+    assert_eq!(event.remote_addr(), None);
+    assert_eq!(event.client_ip(), None);
+    assert_eq!(event.scheme(), "http");
+    assert_eq!(event.host(), None);
+    assert_eq!(event.version(), axum::http::Version::HTTP_11);
+}
+
+#[test]
+fn test_event_remote_addr_and_version() {
+    let event = event_with_headers(&[("host", "example.com")]).with_connection(ConnectionInfo {
+        remote_addr: Some("192.0.2.1:8080".parse().unwrap()),
+        version: axum::http::Version::HTTP_2,
+        trusted_proxies: None,
+    });
+
+    assert_eq!(event.remote_addr(), Some("192.0.2.1:8080".parse().unwrap()));
+    assert_eq!(event.client_ip(), Some("192.0.2.1".parse().unwrap()));
+    assert_eq!(event.version(), axum::http::Version::HTTP_2);
+    assert_eq!(event.host(), Some("example.com"));
+}
+
+#[test]
+fn test_event_client_ip_ipv4_mapped_peer() {
+    let event =
+        event_with_headers(&[("x-forwarded-for", "203.0.113.7")]).with_connection(ConnectionInfo {
+            remote_addr: Some("[::ffff:10.1.2.3]:80".parse().unwrap()),
+            trusted_proxies: Some(TrustedProxies::new(["10.0.0.0/8"])),
+            ..ConnectionInfo::default()
+        });
+
+    assert_eq!(event.client_ip(), Some("203.0.113.7".parse().unwrap()));
+}
+
+#[test]
+fn test_event_client_ip_all_hops_trusted() {
+    let event = event_with_headers(&[("x-forwarded-for", "10.0.0.3, 10.0.0.2")]).with_connection(
+        ConnectionInfo {
+            remote_addr: Some("10.0.0.1:80".parse().unwrap()),
+            trusted_proxies: Some(TrustedProxies::new(["10.0.0.0/8"])),
+            ..ConnectionInfo::default()
+        },
+    );
+
+    assert_eq!(event.client_ip(), Some("10.0.0.3".parse().unwrap()));
+}
+
+#[test]
+fn test_trusted_proxies_ranges() {
+    let proxies = TrustedProxies::new(["10.0.0.0/8", "192.168.1.1", "fd00::/8"]);
+
+    assert!(proxies.contains("10.255.0.1".parse().unwrap()));
+    assert!(proxies.contains("192.168.1.1".parse().unwrap()));
+    assert!(!proxies.contains("192.168.1.2".parse().unwrap()));
+    assert!(proxies.contains("fd12::1".parse().unwrap()));
+    assert!(!proxies.contains("fe80::1".parse().unwrap()));
+}
+
+#[test]
+#[should_panic(expected = "Invalid trusted proxy address")]
+fn test_trusted_proxies_invalid_entry() {
+    let _ = TrustedProxies::new(["10.0.0.0/40"]);
+}
+
+// ============================================================================
+// 请求 ID 测试
+// ============================================================================
+
+#[test]
+fn test_event_request_id_attached() {
+    let event = event_with_headers(&[]).with_request_id("req-1");
+
+    assert_eq!(event.request_id(), "req-1");
+    assert_eq!(event.clone().request_id(), "req-1");
+}
+
+#[test]
+fn test_event_request_id_generated_once() {
+    let event = event_with_headers(&[]);
+    let id = event.request_id().to_string();
+
+    assert_eq!(event.request_id(), id);
+    assert_eq!(id.len(), 36);
+    assert_eq!(&id[14..15], "4");
+}
+
+#[test]
+fn test_request_id_resolve() {
+    use astrea::event::request_id::resolve;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-request-id", HeaderValue::from_static("abc-123"));
+    assert_eq!(resolve(&headers), "abc-123");
+
+    headers.insert("x-request-id", HeaderValue::from_static(""));
+    assert_eq!(resolve(&headers).len(), 36);
+}
+
+// ============================================================================
+// 路由模式测试
+// ============================================================================
+
+#[test]
+fn test_event_route_info() {
+    let event = event_with_headers(&[]);
+    assert_eq!(event.route_pattern(), None);
+    assert_eq!(event.route_source_file(), None);
+
+    let event = event.with_route(astrea::router::RouteInfo {
+        pattern: "/users/{id}",
+        source_file: "src/routes/users/[id].get.rs",
+    });
+    assert_eq!(event.route_pattern(), Some("/users/{id}"));
+    assert_eq!(
+        event.route_source_file(),
+        Some("src/routes/users/[id].get.rs")
+    );
+}
@@ -267,6 +267,121 @@ fn test_get_body_json_invalid() {
     assert!(result.is_err());
 }
 
+fn body_event(content_type: Option<&str>, body: &'static [u8]) -> Event {
+    let mut headers = HeaderMap::new();
+    if let Some(ct) = content_type {
+        headers.insert("content-type", HeaderValue::from_str(ct).unwrap());
+    }
+    Event::new(
+        Method::POST,
+        "/api/users".to_string(),
+        "/api/users".parse().unwrap(),
+        headers,
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::from_static(body),
+    )
+}
+
+#[derive(serde::Deserialize, PartialEq, Debug)]
+struct Signup {
+    name: String,
+    age: u32,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_get_body_dispatches_on_content_type() {
+    let json = body_event(
+        Some("application/json; charset=utf-8"),
+        br#"{"name":"Bob","age":30}"#,
+    );
+    let vendor_json = body_event(
+        Some("application/vnd.api+json"),
+        br#"{"name":"Bob","age":30}"#,
+    );
+    let form = body_event(
+        Some("application/x-www-form-urlencoded"),
+        b"name=Bob&age=30&tags=a&tags=b",
+    );
+
+    let expected = Signup {
+        name: "Bob".to_string(),
+        age: 30,
+        tags: vec![],
+    };
+    assert_eq!(get_body::<Signup>(&json).unwrap(), expected);
+    assert_eq!(get_body::<Signup>(&vendor_json).unwrap(), expected);
+    assert_eq!(get_body::<Signup>(&form).unwrap().tags, vec!["a", "b"]);
+}
+
+#[test]
+fn test_get_body_form_invalid_field() {
+    let form = body_event(
+        Some("application/x-www-form-urlencoded"),
+        b"name=Bob&age=old",
+    );
+    match get_body::<Signup>(&form) {
+        Err(RouteError::Validation(msg)) => assert!(msg.contains("`age`"), "{msg}"),
+        other => panic!("expected validation error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_get_body_unsupported_media_type() {
+    let event = body_event(Some("text/csv"), b"name,age");
+    let err = get_body::<Signup>(&event).unwrap_err();
+    assert_eq!(err.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[test]
+fn test_get_body_any_restricts_formats() {
+    let json = body_event(Some("application/json"), br#"{"name":"Bob","age":30}"#);
+    let err = get_body_any::<Signup>(&json, &[BodyFormat::Form]).unwrap_err();
+    assert_eq!(err.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    // Without Content-Type, the first accepted format is used
+    let bare = body_event(None, b"name=Bob&age=30");
+    assert_eq!(
+        get_body_any::<Signup>(&bare, &[BodyFormat::Form])
+            .unwrap()
+            .age,
+        30
+    );
+}
+
+#[test]
+fn test_get_body_custom_format() {
+    fn decode_kv(bytes: &[u8]) -> anyhow::Result<serde_json::Value> {
+        let text = std::str::from_utf8(bytes)?;
+        let mut map = serde_json::Map::new();
+        for line in text.lines() {
+            let (k, v) = line
+                .split_once(':')
+                .ok_or_else(|| anyhow::anyhow!("bad line"))?;
+            let value = v
+                .trim()
+                .parse::<u64>()
+                .map_or_else(|_| serde_json::json!(v.trim()), |n| serde_json::json!(n));
+            map.insert(k.trim().to_string(), value);
+        }
+        Ok(serde_json::Value::Object(map))
+    }
+
+    register_body_format("text/x-kv", decode_kv);
+    assert!(body_formats().contains(&BodyFormat::Custom("text/x-kv")));
+
+    let event = body_event(Some("text/x-kv"), b"name: Bob\nage: 30");
+    assert_eq!(get_body::<Signup>(&event).unwrap().name, "Bob");
+
+    let broken = body_event(Some("text/x-kv"), b"no separator");
+    assert!(matches!(
+        get_body::<Signup>(&broken),
+        Err(RouteError::BadRequest(_))
+    ));
+}
+
 #[test]
 fn test_get_body_bytes() {
     let data = b"Binary data \x00\x01\x02\xFF";
@@ -329,6 +444,30 @@ fn test_get_body_text_invalid_utf8() {
     }
 }
 
+#[tokio::test]
+async fn test_get_body_stream_buffered() {
+    let event = body_event(None, b"buffered");
+    let stream = get_body_stream(&event).unwrap();
+    assert_eq!(stream.bytes().await.unwrap(), "buffered");
+    // Buffered bodies can be streamed again
+    assert!(get_body_stream(&event).is_ok());
+}
+
+#[tokio::test]
+async fn test_get_body_stream_pending() {
+    let event = body_event(None, b"").with_body_stream(astrea::event::BodyStream::new(
+        astrea::axum::body::Body::from("0123456789"),
+        4,
+    ));
+    let mut stream = get_body_stream(&event).unwrap();
+    let err = stream.chunk().await.unwrap_err();
+    assert_eq!(err.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(matches!(
+        get_body_stream(&event),
+        Err(RouteError::BadRequest(_))
+    ));
+}
+
 // ============================================================================
 // 请求头提取测试
 // ============================================================================
@@ -412,6 +551,436 @@ fn test_get_headers() {
     assert_eq!(retrieved_headers.get("user-agent").unwrap(), "Test/1.0");
 }
 
+// ============================================================================
+// Cookie 提取测试
+// ============================================================================
+
+fn event_with_cookies(cookie_headers: &[&'static str]) -> Event {
+    let mut headers = HeaderMap::new();
+    for value in cookie_headers {
+        headers.append("Cookie", HeaderValue::from_static(value));
+    }
+
+    Event::new(
+        Method::GET,
+        "/".to_string(),
+        "/".parse().unwrap(),
+        headers,
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::new(),
+    )
+}
+
+#[test]
+fn test_get_cookie_exists() {
+    let event = event_with_cookies(&["session=abc123; theme=dark"]);
+
+    assert_eq!(get_cookie(&event, "session"), Some("abc123"));
+    assert_eq!(get_cookie(&event, "theme"), Some("dark"));
+    assert_eq!(get_cookie(&event, "missing"), None);
+}
+
+#[test]
+fn test_get_cookie_multiple_headers_and_decoding() {
+    let event = event_with_cookies(&["a=1", "name=hello%20world"]);
+
+    assert_eq!(get_cookie(&event, "a"), Some("1"));
+    assert_eq!(get_cookie(&event, "name"), Some("hello world"));
+    assert_eq!(event.cookies().iter().count(), 2);
+}
+
+#[test]
+fn test_get_cookie_required() {
+    let event = event_with_cookies(&["session=abc123"]);
+
+    assert_eq!(get_cookie_required(&event, "session").unwrap(), "abc123");
+    assert!(matches!(
+        get_cookie_required(&event, "missing"),
+        Err(RouteError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn test_get_cookie_without_header() {
+    let event = event_with_cookies(&[]);
+
+    assert_eq!(get_cookie(&event, "session"), None);
+    assert_eq!(event.cookies().iter().count(), 0);
+}
+
+#[cfg(feature = "signed-cookies")]
+#[test]
+fn test_signed_cookie_roundtrip() {
+    #[derive(Clone)]
+    struct AppState {
+        key: Key,
+    }
+
+    impl axum::extract::FromRef<AppState> for Key {
+        fn from_ref(state: &AppState) -> Self {
+            state.key.clone()
+        }
+    }
+
+    let state = AppState {
+        key: Key::generate(),
+    };
+    let response = text("ok").signed_cookie(&state.key, Cookie::new("user_id", "42"));
+    let set_cookie = response
+        .headers
+        .get("set-cookie")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    let pair = set_cookie.split(';').next().unwrap().to_string();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("Cookie", HeaderValue::from_str(&pair).unwrap());
+    let mut event = Event::new(
+        Method::GET,
+        "/".to_string(),
+        "/".parse().unwrap(),
+        headers,
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::new(),
+    );
+    event.state = Some(Arc::new(state));
+
+    let key = cookie_key::<AppState>(&event).unwrap();
+    assert_eq!(
+        get_signed_cookie(&event, &key, "user_id").as_deref(),
+        Some("42")
+    );
+    assert_eq!(get_signed_cookie(&event, &Key::generate(), "user_id"), None);
+}
+
+// ============================================================================
+// 类型化查询/路径参数提取测试
+// ============================================================================
+
+#[derive(Debug, serde::Deserialize, PartialEq)]
+struct Filter {
+    name: Option<String>,
+    min: Option<u32>,
+}
+
+#[derive(Debug, serde::Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Order {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Search {
+    #[serde(default)]
+    tag: Vec<String>,
+    page: u32,
+    active: Option<bool>,
+    order: Option<Order>,
+    filter: Option<Filter>,
+}
+
+fn event_with_uri(uri: &str) -> Event {
+    Event::new(
+        Method::GET,
+        uri.split('?').next().unwrap().to_string(),
+        uri.parse().unwrap(),
+        HeaderMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::new(),
+    )
+}
+
+#[test]
+fn test_get_query_as_repeated_and_nested() {
+    let event = event_with_uri(
+        "/search?tag=a&tag=b&page=2&active=true&order=desc&filter[name]=x%20y&filter[min]=3",
+    );
+    let search: Search = get_query_as(&event).unwrap();
+
+    assert_eq!(search.tag, vec!["a", "b"]);
+    assert_eq!(search.page, 2);
+    assert_eq!(search.active, Some(true));
+    assert_eq!(search.order, Some(Order::Desc));
+    assert_eq!(
+        search.filter,
+        Some(Filter {
+            name: Some("x y".to_string()),
+            min: Some(3),
+        })
+    );
+}
+
+#[test]
+fn test_get_query_as_single_value_and_brackets() {
+    let search: Search = get_query_as(&event_with_uri("/search?tag=only&page=1")).unwrap();
+    assert_eq!(search.tag, vec!["only"]);
+    assert_eq!(search.filter, None);
+
+    let search: Search =
+        get_query_as(&event_with_uri("/search?tag[]=a&tag[]=b&page=1&active=")).unwrap();
+    assert_eq!(search.tag, vec!["a", "b"]);
+    assert_eq!(search.active, None);
+}
+
+#[test]
+fn test_get_query_as_indexed_sequence() {
+    // Indices decide the order, not the order of the pairs
+    let search: Search = get_query_as(&event_with_uri("/search?tag[1]=x&tag[0]=y&page=1")).unwrap();
+    assert_eq!(search.tag, vec!["y", "x"]);
+
+    let search: Search = get_query_as(&event_with_uri(
+        "/search?tag[10]=c&tag[2]=b&tag[0]=a&page=1",
+    ))
+    .unwrap();
+    assert_eq!(search.tag, vec!["a", "b", "c"]);
+
+    for query in ["tag[x]=a&page=1", "tag[1]=a&tag[01]=b&page=1"] {
+        let err = get_query_as::<Search>(&event_with_uri(&format!("/search?{query}"))).unwrap_err();
+        match err {
+            RouteError::Validation(msg) => assert!(msg.contains("`tag`"), "{msg}"),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_get_query_as_invalid_number_names_field() {
+    let err = get_query_as::<Search>(&event_with_uri("/search?page=abc")).unwrap_err();
+    match err {
+        RouteError::Validation(msg) => assert!(msg.contains("`page`"), "{msg}"),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+
+    let err = get_query_as::<Search>(&event_with_uri("/search?page=1&filter[min]=-1")).unwrap_err();
+    match err {
+        RouteError::Validation(msg) => assert!(msg.contains("filter.min"), "{msg}"),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_get_query_as_trims_scalars() {
+    // Booleans are trimmed the same way numbers are
+    let search: Search =
+        get_query_as(&event_with_uri("/search?page=%202%20&active=%20true%20")).unwrap();
+    assert_eq!(search.page, 2);
+    assert_eq!(search.active, Some(true));
+}
+
+#[test]
+fn test_get_query_as_many_distinct_keys() {
+    let query = (0..5_000)
+        .map(|i| format!("k{i}={i}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    let map: HashMap<String, u32> =
+        get_query_as(&event_with_uri(&format!("/search?{query}&k7=7"))).unwrap();
+    assert_eq!(map.len(), 5_000);
+    assert_eq!(map["k4999"], 4_999);
+}
+
+#[test]
+fn test_get_query_as_missing_field() {
+    let err = get_query_as::<Search>(&event_with_uri("/search")).unwrap_err();
+    match err {
+        RouteError::Validation(msg) => assert!(msg.contains("`page`"), "{msg}"),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_get_params_as() {
+    #[derive(serde::Deserialize)]
+    struct PostPath {
+        id: u64,
+        slug: String,
+    }
+
+    let mut params = HashMap::new();
+    params.insert("id".to_string(), "42".to_string());
+    params.insert("slug".to_string(), "hello-world".to_string());
+    let event = Event::new(
+        Method::GET,
+        "/posts/42/hello-world".to_string(),
+        "/posts/42/hello-world".parse().unwrap(),
+        HeaderMap::new(),
+        params,
+        HashMap::new(),
+        bytes::Bytes::new(),
+    );
+
+    let path: PostPath = get_params_as(&event).unwrap();
+    assert_eq!(path.id, 42);
+    assert_eq!(path.slug, "hello-world");
+
+    let mut params = HashMap::new();
+    params.insert("id".to_string(), "not-a-number".to_string());
+    params.insert("slug".to_string(), "x".to_string());
+    let event = Event::new(
+        Method::GET,
+        "/".to_string(),
+        "/".parse().unwrap(),
+        HeaderMap::new(),
+        params,
+        HashMap::new(),
+        bytes::Bytes::new(),
+    );
+    match get_params_as::<PostPath>(&event) {
+        Err(RouteError::Validation(msg)) => assert!(msg.contains("`id`"), "{msg}"),
+        other => panic!("expected validation error, got {:?}", other.err()),
+    }
+}
+
+// ============================================================================
+// Multipart 提取测试
+// ============================================================================
+
+const BOUNDARY: &str = "astrea-test-boundary";
+
+fn multipart_event(parts: &[(&str, Option<&str>, &[u8])]) -> Event {
+    let mut body = Vec::new();
+    for (name, file_name, data) in parts {
+        body.extend_from_slice(format!("--{BOUNDARY}\r\n").as_bytes());
+        match file_name {
+            Some(f) => body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{name}\"; filename=\"{f}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+                )
+                .as_bytes(),
+            ),
+            None => body.extend_from_slice(
+                format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes(),
+            ),
+        }
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "content-type",
+        HeaderValue::from_str(&format!("multipart/form-data; boundary={BOUNDARY}")).unwrap(),
+    );
+    Event::new(
+        Method::POST,
+        "/upload".to_string(),
+        "/upload".parse().unwrap(),
+        headers,
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::from(body),
+    )
+}
+
+#[tokio::test]
+async fn test_get_multipart_fields() {
+    let event = multipart_event(&[
+        ("title", None, b"hello"),
+        ("avatar", Some("a.png"), b"\x89PNG-data"),
+    ]);
+    let mut multipart = get_multipart(&event).unwrap();
+
+    let field = multipart.next_field().await.unwrap().unwrap();
+    assert_eq!(field.name(), Some("title"));
+    assert!(!field.is_file());
+    assert_eq!(field.text().await.unwrap(), "hello");
+
+    let mut field = multipart.next_field().await.unwrap().unwrap();
+    assert_eq!(field.name(), Some("avatar"));
+    assert_eq!(field.file_name(), Some("a.png"));
+    assert_eq!(field.content_type(), Some("application/octet-stream"));
+    let mut data = Vec::new();
+    while let Some(chunk) = field.chunk().await.unwrap() {
+        data.extend_from_slice(&chunk);
+    }
+    assert_eq!(data, b"\x89PNG-data");
+    drop(field);
+
+    assert!(multipart.next_field().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_get_multipart_collect_and_spill() {
+    let big = vec![b'x'; 4096];
+    let event = multipart_event(&[
+        ("title", None, b"doc"),
+        ("small", Some("s.txt"), b"tiny"),
+        ("big", Some("b.bin"), &big),
+    ]);
+    let config = MultipartConfig::new().spill_threshold(1024);
+    let form = get_multipart_with(&event, config)
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    assert_eq!(form.text("title"), Some("doc"));
+    assert_eq!(form.files().len(), 2);
+
+    let small = form.file("small").unwrap();
+    assert!(small.temp_path().is_none());
+    assert_eq!(small.bytes().await.unwrap().as_ref(), b"tiny");
+
+    let big_upload = form.file("big").unwrap();
+    assert_eq!(big_upload.size(), 4096);
+    let temp = big_upload.temp_path().unwrap().to_path_buf();
+    assert!(temp.exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&temp).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    assert_eq!(big_upload.bytes().await.unwrap().len(), 4096);
+
+    drop(form);
+    assert!(!temp.exists());
+}
+
+#[tokio::test]
+async fn test_get_multipart_field_limit() {
+    let event = multipart_event(&[("title", None, b"0123456789"), ("avatar", Some("a"), b"ok")]);
+    let config = MultipartConfig::new().field_limit(4).limit_for("title", 16);
+    let form = get_multipart_with(&event, config)
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(form.text("title"), Some("0123456789"));
+
+    let event = multipart_event(&[("avatar", Some("a.png"), b"0123456789")]);
+    let config = MultipartConfig::new().field_limit(4);
+    let err = get_multipart_with(&event, config)
+        .unwrap()
+        .collect()
+        .await
+        .unwrap_err();
+    assert_eq!(err.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[test]
+fn test_get_multipart_wrong_content_type() {
+    let event = Event::new(
+        Method::POST,
+        "/upload".to_string(),
+        "/upload".parse().unwrap(),
+        HeaderMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::new(),
+    );
+    assert!(matches!(
+        get_multipart(&event),
+        Err(RouteError::BadRequest(_))
+    ));
+}
+
 // ============================================================================
 // 状态提取测试
 // ============================================================================
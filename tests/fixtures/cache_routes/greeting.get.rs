@@ -0,0 +1,7 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let lang = get_header(&event, "accept-language").unwrap_or_default();
+    Ok(text(format!("{lang}:{}", crate::next_call())).vary("Accept-Language"))
+}
@@ -0,0 +1,7 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let user = get_header(&event, "authorization").unwrap_or_default();
+    Ok(text(format!("{user}:{}", crate::next_call())))
+}
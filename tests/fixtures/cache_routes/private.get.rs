@@ -0,0 +1,6 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(_event: Event) -> Result<Response> {
+    Ok(text(crate::next_call().to_string()).cache_control(CacheControl::new().no_store()))
+}
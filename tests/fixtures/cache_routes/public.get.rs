@@ -0,0 +1,7 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(_event: Event) -> Result<Response> {
+    Ok(text(format!("public:{}", crate::next_call()))
+        .cache_control(CacheControl::new().public().max_age(60)))
+}
@@ -0,0 +1,10 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let id = get_param_required(&event, "id")?;
+    if get_query_param(&event, "slow").is_some() {
+        astrea::tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    Ok(text(format!("{id}:{}", crate::next_call())))
+}
@@ -0,0 +1,8 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let id = get_param_required(&event, "id")?;
+    astrea::cache::invalidate("/users/{id}", id);
+    Ok(no_content())
+}
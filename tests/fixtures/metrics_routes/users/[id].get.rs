@@ -0,0 +1,10 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let id = get_param_required(&event, "id")?;
+    if id == "0" {
+        return Err(RouteError::not_found("No such user"));
+    }
+    Ok(text(id))
+}
@@ -0,0 +1,6 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    Middleware::new().name("root")
+        .wrap(|router| crate::tag(router, "root"))
+}
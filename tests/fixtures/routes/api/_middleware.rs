@@ -0,0 +1,6 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    Middleware::new().name("api")
+        .wrap(|router| crate::tag(router, "api"))
+}
@@ -0,0 +1,6 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    Middleware::new().name("admin").priority(10)
+        .wrap(|router| crate::tag(router, "admin"))
+}
@@ -0,0 +1,10 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    Ok(text(format!(
+        "{} {}",
+        event.route_pattern().unwrap_or_default(),
+        event.route_source_file().unwrap_or_default()
+    )))
+}
@@ -0,0 +1,6 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    Ok(text(crate::chain_of(&event)))
+}
@@ -0,0 +1,6 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    Middleware::override_parent().name("public")
+        .wrap(|router| crate::tag(router, "public"))
+}
@@ -0,0 +1,7 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    Middleware::new()
+        .name("cached")
+        .cache_control(CacheControl::new().public().max_age(300))
+}
@@ -0,0 +1,6 @@
+use astrea::prelude::*;
+
+#[route(cache = "public, max-age=60, stale-while-revalidate=30")]
+pub async fn handler(_event: Event) -> Result<Response> {
+    Ok(text("feed"))
+}
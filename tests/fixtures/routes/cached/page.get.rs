@@ -0,0 +1,10 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    match get_query_param(&event, "mode").as_deref() {
+        Some("error") => Err(RouteError::not_found("missing")),
+        Some("own") => Ok(text("own").cache_control(CacheControl::new().no_store())),
+        _ => Ok(text("page")),
+    }
+}
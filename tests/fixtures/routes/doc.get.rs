@@ -0,0 +1,9 @@
+use astrea::prelude::*;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[route]
+pub async fn handler(_event: Event) -> Result<Response> {
+    Ok(text("hello")
+        .auto_etag()
+        .last_modified(UNIX_EPOCH + Duration::from_secs(1_000_000_000)))
+}
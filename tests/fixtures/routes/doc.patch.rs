@@ -0,0 +1,11 @@
+use astrea::prelude::*;
+
+/// The document's current tag / 文档当前的标签
+async fn current_tag(_event: &Event) -> Result<Option<ETag>> {
+    Ok(Some(ETag::strong("v1")))
+}
+
+#[route(if_match = "current_tag")]
+pub async fn handler(event: Event) -> Result<Response> {
+    Ok(text("patched").etag("v2"))
+}
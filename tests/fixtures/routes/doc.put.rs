@@ -0,0 +1,7 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    check_if_match(&event, &ETag::strong("v1"))?;
+    Ok(text("updated").etag("v2"))
+}
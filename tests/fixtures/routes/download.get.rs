@@ -0,0 +1,9 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let name = get_query_param(&event, "name").unwrap_or_else(|| "sample.txt".to_string());
+    Ok(file(format!("tests/fixtures/files/{name}"))
+        .await?
+        .download("报告 2024.txt"))
+}
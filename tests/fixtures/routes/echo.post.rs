@@ -0,0 +1,6 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    Ok(text(event.body.len().to_string()))
+}
@@ -0,0 +1,22 @@
+use astrea::prelude::*;
+use futures_util::{StreamExt, stream};
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let count = match get_query_param(&event, "count") {
+        Some(count) => count.parse().map_err(|_| RouteError::bad_request("count"))?,
+        None => 3,
+    };
+    let items = stream::iter(0..count).map(|id| json!({ "id": id }));
+    Ok(match get_query_param(&event, "format").as_deref() {
+        Some("ndjson") => ndjson(items),
+        Some("fail") => try_json_stream(stream::iter(0..count).map(|id| {
+            if id < 2 {
+                Ok(json!({ "id": id }))
+            } else {
+                Err(std::io::Error::other("database went away"))
+            }
+        })),
+        _ => json_stream(items),
+    })
+}
@@ -0,0 +1,5 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    Middleware::new().name("uploads").body_limit(16)
+}
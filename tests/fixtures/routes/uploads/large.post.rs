@@ -0,0 +1,6 @@
+use astrea::prelude::*;
+
+#[route(body_limit = "1KB")]
+pub async fn handler(event: Event) -> Result<Response> {
+    Ok(text(event.body.len().to_string()))
+}
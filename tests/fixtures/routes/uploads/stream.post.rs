@@ -0,0 +1,15 @@
+use astrea::prelude::*;
+
+#[route(body = "stream", body_limit = 64)]
+pub async fn handler(event: Event) -> Result<Response> {
+    assert!(event.body.is_empty());
+    let mut stream = get_body_stream(&event)?;
+    let mut chunks = 0;
+    let mut total = 0;
+    while let Some(chunk) = stream.chunk().await? {
+        chunks += 1;
+        total += chunk.len();
+    }
+    assert!(get_body_stream(&event).is_err());
+    Ok(text(format!("{chunks}:{total}")))
+}
@@ -0,0 +1,8 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(event: Event) -> Result<Response> {
+    let ip = event.client_ip().map(|ip| ip.to_string()).unwrap_or_default();
+    let host = event.host().unwrap_or_default();
+    Ok(text(format!("{ip} {}://{host}", event.scheme())))
+}
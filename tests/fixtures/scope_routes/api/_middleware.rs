@@ -0,0 +1,6 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    crate::loaded("api");
+    Middleware::new().wrap(|router| crate::counted(router, "api"))
+}
@@ -0,0 +1,6 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    crate::loaded("uploads");
+    Middleware::new().body_limit(16)
+}
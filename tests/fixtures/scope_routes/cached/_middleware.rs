@@ -0,0 +1,6 @@
+use astrea::middleware::*;
+
+pub fn middleware<S: Clone + Send + Sync + 'static>() -> Middleware<S> {
+    crate::loaded("cached");
+    Middleware::new().cache_control(CacheControl::new().public().max_age(60))
+}
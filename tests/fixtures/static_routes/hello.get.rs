@@ -0,0 +1,6 @@
+use astrea::prelude::*;
+
+#[route]
+pub async fn handler(_event: Event) -> Result<Response> {
+    Ok(text("from route"))
+}
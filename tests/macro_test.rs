@@ -15,6 +15,6 @@ mod tests {
     #[test]
     fn test_macro_compiles() {
         // 如果宏能正确展开，这个测试应该能编译通过
-        assert!(true);
+        let _ = super::test_handler::<()>;
     }
 }
@@ -0,0 +1,94 @@
+//! 指标测试 - 使用 `tests/fixtures/metrics_routes` 测试 Prometheus 指标中间件
+//!
+//! Metrics middleware tests against the `tests/fixtures/metrics_routes` tree.
+
+#![cfg(feature = "metrics")]
+
+use astrea::axum::{Router, body::Body, extract::Request};
+use astrea::prelude::*;
+use astrea::tower::ServiceExt;
+
+mod routes {
+    astrea::generate_routes!("tests/fixtures/metrics_routes");
+}
+
+fn app() -> Router {
+    routes::create_router::<()>()
+        .merge(astrea::metrics::router())
+        .layer(astrea::metrics::layer())
+}
+
+async fn get(app: &Router, path: &str) -> (StatusCode, String) {
+    let response = app
+        .clone()
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+// ============================================================================
+// 指标记录
+// ============================================================================
+
+#[tokio::test]
+async fn test_metrics_keyed_by_route_pattern() {
+    let app = app();
+    get(&app, "/users/1").await;
+    get(&app, "/users/2").await;
+    get(&app, "/users/0").await;
+
+    let (status, body) = get(&app, "/metrics").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains(
+        "astrea_http_requests_total{method=\"GET\",route=\"/users/{id}\",status=\"2xx\"} 2\n"
+    ));
+    assert!(body.contains(
+        "astrea_http_requests_total{method=\"GET\",route=\"/users/{id}\",status=\"4xx\"} 1\n"
+    ));
+    assert!(body.contains(
+        "astrea_http_request_duration_seconds_count{method=\"GET\",route=\"/users/{id}\",status=\"2xx\"} 2\n"
+    ));
+    assert!(body.contains(
+        "astrea_http_request_duration_seconds_bucket{method=\"GET\",route=\"/users/{id}\",status=\"2xx\",le=\"+Inf\"} 2\n"
+    ));
+    assert!(!body.contains("/users/1"));
+}
+
+#[tokio::test]
+async fn test_metrics_routes_preregistered() {
+    let app = app();
+    let (_, body) = get(&app, "/metrics").await;
+
+    assert!(
+        body.contains(
+            "astrea_http_requests_total{method=\"GET\",route=\"/idle\",status=\"2xx\"} 0\n"
+        )
+    );
+    assert!(body.contains("astrea_http_requests_in_flight{method=\"GET\",route=\"/idle\"} 0\n"));
+}
+
+#[tokio::test]
+async fn test_metrics_content_type() {
+    let response = app()
+        .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; version=0.0.4; charset=utf-8"
+    );
+}
+
+#[test]
+fn test_metrics_label_escaping() {
+    astrea::metrics::register_route("get", "/weird/\"quoted\"");
+
+    assert!(
+        astrea::metrics::render().contains("route=\"/weird/\\\"quoted\\\"\",status=\"2xx\"} 0\n")
+    );
+}
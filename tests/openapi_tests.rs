@@ -298,8 +298,23 @@ fn test_response_content_types() {
     let text_meta = text_mod::__openapi_meta();
     assert_eq!(text_meta.response_content_type, "text/plain");
 
+    mod ndjson_mod {
+        use super::*;
+
+        /// Stream events
+        /// @tag Events
+        #[route]
+        pub async fn ndjson_handler(_event: Event) -> Result<Response> {
+            Ok::<_, RouteError>(ndjson(futures_util::stream::iter([json!({ "seq": 1 })])))
+        }
+    }
+
     let bytes_meta = bytes_mod::__openapi_meta();
     assert_eq!(bytes_meta.response_content_type, "application/octet-stream");
+
+    let ndjson_meta = ndjson_mod::__openapi_meta();
+    assert_eq!(ndjson_meta.response_content_type, "application/x-ndjson");
+    assert_eq!(ndjson_meta.response_schema_fields, vec!["seq".to_string()]);
 }
 
 // ---------------------------------------------------------------------------
@@ -521,3 +536,265 @@ fn test_no_content_response() {
     assert_eq!(meta.response_content_type, "none");
     assert!(meta.response_schema_fields.is_empty());
 }
+
+// ---------------------------------------------------------------------------
+// Test 13: Cookie parameters
+// 测试 13: Cookie 参数
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cookie_parameters() {
+    mod handler {
+        use super::*;
+
+        /// Current session
+        #[route]
+        pub async fn cookie_handler(event: Event) -> Result<Response> {
+            let session = get_cookie_required(&event, "session")?;
+            let theme = get_cookie(&event, "theme").unwrap_or("light");
+            json(json!({ "session": session, "theme": theme }))
+        }
+    }
+
+    let meta = handler::__openapi_meta();
+
+    assert_eq!(meta.parameters.len(), 2);
+    assert_eq!(meta.parameters[0].name, "session");
+    assert_eq!(meta.parameters[0].location, ParamLocation::Cookie);
+    assert!(meta.parameters[0].required);
+    assert_eq!(meta.parameters[1].name, "theme");
+    assert_eq!(meta.parameters[1].location, ParamLocation::Cookie);
+    assert!(!meta.parameters[1].required);
+}
+
+// ---------------------------------------------------------------------------
+// Test 14: Typed query parameters derived from a struct
+// 测试 14: 从结构体派生的类型化查询参数
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_typed_query_parameters() {
+    mod handler {
+        use super::*;
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        pub struct Range {
+            pub min: u32,
+            pub max: Option<u32>,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        pub struct Search {
+            pub q: String,
+            #[serde(default)]
+            pub tag: Vec<String>,
+            pub page: Option<u64>,
+            pub range: Option<Range>,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        pub struct ItemPath {
+            pub id: i64,
+        }
+
+        #[route]
+        pub async fn typed_handler(event: Event) -> Result<Response> {
+            let search = get_query_as::<Search>(&event)?;
+            let path: ItemPath = get_params_as(&event)?;
+            json(json!({ "q": search.q, "id": path.id }))
+        }
+    }
+
+    let meta = handler::__openapi_meta();
+    let find = |name: &str| {
+        meta.parameters
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap_or_else(|| panic!("missing parameter {name}"))
+    };
+
+    assert_eq!(meta.parameters.len(), 6);
+
+    let q = find("q");
+    assert_eq!(q.location, ParamLocation::Query);
+    assert!(q.required);
+    assert_eq!(q.schema_type, "string");
+
+    let tag = find("tag");
+    assert!(!tag.required);
+    assert_eq!(tag.schema_type, "array");
+    assert_eq!(tag.item_type.as_deref(), Some("string"));
+
+    let page = find("page");
+    assert!(!page.required);
+    assert_eq!(page.schema_type, "integer");
+    assert_eq!(page.schema_format.as_deref(), Some("uint64"));
+
+    let min = find("range[min]");
+    assert!(!min.required);
+    assert_eq!(min.schema_format.as_deref(), Some("uint32"));
+    assert!(!find("range[max]").required);
+
+    let id = find("id");
+    assert_eq!(id.location, ParamLocation::Path);
+    assert_eq!(id.schema_format.as_deref(), Some("int64"));
+}
+
+// ---------------------------------------------------------------------------
+// Test 15: Multipart form request body
+// 测试 15: Multipart 表单请求体
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_multipart_request_body() {
+    mod handler {
+        use super::*;
+
+        #[route]
+        pub async fn upload_handler(event: Event) -> Result<Response> {
+            let form = get_multipart(&event)?.collect().await?;
+            let title = form.text("title").unwrap_or_default();
+            let size = form.file("avatar").map(|f| f.size()).unwrap_or(0);
+            json(json!({ "title": title, "size": size }))
+        }
+    }
+
+    let meta = handler::__openapi_meta();
+    let body = meta.request_body.expect("multipart body");
+
+    assert_eq!(body.content_type, "multipart/form-data");
+    assert!(body.schema_type_name.is_empty());
+    assert_eq!(body.form_fields.len(), 2);
+    assert_eq!(body.form_fields[0].name, "title");
+    assert!(!body.form_fields[0].binary);
+    assert_eq!(body.form_fields[1].name, "avatar");
+    assert!(body.form_fields[1].binary);
+}
+
+// ---------------------------------------------------------------------------
+// Test 16: Accepted request body media types
+// 测试 16: 接受的请求体媒体类型
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_request_body_media_types() {
+    mod any_handler {
+        use super::*;
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        pub struct Login {
+            pub user: String,
+        }
+
+        #[route]
+        pub async fn any_body_handler(event: Event) -> Result<Response> {
+            let login: Login = get_body(&event)?;
+            json(json!({ "user": login.user }))
+        }
+    }
+
+    mod form_handler {
+        use super::*;
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        pub struct Login {
+            pub user: String,
+        }
+
+        #[route]
+        pub async fn form_only_handler(event: Event) -> Result<Response> {
+            let login = get_body_any::<Login>(&event, &[BodyFormat::Form])?;
+            json(json!({ "user": login.user }))
+        }
+    }
+
+    let body = any_handler::__openapi_meta().request_body.unwrap();
+    assert_eq!(body.content_type, "application/json");
+    assert!(body.content_types.contains(&"application/json".to_string()));
+    assert!(
+        body.content_types
+            .contains(&"application/x-www-form-urlencoded".to_string())
+    );
+
+    let body = form_handler::__openapi_meta().request_body.unwrap();
+    assert_eq!(body.schema_type_name, "Login");
+    assert_eq!(body.content_type, "application/x-www-form-urlencoded");
+    assert_eq!(
+        body.content_types,
+        vec!["application/x-www-form-urlencoded"]
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Test 17: Validation rules reflected in parameters and body schemas
+// 测试 17: 验证规则反映到参数和请求体模式中
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_validation_constraints() {
+    mod handler {
+        use super::*;
+
+        #[derive(serde::Deserialize, Validate)]
+        #[allow(dead_code)]
+        pub struct Page {
+            #[validate(range(min = 1, max = 100))]
+            pub per_page: u32,
+        }
+
+        #[derive(serde::Deserialize, Validate)]
+        #[allow(dead_code)]
+        pub struct Profile {
+            #[validate(length(max = 64))]
+            pub city: Option<String>,
+        }
+
+        #[derive(serde::Deserialize, Validate)]
+        #[allow(dead_code)]
+        pub struct CreateUser {
+            #[validate(length(min = 3, max = 20), regex = "^[a-z]+$")]
+            pub name: String,
+            #[validate(email)]
+            pub email: String,
+            #[validate(length(max = 5))]
+            #[serde(default)]
+            pub tags: Vec<String>,
+            #[validate(nested)]
+            pub profile: Profile,
+        }
+
+        #[route]
+        pub async fn create_user(event: Event) -> Result<Response> {
+            let page: Page = get_valid_query(&event)?;
+            let user = get_valid_body::<CreateUser>(&event)?;
+            json(json!({ "name": user.name, "page": page.per_page }))
+        }
+    }
+
+    let meta = handler::__openapi_meta();
+    let per_page = &meta.parameters[0];
+    assert_eq!(per_page.name, "per_page");
+    assert_eq!(per_page.schema_keywords["minimum"], json!(1.0));
+    assert_eq!(per_page.schema_keywords["maximum"], json!(100.0));
+
+    let body = meta.request_body.unwrap();
+    assert_eq!(body.schema_type_name, "CreateUser");
+    let schema = body.schema.unwrap();
+    assert_eq!(schema["required"], json!(["name", "email", "profile"]));
+    let props = &schema["properties"];
+    assert_eq!(props["name"]["minLength"], json!(3));
+    assert_eq!(props["name"]["maxLength"], json!(20));
+    assert_eq!(props["name"]["pattern"], json!("^[a-z]+$"));
+    assert_eq!(props["email"]["format"], json!("email"));
+    assert_eq!(props["tags"]["type"], json!("array"));
+    assert_eq!(props["tags"]["maxItems"], json!(5));
+    assert_eq!(
+        props["profile"]["properties"]["city"]["maxLength"],
+        json!(64)
+    );
+}
@@ -84,6 +84,51 @@ fn test_json_response_empty_object() {
     assert_eq!(String::from_utf8_lossy(&response.body), "{}");
 }
 
+#[test]
+fn test_json_with_status() {
+    let response = json_with_status(StatusCode::CREATED, json!({ "id": 1 })).unwrap();
+    assert_eq!(response.status, StatusCode::CREATED);
+    assert_eq!(response.headers["content-type"], "application/json");
+    assert_eq!(response.body, br#"{"id":1}"#);
+}
+
+#[test]
+fn test_json_pretty() {
+    let response = json_pretty(json!({ "id": 1, "tags": ["a"] })).unwrap();
+    assert_eq!(response.headers["content-type"], "application/json");
+    assert_eq!(
+        String::from_utf8(response.body).unwrap(),
+        "{\n  \"id\": 1,\n  \"tags\": [\n    \"a\"\n  ]\n}"
+    );
+}
+
+#[test]
+fn test_json_canonical_sorts_keys() {
+    #[derive(Serialize)]
+    struct Payload {
+        zeta: u8,
+        alpha: Vec<Inner>,
+    }
+    #[derive(Serialize)]
+    struct Inner {
+        y: bool,
+        b: Option<u8>,
+    }
+
+    let payload = Payload {
+        zeta: 1,
+        alpha: vec![Inner { y: true, b: None }],
+    };
+    assert_eq!(
+        json(&payload).unwrap().body,
+        br#"{"zeta":1,"alpha":[{"y":true,"b":null}]}"#
+    );
+    assert_eq!(
+        json_canonical(&payload).unwrap().body,
+        br#"{"alpha":[{"b":null,"y":true}],"zeta":1}"#
+    );
+}
+
 // ============================================================================
 // Text 响应测试
 // ============================================================================
@@ -222,6 +267,110 @@ fn test_redirect_response_invalid_url() {
     }
 }
 
+#[test]
+fn test_redirect_variants() {
+    for (response, status) in [
+        (redirect_permanent("/v2"), StatusCode::PERMANENT_REDIRECT),
+        (redirect_moved("/v2"), StatusCode::MOVED_PERMANENTLY),
+        (redirect_see_other("/v2"), StatusCode::SEE_OTHER),
+        (redirect_temporary("/v2"), StatusCode::TEMPORARY_REDIRECT),
+    ] {
+        let response = response.unwrap();
+        assert_eq!(response.status, status);
+        assert_eq!(response.headers["location"], "/v2");
+    }
+    assert!(matches!(
+        redirect_see_other("/a\nb"),
+        Err(RouteError::BadRequest(_))
+    ));
+}
+
+fn event_at(uri: &'static str) -> Event {
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert("host", "app.example.com:8080".parse().unwrap());
+    let uri = axum::http::Uri::from_static(uri);
+    Event::new(
+        axum::http::Method::GET,
+        uri.path().to_string(),
+        uri,
+        headers,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    )
+}
+
+#[test]
+fn test_resolve_location() {
+    let event = event_at("/users/42/profile?tab=posts");
+    for (target, expected) in [
+        ("edit", "/users/42/edit"),
+        ("../43/profile", "/users/43/profile"),
+        ("./", "/users/42/"),
+        ("..", "/users/"),
+        ("../../../../login", "/login"),
+        ("/a/./b/../c?x=1", "/a/c?x=1"),
+        ("?tab=likes", "/users/42/profile?tab=likes"),
+        ("#bio", "/users/42/profile?tab=posts#bio"),
+        ("https://example.com/x", "https://example.com/x"),
+        ("//cdn.example.com/x", "//cdn.example.com/x"),
+    ] {
+        assert_eq!(resolve_location(&event, target), expected, "{target}");
+    }
+}
+
+#[test]
+fn test_safe_redirect() {
+    astrea::response::redirect::allow_redirect_hosts(["accounts.example.net", "*.example.org"]);
+    let event = event_at("/login");
+
+    for (target, location) in [
+        ("dashboard", "/dashboard"),
+        ("/account?x=1", "/account?x=1"),
+        (
+            "https://app.example.com/home",
+            "https://app.example.com/home",
+        ),
+        (
+            "http://APP.example.com:9000/",
+            "http://APP.example.com:9000/",
+        ),
+        (
+            "https://accounts.example.net/sso",
+            "https://accounts.example.net/sso",
+        ),
+        ("//docs.example.org/", "//docs.example.org/"),
+        ("/.//app.example.com/x", "//app.example.com/x"),
+    ] {
+        let response = safe_redirect(&event, target).unwrap();
+        assert_eq!(response.status, StatusCode::FOUND);
+        assert_eq!(response.headers["location"], location);
+    }
+
+    for target in [
+        "https://evil.com/",
+        "//evil.com",
+        "https://app.example.com.evil.com/",
+        "https://app.example.com@evil.com/",
+        "https://example.org/",
+        "javascript:alert(1)",
+        "/\\evil.com",
+        "/\tevil",
+        "ftp://app.example.com/",
+        "/.//evil.com",
+        "/..//evil.com/x",
+        "a/..//evil.com",
+    ] {
+        assert!(
+            matches!(
+                safe_redirect(&event, target),
+                Err(RouteError::BadRequest(_))
+            ),
+            "{target}"
+        );
+    }
+}
+
 // ============================================================================
 // No Content 响应测试
 // ============================================================================
@@ -325,6 +474,44 @@ fn test_response_chaining_override_content_type() {
     );
 }
 
+// ============================================================================
+// Cookie 响应测试
+// ============================================================================
+
+#[test]
+fn test_response_cookie() {
+    let response = text("ok")
+        .cookie(Cookie::build(("theme", "dark")).path("/").http_only(true))
+        .cookie(Cookie::new("lang", "zh CN"));
+
+    let cookies: Vec<&str> = response
+        .headers
+        .get_all("set-cookie")
+        .iter()
+        .map(|v| v.to_str().unwrap())
+        .collect();
+
+    assert_eq!(cookies.len(), 2);
+    assert!(cookies[0].starts_with("theme=dark"));
+    assert!(cookies[0].contains("HttpOnly"));
+    assert!(cookies[0].contains("Path=/"));
+    assert_eq!(cookies[1], "lang=zh%20CN");
+}
+
+#[test]
+fn test_response_remove_cookie() {
+    let response = no_content().remove_cookie("session");
+
+    let cookie = response
+        .headers
+        .get("set-cookie")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(cookie.starts_with("session=;"));
+    assert!(cookie.contains("Max-Age=0"));
+}
+
 // ============================================================================
 // Response 默认值测试
 // ============================================================================
@@ -515,3 +702,324 @@ fn test_response_invalid_header_value() {
     // 应该默默失败，不添加这个头
     assert!(!response.headers.contains_key("x-custom"));
 }
+
+// ============================================================================
+// ETag / Last-Modified 测试
+// ============================================================================
+
+#[test]
+fn test_response_etag_strong_and_weak() {
+    assert_eq!(text("a").etag("v1").headers["etag"], "\"v1\"");
+    assert_eq!(text("a").weak_etag("v1").headers["etag"], "W/\"v1\"");
+}
+
+#[test]
+fn test_response_auto_etag_follows_body() {
+    let a = text("hello").auto_etag();
+    let b = text("hello").auto_etag();
+    let c = text("world").auto_etag();
+
+    assert_eq!(a.headers["etag"], b.headers["etag"]);
+    assert_ne!(a.headers["etag"], c.headers["etag"]);
+}
+
+#[test]
+fn test_response_last_modified() {
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+    let response = text("a").last_modified(time);
+
+    assert_eq!(
+        response.headers["last-modified"],
+        "Sun, 09 Sep 2001 01:46:40 GMT"
+    );
+}
+
+#[test]
+fn test_etag_parse_and_compare() {
+    let strong = ETag::parse("\"abc\"").unwrap();
+    let weak = ETag::parse("W/\"abc\"").unwrap();
+
+    assert!(!strong.is_weak());
+    assert!(weak.is_weak());
+    assert_eq!(weak.tag(), "abc");
+    assert_eq!(weak.to_string(), "W/\"abc\"");
+    assert!(strong.weak_eq(&weak));
+    assert!(!strong.strong_eq(&weak));
+    assert!(strong.strong_eq(&ETag::strong("abc")));
+    assert!(ETag::parse("abc").is_none());
+}
+
+#[test]
+fn test_conditions_only_apply_to_get_and_head() {
+    use astrea::response::conditional::Conditions;
+    use axum::http::{HeaderMap, HeaderValue, Method};
+
+    let mut headers = HeaderMap::new();
+    headers.insert("if-none-match", HeaderValue::from_static("W/\"v1\""));
+
+    let get = Conditions::from_request(&Method::GET, &headers).apply(text("a").etag("v1"));
+    assert_eq!(get.status, StatusCode::NOT_MODIFIED);
+    assert!(get.body.is_empty());
+    assert!(!get.headers.contains_key("content-type"));
+
+    let post = Conditions::from_request(&Method::POST, &headers).apply(text("a").etag("v1"));
+    assert_eq!(post.status, StatusCode::OK);
+
+    headers.insert("if-none-match", HeaderValue::from_static("*"));
+    let any = Conditions::from_request(&Method::HEAD, &headers).apply(text("a").etag("v9"));
+    assert_eq!(any.status, StatusCode::NOT_MODIFIED);
+}
+
+// ============================================================================
+// Cache-Control / Vary 测试
+// ============================================================================
+
+#[test]
+fn test_cache_control_builder() {
+    let policy = CacheControl::new()
+        .private()
+        .public()
+        .max_age(60)
+        .s_maxage(120)
+        .stale_while_revalidate(30);
+    assert_eq!(
+        policy.to_string(),
+        "public, max-age=60, s-maxage=120, stale-while-revalidate=30"
+    );
+    assert_eq!(CacheControl::new().no_store().to_string(), "no-store");
+    assert_eq!(CacheControl::new().to_string(), "");
+
+    let response = text("a").cache_control(CacheControl::new().private().max_age(5));
+    assert_eq!(response.headers["cache-control"], "private, max-age=5");
+}
+
+#[test]
+fn test_response_vary_merges_names() {
+    let response = text("a")
+        .vary("Accept")
+        .vary("accept-language")
+        .vary("ACCEPT");
+    assert_eq!(response.headers["vary"], "Accept, accept-language");
+
+    let response = text("a").vary("Accept").vary("*").vary("Cookie");
+    assert_eq!(response.headers["vary"], "*");
+}
+
+// ============================================================================
+// 文件下载测试
+// ============================================================================
+
+#[test]
+fn test_download_content_disposition() {
+    let response = bytes(b"data".to_vec()).download("report.pdf");
+    assert_eq!(
+        response.headers["content-disposition"],
+        "attachment; filename=\"report.pdf\""
+    );
+
+    let response = bytes(b"data".to_vec()).download("résumé \"final\".pdf");
+    assert_eq!(
+        response.headers["content-disposition"],
+        "attachment; filename=\"r_sum_ _final_.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9%20%22final%22.pdf"
+    );
+}
+
+#[tokio::test]
+async fn test_cloned_file_response_does_not_share_stream() {
+    let original = file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/files/sample.txt"
+    ))
+    .await
+    .unwrap();
+    let clone = original.clone();
+
+    // The clone is sent first, and still doesn't take the stream
+    let cloned = axum::body::to_bytes(clone.into_axum_response().into_body(), usize::MAX).await;
+    assert!(cloned.is_err());
+
+    let body = axum::body::to_bytes(original.into_axum_response().into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body.as_ref(), b"0123456789abcdefghij");
+}
+
+#[tokio::test]
+async fn test_auto_etag_skips_streamed_responses() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/files/sample.txt"
+    );
+    let original = file(path).await.unwrap();
+    let etag = original.headers["etag"].clone();
+    assert_eq!(original.auto_etag().headers["etag"], etag);
+
+    let stream = json_stream(futures_util::stream::iter([1, 2, 3])).auto_etag();
+    assert!(!stream.headers.contains_key("etag"));
+
+    let empty = ETag::from_body(b"").to_string();
+    assert_eq!(text("").auto_etag().headers["etag"], empty.as_str());
+}
+
+#[test]
+fn test_range_on_in_memory_body() {
+    use astrea::response::conditional::Conditions;
+    use axum::http::{HeaderMap, HeaderValue, Method};
+
+    let mut headers = HeaderMap::new();
+    headers.insert("range", HeaderValue::from_static("bytes=6-"));
+
+    // Only responses advertising `Accept-Ranges: bytes` are sliced
+    let plain = Conditions::from_request(&Method::GET, &headers).apply(text("hello world"));
+    assert_eq!(plain.status, StatusCode::OK);
+
+    let ranged = Conditions::from_request(&Method::GET, &headers)
+        .apply(text("hello world").header("Accept-Ranges", "bytes"));
+    assert_eq!(ranged.status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(ranged.headers["content-range"], "bytes 6-10/11");
+    assert_eq!(ranged.body, b"world");
+
+    // Range is ignored for other methods
+    let post = Conditions::from_request(&Method::POST, &headers)
+        .apply(text("hello world").header("Accept-Ranges", "bytes"));
+    assert_eq!(post.status, StatusCode::OK);
+}
+
+// ============================================================================
+// 内容协商测试
+// ============================================================================
+
+#[test]
+fn test_select_format_from_accept() {
+    use astrea::response::negotiate::{ResponseFormat, select_format};
+
+    let formats = [ResponseFormat::Json, ResponseFormat::Custom("text/csv")];
+    assert_eq!(select_format(None, &formats), Some(ResponseFormat::Json));
+    assert_eq!(
+        select_format(Some("text/csv"), &formats),
+        Some(ResponseFormat::Custom("text/csv"))
+    );
+    // Higher quality wins, equal quality keeps the server order
+    assert_eq!(
+        select_format(Some("application/json;q=0.4, text/*;q=0.9"), &formats),
+        Some(ResponseFormat::Custom("text/csv"))
+    );
+    assert_eq!(
+        select_format(Some("*/*"), &formats),
+        Some(ResponseFormat::Json)
+    );
+    // The most specific range decides, even when a wildcard allows it
+    assert_eq!(
+        select_format(Some("*/*, application/json;q=0"), &formats),
+        Some(ResponseFormat::Custom("text/csv"))
+    );
+    assert_eq!(select_format(Some("image/png"), &formats), None);
+}
+
+#[test]
+fn test_negotiate_custom_format() {
+    use astrea::response::negotiate::{ResponseFormat, negotiate_any, register_response_format};
+
+    fn encode_csv(value: &serde_json::Value) -> anyhow::Result<Vec<u8>> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("not an object"))?;
+        let header: Vec<&str> = object.keys().map(String::as_str).collect();
+        let row: Vec<String> = object.values().map(ToString::to_string).collect();
+        Ok(format!("{}\n{}\n", header.join(","), row.join(",")).into_bytes())
+    }
+    register_response_format("text/csv", encode_csv);
+
+    let event = Event::new(
+        axum::http::Method::GET,
+        "/report".to_string(),
+        axum::http::Uri::from_static("/report"),
+        {
+            let mut headers = axum::http::HeaderMap::new();
+            headers.insert("accept", "text/csv".parse().unwrap());
+            headers
+        },
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    );
+    let response = negotiate_any(
+        &event,
+        json!({ "id": 1 }),
+        &[ResponseFormat::Json, ResponseFormat::Custom("text/csv")],
+    )
+    .unwrap();
+    assert_eq!(response.headers["content-type"], "text/csv");
+    assert_eq!(response.body, b"id\n1\n");
+    assert!(negotiate(&event, json!({ "id": 1 })).is_ok());
+}
+
+// ============================================================================
+// 类型化响应头测试
+// ============================================================================
+
+#[test]
+fn test_try_header() {
+    let response = text("a").try_header("X-Trace", "abc").unwrap();
+    assert_eq!(response.headers["x-trace"], "abc");
+
+    let error = text("a")
+        .try_header("X-Trace", "a\r\nSet-Cookie: x=1")
+        .unwrap_err();
+    assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(error.to_string().contains("X-Trace"));
+    assert!(text("a").try_header("bad name", "v").is_err());
+}
+
+#[test]
+fn test_invalid_headers_are_discarded() {
+    let response = text("a")
+        .header("X-Ok", "1")
+        .header("X-Bad", "line\nbreak")
+        .append_header("bad name", "v")
+        .content_type("text/\u{7f}");
+    assert_eq!(response.headers["x-ok"], "1");
+    assert!(!response.headers.contains_key("x-bad"));
+    assert_eq!(
+        response.headers["content-type"],
+        "text/plain; charset=utf-8"
+    );
+}
+
+#[test]
+fn test_append_header_keeps_values() {
+    let response = text("a")
+        .header("X-Feature", "a")
+        .append_header("X-Feature", "b")
+        .link("/users?page=3", "next")
+        .link("/users?page=1", "prev");
+    let features: Vec<_> = response.headers.get_all("x-feature").iter().collect();
+    assert_eq!(features, ["a", "b"]);
+    let links: Vec<_> = response.headers.get_all("link").iter().collect();
+    assert_eq!(
+        links,
+        [
+            "</users?page=3>; rel=\"next\"",
+            "</users?page=1>; rel=\"prev\""
+        ]
+    );
+}
+
+#[test]
+fn test_typed_header_setters() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let response = no_content()
+        .location("/users/1")
+        .content_language("zh-CN")
+        .retry_after(Duration::from_millis(1500));
+    assert_eq!(response.headers["location"], "/users/1");
+    assert_eq!(response.headers["content-language"], "zh-CN");
+    assert_eq!(response.headers["retry-after"], "2");
+
+    let response = no_content().retry_after_date(UNIX_EPOCH + Duration::from_secs(784_111_777));
+    assert_eq!(
+        response.headers["retry-after"],
+        "Sun, 06 Nov 1994 08:49:37 GMT"
+    );
+}
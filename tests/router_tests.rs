@@ -0,0 +1,893 @@
+//! 路由生成测试 - 使用 `tests/fixtures/routes` 测试 `generate_routes!` 与中间件顺序
+//!
+//! Router generation tests against the `tests/fixtures/routes` tree.
+
+use astrea::axum::{
+    Router,
+    body::Body,
+    extract::Request,
+    http::{HeaderMap, HeaderValue},
+    middleware::{Next, from_fn},
+};
+use astrea::prelude::*;
+use astrea::tower::ServiceExt;
+
+mod routes {
+    astrea::generate_routes!("tests/fixtures/routes");
+}
+
+/// Append `name` to the `x-chain` request header (used by fixture middleware)
+pub fn tag<S: Clone + Send + Sync + 'static>(router: Router<S>, name: &'static str) -> Router<S> {
+    router.layer(from_fn(move |mut req: Request, next: Next| async move {
+        req.headers_mut()
+            .append("x-chain", HeaderValue::from_static(name));
+        next.run(req).await
+    }))
+}
+
+/// Join the `x-chain` header values seen by the handler (used by fixture routes)
+pub fn chain_of(event: &Event) -> String {
+    event
+        .headers()
+        .get_all("x-chain")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+async fn get_chain(path: &str) -> String {
+    let response = routes::create_router::<()>()
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+fn chain(names: &[&str]) -> Option<Vec<String>> {
+    Some(names.iter().map(|s| s.to_string()).collect())
+}
+
+// ============================================================================
+// middleware_chain 查询
+// ============================================================================
+
+#[test]
+fn test_middleware_chain_extend() {
+    assert_eq!(routes::middleware_chain("GET", "/"), chain(&["root"]));
+    assert_eq!(
+        routes::middleware_chain("GET", "/api/users"),
+        chain(&["root", "api"])
+    );
+}
+
+#[test]
+fn test_middleware_chain_concrete_path() {
+    assert_eq!(
+        routes::middleware_chain("GET", "/api/users/{id}"),
+        chain(&["root", "api"])
+    );
+    assert_eq!(
+        routes::middleware_chain("get", "/api/users/42"),
+        chain(&["root", "api"])
+    );
+}
+
+#[tokio::test]
+async fn test_middleware_chain_static_before_dynamic() {
+    // `/api/{section}/{check}`（api 作用域）排在 `/api/public/{check}`（public 作用域）之前，
+    // 但与 Axum 一样，静态段优先
+    assert_eq!(
+        routes::middleware_chain("GET", "/api/public/status"),
+        chain(&["public"])
+    );
+    assert_eq!(get_chain("/api/public/status").await, "public");
+    assert_eq!(
+        routes::middleware_chain("GET", "/api/other/status"),
+        chain(&["root", "api"])
+    );
+    assert_eq!(get_chain("/api/other/status").await, "root,api");
+}
+
+#[test]
+fn test_middleware_chain_priority() {
+    // admin has priority 10, so it moves outside root and api
+    assert_eq!(
+        routes::middleware_chain("GET", "/api/admin/stats"),
+        chain(&["admin", "root", "api"])
+    );
+}
+
+#[test]
+fn test_middleware_chain_override() {
+    assert_eq!(
+        routes::middleware_chain("GET", "/api/public/health"),
+        chain(&["public"])
+    );
+}
+
+#[test]
+fn test_middleware_chain_unknown_route() {
+    assert_eq!(routes::middleware_chain("POST", "/api/users"), None);
+    assert_eq!(routes::middleware_chain("GET", "/missing"), None);
+}
+
+// ============================================================================
+// 实际请求中的中间件执行顺序
+// ============================================================================
+
+#[tokio::test]
+async fn test_layers_run_in_resolved_order() {
+    assert_eq!(get_chain("/").await, "root");
+    assert_eq!(get_chain("/api/users").await, "root,api");
+    assert_eq!(get_chain("/api/users/42").await, "root,api");
+    assert_eq!(get_chain("/api/admin/stats").await, "admin,root,api");
+    assert_eq!(get_chain("/api/public/health").await, "public");
+}
+
+// ============================================================================
+// 请求体大小限制
+// ============================================================================
+
+async fn post(path: &str, body: Vec<u8>) -> (StatusCode, String) {
+    post_request(Request::post(path).body(Body::from(body)).unwrap()).await
+}
+
+async fn post_request(request: Request) -> (StatusCode, String) {
+    let response = routes::create_router::<()>()
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_default_body_limit() {
+    let limit = astrea::event::body::DEFAULT_BODY_LIMIT;
+    assert_eq!(
+        post("/echo", vec![b'a'; limit]).await,
+        (StatusCode::OK, limit.to_string())
+    );
+
+    let (status, body) = post("/echo", vec![b'a'; limit + 1]).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(body.contains("exceeds the limit"));
+}
+
+#[tokio::test]
+async fn test_axum_default_body_limit_layer() {
+    use astrea::axum::extract::DefaultBodyLimit;
+
+    let send = |layer: DefaultBodyLimit, path: &'static str, len: usize| async move {
+        let response = routes::create_router::<()>()
+            .layer(layer)
+            .oneshot(
+                Request::post(path)
+                    .body(Body::from(vec![b'a'; len]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        response.status()
+    };
+    let limit = astrea::event::body::DEFAULT_BODY_LIMIT;
+
+    // 放宽与收紧默认限制 / Raise and lower the default
+    assert_eq!(
+        send(DefaultBodyLimit::max(limit * 2), "/echo", limit + 1).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(DefaultBodyLimit::max(8), "/echo", 9).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+    assert_eq!(
+        send(DefaultBodyLimit::disable(), "/echo", limit + 1).await,
+        StatusCode::OK
+    );
+
+    // 作用域限制优先 / The scope's limit still wins
+    assert_eq!(
+        send(DefaultBodyLimit::max(1024), "/uploads/small", 17).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+    assert_eq!(
+        send(DefaultBodyLimit::max(8), "/uploads/small", 16).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn test_scope_body_limit() {
+    assert_eq!(
+        post("/uploads/small", vec![b'a'; 16]).await,
+        (StatusCode::OK, "16".to_string())
+    );
+    assert_eq!(
+        post("/uploads/small", vec![b'a'; 17]).await.0,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
+
+#[tokio::test]
+async fn test_route_body_limit_overrides_scope() {
+    assert_eq!(
+        post("/uploads/large", vec![b'a'; 1024]).await,
+        (StatusCode::OK, "1024".to_string())
+    );
+    assert_eq!(
+        post("/uploads/large", vec![b'a'; 1025]).await.0,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
+
+#[tokio::test]
+async fn test_content_length_rejected_early() {
+    let request = Request::post("/uploads/small")
+        .header("content-length", "1000000")
+        .body(Body::from("tiny"))
+        .unwrap();
+    assert_eq!(post_request(request).await.0, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_streaming_body() {
+    assert_eq!(
+        post("/uploads/stream", vec![b'a'; 40]).await,
+        (StatusCode::OK, "1:40".to_string())
+    );
+    assert_eq!(
+        post("/uploads/stream", vec![b'a'; 65]).await.0,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
+
+#[test]
+fn test_middleware_chain_without_wrapper() {
+    assert_eq!(
+        routes::middleware_chain("POST", "/uploads/small"),
+        chain(&["root", "uploads"])
+    );
+}
+
+// ============================================================================
+// 客户端地址与连接信息
+// ============================================================================
+
+async fn whoami(peer: &str, proxies: Option<&[&str]>, headers: &[(&str, &str)]) -> String {
+    let proxies = proxies.map(TrustedProxies::new);
+    whoami_behind(peer, proxies, headers).await
+}
+
+async fn whoami_behind(
+    peer: &str,
+    proxies: Option<TrustedProxies>,
+    headers: &[(&str, &str)],
+) -> String {
+    use astrea::axum::extract::connect_info::MockConnectInfo;
+
+    let mut app = routes::create_router::<()>();
+    if let Some(proxies) = proxies {
+        app = app.layer(astrea::axum::Extension(proxies));
+    }
+    let app = app.layer(MockConnectInfo(
+        peer.parse::<std::net::SocketAddr>().unwrap(),
+    ));
+
+    let mut request = Request::get("/whoami").header("host", "internal:3000");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = app
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_client_ip_without_proxies_ignores_headers() {
+    let forwarded = [
+        ("x-forwarded-for", "1.1.1.1"),
+        ("x-forwarded-proto", "https"),
+        ("x-forwarded-host", "example.com"),
+    ];
+    assert_eq!(
+        whoami("10.0.0.1:5000", None, &forwarded).await,
+        "10.0.0.1 http://internal:3000"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_from_trusted_proxy() {
+    let forwarded = [
+        ("x-forwarded-for", "203.0.113.7, 10.0.0.2"),
+        ("x-forwarded-proto", "https"),
+        ("x-forwarded-host", "example.com"),
+    ];
+    assert_eq!(
+        whoami("10.0.0.1:5000", Some(&["10.0.0.0/8"]), &forwarded).await,
+        "203.0.113.7 https://example.com"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_untrusted_peer_cannot_spoof() {
+    let forwarded = [("x-forwarded-for", "1.1.1.1")];
+    assert_eq!(
+        whoami("198.51.100.9:5000", Some(&["10.0.0.0/8"]), &forwarded).await,
+        "198.51.100.9 http://internal:3000"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_stops_at_first_untrusted_hop() {
+    // 客户端伪造的最左侧条目会被忽略
+    let forwarded = [("x-forwarded-for", "6.6.6.6, 203.0.113.7, 10.0.0.2")];
+    assert_eq!(
+        whoami("10.0.0.1:5000", Some(&["10.0.0.0/8"]), &forwarded).await,
+        "203.0.113.7 http://internal:3000"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_forwarded_header() {
+    let forwarded = [(
+        "forwarded",
+        r#"for="[2001:db8::1]:4711";proto=https;host=example.com"#,
+    )];
+    let proxies = TrustedProxies::new(["127.0.0.1"]).header(ForwardedHeader::Forwarded);
+    assert_eq!(
+        whoami_behind("127.0.0.1:5000", Some(proxies), &forwarded).await,
+        "2001:db8::1 https://example.com"
+    );
+}
+
+#[tokio::test]
+async fn test_client_ip_ignores_spoofed_forwarded_header() {
+    // 代理只追加 X-Forwarded-For，客户端自带的 Forwarded 被原样透传
+    let headers = [
+        ("forwarded", "for=1.2.3.4;host=evil.example;proto=https"),
+        ("x-forwarded-for", "203.0.113.7"),
+    ];
+    assert_eq!(
+        whoami("10.0.0.1:5000", Some(&["10.0.0.0/8"]), &headers).await,
+        "203.0.113.7 http://internal:3000"
+    );
+
+    // 反过来，配置为 Forwarded 时忽略 X-Forwarded-*
+    let headers = [
+        ("forwarded", "for=203.0.113.7"),
+        ("x-forwarded-for", "1.2.3.4"),
+        ("x-forwarded-host", "evil.example"),
+        ("x-forwarded-proto", "https"),
+    ];
+    let proxies = TrustedProxies::new(["10.0.0.0/8"]).header(ForwardedHeader::Forwarded);
+    assert_eq!(
+        whoami_behind("10.0.0.1:5000", Some(proxies), &headers).await,
+        "203.0.113.7 http://internal:3000"
+    );
+}
+
+// ============================================================================
+// 请求 ID
+// ============================================================================
+
+async fn send(request: Request) -> (Option<String>, String) {
+    let response = routes::create_router::<()>()
+        .oneshot(request)
+        .await
+        .unwrap();
+    let header = response
+        .headers()
+        .get("x-request-id")
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (header, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_request_id_taken_from_header() {
+    let request = Request::get("/trace")
+        .header("x-request-id", "req-123")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        send(request).await,
+        (Some("req-123".to_string()), "req-123".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_request_id_generated() {
+    let request = Request::get("/trace").body(Body::empty()).unwrap();
+    let (header, body) = send(request).await;
+    assert_eq!(header.as_deref(), Some(body.as_str()));
+    assert_eq!(body.len(), 36);
+
+    let request = Request::get("/trace").body(Body::empty()).unwrap();
+    assert_ne!(send(request).await.1, body);
+}
+
+#[tokio::test]
+async fn test_request_id_invalid_header_replaced() {
+    let request = Request::get("/trace")
+        .header("x-request-id", "has spaces in it")
+        .body(Body::empty())
+        .unwrap();
+    let (header, body) = send(request).await;
+    assert_ne!(body, "has spaces in it");
+    assert_eq!(header.as_deref(), Some(body.as_str()));
+}
+
+#[tokio::test]
+async fn test_request_id_in_error_body() {
+    let request = Request::post("/uploads/small")
+        .header("x-request-id", "req-413")
+        .body(Body::from(vec![b'a'; 17]))
+        .unwrap();
+    let (header, body) = send(request).await;
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(header.as_deref(), Some("req-413"));
+    assert_eq!(body["status"], 413);
+    assert_eq!(body["request_id"], "req-413");
+}
+
+// ============================================================================
+// 路由模式
+// ============================================================================
+
+#[tokio::test]
+async fn test_route_pattern_and_source_file() {
+    let request = Request::get("/api/posts/hello-world")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        send(request).await.1,
+        "/api/posts/{slug} tests/fixtures/routes/api/posts/[slug].get.rs"
+    );
+}
+
+// ============================================================================
+// 条件请求
+// ============================================================================
+
+async fn conditional(request: Request) -> (StatusCode, Option<String>, String) {
+    let response = routes::create_router::<()>()
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let etag = response
+        .headers()
+        .get("etag")
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, etag, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_if_none_match_returns_304() {
+    let (status, etag, body) = conditional(Request::get("/doc").body(Body::empty()).unwrap()).await;
+    assert_eq!((status, body.as_str()), (StatusCode::OK, "hello"));
+    let etag = etag.unwrap();
+
+    let request = Request::get("/doc")
+        .header("if-none-match", format!("\"other\", {etag}"))
+        .body(Body::empty())
+        .unwrap();
+    let (status, same, body) = conditional(request).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(same.as_deref(), Some(etag.as_str()));
+    assert!(body.is_empty());
+
+    let request = Request::get("/doc")
+        .header("if-none-match", "\"stale\"")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(conditional(request).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_if_modified_since_returns_304() {
+    let request = Request::get("/doc")
+        .header("if-modified-since", "Sun, 09 Sep 2001 01:46:40 GMT")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(conditional(request).await.0, StatusCode::NOT_MODIFIED);
+
+    let request = Request::get("/doc")
+        .header("if-modified-since", "Sat, 08 Sep 2001 00:00:00 GMT")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(conditional(request).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_if_match_returns_412() {
+    let request = Request::put("/doc")
+        .header("if-match", "\"v0\"")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        conditional(request).await.0,
+        StatusCode::PRECONDITION_FAILED
+    );
+
+    let request = Request::put("/doc")
+        .header("if-match", "\"v1\"")
+        .body(Body::empty())
+        .unwrap();
+    let (status, etag, _) = conditional(request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"v2\""));
+
+    let request = Request::put("/doc").body(Body::empty()).unwrap();
+    assert_eq!(conditional(request).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_route_if_match_hook_returns_412() {
+    let request = Request::patch("/doc")
+        .header("if-match", "\"v0\"")
+        .body(Body::empty())
+        .unwrap();
+    let (status, etag, _) = conditional(request).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(etag, None);
+
+    let request = Request::patch("/doc")
+        .header("if-match", "\"other\", \"v1\"")
+        .body(Body::empty())
+        .unwrap();
+    let (status, etag, body) = conditional(request).await;
+    assert_eq!((status, body.as_str()), (StatusCode::OK, "patched"));
+    assert_eq!(etag.as_deref(), Some("\"v2\""));
+
+    let request = Request::patch("/doc")
+        .header("if-match", "W/\"v1\"")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        conditional(request).await.0,
+        StatusCode::PRECONDITION_FAILED
+    );
+
+    let request = Request::patch("/doc").body(Body::empty()).unwrap();
+    assert_eq!(conditional(request).await.0, StatusCode::OK);
+}
+
+// ============================================================================
+// 缓存策略
+// ============================================================================
+
+async fn cache_header(path: &str) -> (StatusCode, Option<String>) {
+    let response = routes::create_router::<()>()
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let cache = response
+        .headers()
+        .get("cache-control")
+        .map(|v| v.to_str().unwrap().to_string());
+    (response.status(), cache)
+}
+
+#[tokio::test]
+async fn test_scope_cache_policy() {
+    assert_eq!(
+        cache_header("/cached/page").await,
+        (StatusCode::OK, Some("public, max-age=300".to_string()))
+    );
+    assert_eq!(cache_header("/").await.1, None);
+}
+
+#[tokio::test]
+async fn test_route_cache_policy_overrides_scope() {
+    assert_eq!(
+        cache_header("/cached/feed").await.1.as_deref(),
+        Some("public, max-age=60, stale-while-revalidate=30")
+    );
+}
+
+#[tokio::test]
+async fn test_response_cache_control_wins() {
+    assert_eq!(
+        cache_header("/cached/page?mode=own").await.1.as_deref(),
+        Some("no-store")
+    );
+}
+
+#[tokio::test]
+async fn test_cache_policy_skips_errors() {
+    assert_eq!(
+        cache_header("/cached/page?mode=error").await,
+        (StatusCode::NOT_FOUND, None)
+    );
+}
+
+// ============================================================================
+// 文件下载与 Range
+// ============================================================================
+
+async fn download(headers: &[(&str, &str)]) -> (StatusCode, HeaderMap, String) {
+    let mut request = Request::get("/download");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = routes::create_router::<()>()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_file_download() {
+    let (status, headers, body) = download(&[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "0123456789abcdefghij");
+    assert_eq!(headers["content-type"], "text/plain");
+    assert_eq!(headers["content-length"], "20");
+    assert_eq!(headers["accept-ranges"], "bytes");
+    assert!(headers["etag"].to_str().unwrap().starts_with('"'));
+    assert!(headers.contains_key("last-modified"));
+    assert_eq!(
+        headers["content-disposition"],
+        "attachment; filename=\"__ 2024.txt\"; filename*=UTF-8''%E6%8A%A5%E5%91%8A%202024.txt"
+    );
+}
+
+#[tokio::test]
+async fn test_file_single_range() {
+    let (status, headers, body) = download(&[("range", "bytes=2-5")]).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(headers["content-range"], "bytes 2-5/20");
+    assert_eq!(headers["content-length"], "4");
+    assert_eq!(body, "2345");
+
+    let (_, headers, body) = download(&[("range", "bytes=-3")]).await;
+    assert_eq!(headers["content-range"], "bytes 17-19/20");
+    assert_eq!(body, "hij");
+}
+
+#[tokio::test]
+async fn test_file_multi_range() {
+    let (status, headers, body) = download(&[("range", "bytes=0-1, 10-")]).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    let content_type = headers["content-type"].to_str().unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/byteranges; boundary=")
+        .unwrap();
+    assert_eq!(
+        body,
+        format!(
+            "--{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 0-1/20\r\n\r\n01\r\n\
+             --{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 10-19/20\r\n\r\nabcdefghij\r\n\
+             --{boundary}--\r\n"
+        )
+    );
+    assert_eq!(headers["content-length"], body.len().to_string().as_str());
+}
+
+#[tokio::test]
+async fn test_file_unsatisfiable_range() {
+    let (status, headers, body) = download(&[("range", "bytes=20-30")]).await;
+    assert_eq!(status, StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(headers["content-range"], "bytes */20");
+    assert!(body.is_empty());
+
+    // Malformed ranges are ignored
+    assert_eq!(download(&[("range", "bytes=5-2")]).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_file_if_range() {
+    let (_, headers, _) = download(&[]).await;
+    let modified = headers["last-modified"].to_str().unwrap().to_string();
+
+    let (status, _, body) =
+        download(&[("range", "bytes=0-0"), ("if-range", modified.as_str())]).await;
+    assert_eq!((status, body.as_str()), (StatusCode::PARTIAL_CONTENT, "0"));
+
+    // Resume with the file's ETag
+    let etag = headers["etag"].to_str().unwrap().to_string();
+    let (status, headers, body) =
+        download(&[("range", "bytes=10-"), ("if-range", etag.as_str())]).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(headers["content-range"], "bytes 10-19/20");
+    assert_eq!(body, "abcdefghij");
+
+    // Weak or stale ETags never satisfy If-Range, so the whole file is sent
+    let weak = format!("W/{etag}");
+    for validator in [weak.as_str(), "\"stale\""] {
+        let (status, _, body) = download(&[("range", "bytes=0-0"), ("if-range", validator)]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.len(), 20);
+    }
+}
+
+#[tokio::test]
+async fn test_missing_file_is_404() {
+    let response = routes::create_router::<()>()
+        .oneshot(
+            Request::get("/download?name=missing.txt")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_attachment_streams_reader() {
+    let response = routes::create_router::<()>()
+        .oneshot(Request::get("/export").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["content-type"], "text/csv");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"users.csv\""
+    );
+    assert!(!response.headers().contains_key("accept-ranges"));
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"id,name\n1,alice\n");
+}
+
+// ============================================================================
+// 内容协商
+// ============================================================================
+
+async fn negotiated(accept: Option<&str>) -> (StatusCode, HeaderMap, Vec<u8>) {
+    let mut request = Request::get("/negotiated");
+    if let Some(accept) = accept {
+        request = request.header("accept", accept);
+    }
+    let response = routes::create_router::<()>()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_negotiate_defaults_to_json() {
+    for accept in [None, Some("*/*"), Some("text/html, application/*;q=0.8")] {
+        let (status, headers, body) = negotiated(accept).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(headers["vary"], "Accept");
+        assert_eq!(body, br#"{"name":"alice"}"#);
+    }
+}
+
+#[tokio::test]
+async fn test_pretty_query_indents_json_in_debug_builds() {
+    let response = routes::create_router::<()>()
+        .oneshot(
+            Request::get("/negotiated?pretty=1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let expected: &[u8] = if cfg!(debug_assertions) {
+        b"{\n  \"name\": \"alice\"\n}"
+    } else {
+        br#"{"name":"alice"}"#
+    };
+    assert_eq!(&body[..], expected);
+}
+
+#[tokio::test]
+async fn test_negotiate_not_acceptable() {
+    let (status, _, body) = negotiated(Some("text/html, application/json;q=0")).await;
+    assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+    assert!(
+        String::from_utf8(body)
+            .unwrap()
+            .contains("application/json")
+    );
+}
+
+#[cfg(feature = "msgpack")]
+#[tokio::test]
+async fn test_negotiate_msgpack() {
+    let (status, headers, body) =
+        negotiated(Some("application/x-msgpack, application/json;q=0.5")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "application/msgpack");
+    assert_eq!(body, b"\x81\xa4name\xa5alice");
+}
+
+#[cfg(feature = "cbor")]
+#[tokio::test]
+async fn test_negotiate_cbor() {
+    let (_, headers, body) = negotiated(Some("application/cbor")).await;
+    assert_eq!(headers["content-type"], "application/cbor");
+    assert_eq!(body, b"\xa1\x64name\x65alice");
+}
+
+// ============================================================================
+// 流式 JSON
+// ============================================================================
+
+async fn list(query: &str) -> (HeaderMap, std::result::Result<String, astrea::axum::Error>) {
+    let response = routes::create_router::<()>()
+        .oneshot(
+            Request::get(format!("/list?{query}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let headers = response.headers().clone();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map(|body| String::from_utf8(body.to_vec()).unwrap());
+    (headers, body)
+}
+
+#[tokio::test]
+async fn test_json_stream_array() {
+    let (headers, body) = list("").await;
+    assert_eq!(headers["content-type"], "application/json");
+    assert!(!headers.contains_key("content-length"));
+    assert_eq!(body.unwrap(), r#"[{"id":0},{"id":1},{"id":2}]"#);
+
+    assert_eq!(list("count=0").await.1.unwrap(), "[]");
+}
+
+#[tokio::test]
+async fn test_ndjson_stream() {
+    let (headers, body) = list("format=ndjson").await;
+    assert_eq!(headers["content-type"], "application/x-ndjson");
+    assert_eq!(body.unwrap(), "{\"id\":0}\n{\"id\":1}\n{\"id\":2}\n");
+
+    assert_eq!(list("format=ndjson&count=0").await.1.unwrap(), "");
+}
+
+#[tokio::test]
+async fn test_json_stream_error_aborts_body() {
+    let (headers, body) = list("format=fail&count=5").await;
+    assert_eq!(headers["content-type"], "application/json");
+    assert!(body.unwrap_err().to_string().contains("database went away"));
+
+    // Errors after the last item are never reached
+    assert_eq!(
+        list("format=fail&count=2").await.1.unwrap(),
+        r#"[{"id":0},{"id":1}]"#
+    );
+}
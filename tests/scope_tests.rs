@@ -0,0 +1,113 @@
+//! 作用域中间件实例测试 - 使用 `tests/fixtures/scope_routes` 测试每个作用域只实例化一次
+//!
+//! Scope middleware instance tests against the `tests/fixtures/scope_routes` tree.
+
+use astrea::axum::{
+    Router,
+    body::Body,
+    extract::Request,
+    http::HeaderValue,
+    middleware::{Next, from_fn},
+};
+use astrea::prelude::*;
+use astrea::tower::ServiceExt;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+mod routes {
+    astrea::generate_routes!("tests/fixtures/scope_routes");
+}
+
+/// Instances created per scope name
+static INSTANCES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// `middleware()` calls per scope name
+static LOADS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// Record a `middleware()` call (used by fixture middleware)
+pub fn loaded(name: &'static str) {
+    LOADS.lock().unwrap().push(name);
+}
+
+fn counts(names: &[&'static str]) -> HashMap<&'static str, usize> {
+    let mut counts = HashMap::new();
+    for name in names {
+        *counts.entry(*name).or_default() += 1;
+    }
+    counts
+}
+
+/// Wrap `router` in a layer that tags requests with `name#<instance>` (used by fixture middleware)
+pub fn counted<S: Clone + Send + Sync + 'static>(
+    router: Router<S>,
+    name: &'static str,
+) -> Router<S> {
+    let instance = {
+        let mut instances = INSTANCES.lock().unwrap();
+        instances.push(name);
+        instances.iter().filter(|n| **n == name).count()
+    };
+    let tag = HeaderValue::from_str(&format!("{name}#{instance}")).unwrap();
+    router.layer(from_fn(move |mut req: Request, next: Next| {
+        req.headers_mut().append("x-instance", tag.clone());
+        next.run(req)
+    }))
+}
+
+/// Join the `x-instance` header values seen by the handler (used by fixture routes)
+pub fn instances_seen(event: &Event) -> String {
+    event
+        .headers()
+        .get_all("x-instance")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+async fn call(app: &Router, request: Request) -> String {
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+// ============================================================================
+// 作用域实例
+// ============================================================================
+
+#[tokio::test]
+async fn test_each_scope_is_instantiated_once() {
+    let app = routes::create_router::<()>();
+
+    assert_eq!(
+        counts(&INSTANCES.lock().unwrap()),
+        HashMap::from([("root", 1), ("api", 1)])
+    );
+    // The startup listing and the router share one `middleware()` call per scope
+    assert_eq!(
+        counts(&LOADS.lock().unwrap()),
+        HashMap::from([("root", 1), ("api", 1), ("uploads", 1), ("cached", 1)])
+    );
+
+    // Child scopes that only change settings share their parents' layers
+    let get = |path: &str| Request::get(path).body(Body::empty()).unwrap();
+    assert_eq!(call(&app, get("/")).await, "root#1");
+    assert_eq!(call(&app, get("/cached/page")).await, "root#1");
+    assert_eq!(call(&app, get("/api/users")).await, "root#1,api#1");
+    let upload = Request::post("/api/uploads/file")
+        .body(Body::from("ok"))
+        .unwrap();
+    assert_eq!(call(&app, upload).await, "root#1,api#1");
+
+    // Settings still apply per route
+    let upload = Request::post("/api/uploads/file")
+        .body(Body::from(vec![b'a'; 17]))
+        .unwrap();
+    let response = app.clone().oneshot(upload).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let response = app.clone().oneshot(get("/cached/page")).await.unwrap();
+    assert_eq!(response.headers()["cache-control"], "public, max-age=60");
+}
@@ -0,0 +1,251 @@
+//! 静态文件测试 - 使用 `tests/fixtures/public` 测试 `generate_routes!(public = ...)`
+//!
+//! Static file serving tests against the `tests/fixtures/public` tree.
+
+use astrea::axum::{Router, body::Body, extract::Request, http::HeaderMap};
+use astrea::prelude::*;
+use astrea::tower::ServiceExt;
+
+mod routes {
+    astrea::generate_routes!(
+        "tests/fixtures/static_routes",
+        public = "tests/fixtures/public"
+    );
+}
+
+mod embedded {
+    astrea::generate_routes!(
+        "tests/fixtures/static_routes",
+        public = "tests/fixtures/public",
+        embed = true
+    );
+}
+
+fn app() -> Router {
+    routes::create_router::<()>()
+}
+
+async fn send(request: Request) -> (StatusCode, HeaderMap, String) {
+    send_to(app(), request).await
+}
+
+async fn send_to(app: Router, request: Request) -> (StatusCode, HeaderMap, String) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, String::from_utf8_lossy(&body).to_string())
+}
+
+async fn get(path: &str) -> (StatusCode, HeaderMap, String) {
+    send(Request::get(path).body(Body::empty()).unwrap()).await
+}
+
+// ============================================================================
+// 文件服务
+// ============================================================================
+
+/// Set in the child process started by `test_public_dir_independent_of_working_directory`
+const CHILD_ENV: &str = "ASTREA_STATIC_TESTS_CHILD";
+
+#[test]
+fn test_public_dir_independent_of_working_directory() {
+    // Re-run the test below in a child process that starts in another
+    // directory, instead of changing this process's working directory
+    let status = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "serve_from_other_working_directory", "--quiet"])
+        .current_dir(std::env::temp_dir())
+        .env(CHILD_ENV, "1")
+        .env_remove(astrea::static_files::PUBLIC_DIR_ENV)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[tokio::test]
+async fn serve_from_other_working_directory() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    let (status, _, body) = get("/app.css").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "body { color: red; }\n");
+}
+
+#[test]
+fn test_public_dir_path_resolution() {
+    use astrea::static_files::public_dir_path;
+    use std::ffi::OsStr;
+    use std::path::PathBuf;
+
+    let scanned = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/public");
+    let missing = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/no-such-dir");
+
+    // The environment variable wins
+    assert_eq!(
+        public_dir_path(Some(OsStr::new("/srv/public")), "public", scanned),
+        PathBuf::from("/srv/public")
+    );
+    // Then the scanned directory, while it exists
+    assert_eq!(
+        public_dir_path(Some(OsStr::new("")), "public", scanned),
+        PathBuf::from(scanned)
+    );
+    assert_eq!(
+        public_dir_path(None, "public", scanned),
+        PathBuf::from(scanned)
+    );
+    // Deployed elsewhere: relative to the working directory
+    assert_eq!(
+        public_dir_path(None, "public", missing),
+        PathBuf::from("public")
+    );
+}
+
+#[tokio::test]
+async fn test_serves_file_with_mime_type() {
+    let (status, headers, body) = get("/app.css").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "text/css");
+    assert!(headers.contains_key("last-modified"));
+    assert!(headers["etag"].to_str().unwrap().starts_with("W/\""));
+    assert_eq!(body, "body { color: red; }\n");
+}
+
+#[tokio::test]
+async fn test_serves_index_file() {
+    let (status, headers, body) = get("/docs/").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        headers["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+    assert_eq!(body, "<h1>Docs</h1>\n");
+}
+
+#[tokio::test]
+async fn test_route_takes_precedence() {
+    assert_eq!(get("/hello").await.2, "from route");
+}
+
+#[tokio::test]
+async fn test_missing_file_is_404() {
+    assert_eq!(get("/nope.txt").await.0, StatusCode::NOT_FOUND);
+}
+
+// ============================================================================
+// 条件请求、Range 与预压缩
+// ============================================================================
+
+#[tokio::test]
+async fn test_etag_returns_304() {
+    let (_, headers, _) = get("/app.css").await;
+    let etag = headers["etag"].clone();
+    let request = Request::get("/app.css")
+        .header("if-none-match", etag)
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, body) = send(request).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_empty());
+
+    let request = Request::get("/app.css")
+        .header("if-modified-since", headers["last-modified"].clone())
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(request).await.0, StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn test_range_request() {
+    let request = Request::get("/app.css")
+        .header("range", "bytes=0-3")
+        .body(Body::empty())
+        .unwrap();
+    let (status, headers, body) = send(request).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(headers["content-range"], "bytes 0-3/21");
+    assert_eq!(body, "body");
+}
+
+#[tokio::test]
+async fn test_precompressed_variant() {
+    let request = Request::get("/app.css")
+        .header("accept-encoding", "gzip")
+        .body(Body::empty())
+        .unwrap();
+    let (status, headers, body) = send(request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-encoding"], "gzip");
+    assert_eq!(headers["content-type"], "text/css");
+    assert_eq!(body, "gzipped-css");
+}
+
+// ============================================================================
+// 嵌入模式
+// ============================================================================
+
+async fn get_embedded(path: &str) -> (StatusCode, HeaderMap, String) {
+    send_to(
+        embedded::create_router::<()>(),
+        Request::get(path).body(Body::empty()).unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_embedded_file_has_strong_content_etag() {
+    let (status, headers, body) = get_embedded("/app.css").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "text/css");
+    assert_eq!(body, "body { color: red; }\n");
+    assert_eq!(headers["content-length"], "21");
+    let expected = ETag::from_body(b"body { color: red; }\n").to_string();
+    assert_eq!(headers["etag"], expected.as_str());
+    assert_eq!(headers["vary"], "Accept-Encoding");
+    assert!(!headers.contains_key("cache-control"));
+
+    let request = Request::get("/app.css")
+        .header("if-none-match", expected)
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, body) = send_to(embedded::create_router::<()>(), request).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn test_embedded_hashed_file_is_immutable() {
+    let (status, headers, _) = get_embedded("/app.3f9a1c2b.js").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        headers["cache-control"],
+        "public, immutable, max-age=31536000"
+    );
+}
+
+#[tokio::test]
+async fn test_embedded_precompressed_and_index() {
+    let request = Request::get("/app.css")
+        .header("accept-encoding", "br;q=0, gzip")
+        .body(Body::empty())
+        .unwrap();
+    let (_, headers, body) = send_to(embedded::create_router::<()>(), request).await;
+    assert_eq!(headers["content-encoding"], "gzip");
+    assert_eq!(body, "gzipped-css");
+
+    assert_eq!(get_embedded("/docs/").await.2, "<h1>Docs</h1>\n");
+    let (status, headers, _) = get_embedded("/docs").await;
+    assert_eq!(status, StatusCode::TEMPORARY_REDIRECT);
+    assert_eq!(headers["location"], "/docs/");
+}
+
+#[tokio::test]
+async fn test_embedded_route_precedence_and_404() {
+    assert_eq!(get_embedded("/hello").await.2, "from route");
+    assert_eq!(get_embedded("/nope.txt").await.0, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,96 @@
+//! 模板渲染测试 - 使用 `tests/fixtures/templates`
+//!
+//! Template rendering tests against the `tests/fixtures/templates` directory.
+
+#![cfg(feature = "templates")]
+
+use std::sync::Mutex;
+
+use astrea::prelude::*;
+use astrea::response::template::{configure, set_templates_dir};
+
+/// The templates directory is global, so tests take turns
+/// / 模板目录是全局的，因此测试轮流执行
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn fixtures() -> std::sync::MutexGuard<'static, ()> {
+    let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    set_templates_dir("tests/fixtures/templates");
+    guard
+}
+
+fn body(response: &Response) -> &str {
+    std::str::from_utf8(&response.body).unwrap()
+}
+
+// ============================================================================
+// 渲染
+// ============================================================================
+
+#[test]
+fn test_render_with_layout() {
+    let _guard = fixtures();
+    let response = render("users/show.html", json!({ "user": { "name": "<Alice>" } })).unwrap();
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers["content-type"], "text/html; charset=utf-8");
+    assert_eq!(
+        body(&response),
+        "<title>&lt;Alice&gt;</title>\n<main><h1>&lt;Alice&gt;</h1></main>"
+    );
+}
+
+#[test]
+fn test_configure_globals() {
+    let _guard = fixtures();
+    configure(|env| {
+        env.add_template_owned("greeting.txt", "Hello from {{ site }}".to_string())
+            .unwrap();
+        env.add_global("site", "Astrea");
+    });
+    let response = render("greeting.txt", ()).unwrap();
+    assert_eq!(body(&response), "Hello from Astrea");
+}
+
+// ============================================================================
+// 错误
+// ============================================================================
+
+#[test]
+fn test_missing_template_is_internal_error() {
+    let _guard = fixtures();
+    let error = render("users/missing.html", ()).unwrap_err();
+    assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(error.to_string().contains("users/missing.html"));
+}
+
+#[test]
+fn test_syntax_error_names_template_and_line() {
+    let _guard = fixtures();
+    let error = render("broken.html", json!({ "user": { "name": "a" } })).unwrap_err();
+    assert!(matches!(error, RouteError::Internal(_)));
+    assert!(error.to_string().contains("broken.html:3"), "{error}");
+}
+
+// ============================================================================
+// 自动重载
+// ============================================================================
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_debug_builds_reload_changed_templates() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = std::env::temp_dir().join(format!("astrea-templates-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let page = dir.join("page.html");
+    std::fs::write(&page, "v1").unwrap();
+    set_templates_dir(&dir);
+    assert_eq!(body(&render("page.html", ()).unwrap()), "v1");
+
+    std::fs::write(&page, "v2").unwrap();
+    let file = std::fs::File::options().write(true).open(&page).unwrap();
+    file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(body(&render("page.html", ()).unwrap()), "v2");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,260 @@
+//! 测试声明式请求验证：`#[derive(Validate)]`、`get_valid_*` 提取函数与 422 响应
+
+use std::collections::HashMap;
+
+use astrea::axum::http::{HeaderMap, HeaderValue, Method};
+use astrea::axum::response::IntoResponse;
+use astrea::prelude::*;
+use astrea::validate::Constraint;
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+struct Signup {
+    #[validate(length(min = 3, max = 8), regex = "^[a-z0-9_]+$")]
+    user_name: String,
+    #[validate(email)]
+    email: String,
+    #[validate(range(min = 13, max = 120))]
+    age: u32,
+    #[validate(length(max = 2))]
+    #[serde(default)]
+    tags: Vec<String>,
+    #[validate(nested)]
+    address: Option<Address>,
+    #[validate(nested)]
+    #[serde(default)]
+    contacts: Vec<Contact>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct Address {
+    #[validate(length(min = 1))]
+    city: String,
+    #[validate(length(equal = 5))]
+    #[serde(rename = "zipCode")]
+    zip: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct Contact {
+    #[validate(email)]
+    email: String,
+}
+
+fn valid_signup() -> Signup {
+    Signup {
+        user_name: "alice".to_string(),
+        email: "alice@example.com".to_string(),
+        age: 30,
+        tags: vec![],
+        address: None,
+        contacts: vec![],
+    }
+}
+
+fn json_event(body: &'static str) -> Event {
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", HeaderValue::from_static("application/json"));
+    Event::new(
+        Method::POST,
+        "/signup".to_string(),
+        "/signup".parse().unwrap(),
+        headers,
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::from_static(body.as_bytes()),
+    )
+}
+
+fn codes(errors: &ValidationErrors) -> Vec<(&str, &str)> {
+    errors
+        .errors()
+        .iter()
+        .map(|e| (e.path.as_str(), e.code.as_str()))
+        .collect()
+}
+
+// ============================================================================
+// 派生规则测试
+// ============================================================================
+
+#[test]
+fn test_valid_value_passes() {
+    assert!(valid_signup().validate().is_ok());
+}
+
+#[test]
+fn test_rules_collect_every_failure() {
+    let signup = Signup {
+        user_name: "Al".to_string(),
+        email: "not-an-email".to_string(),
+        age: 7,
+        tags: vec!["a".into(), "b".into(), "c".into()],
+        ..valid_signup()
+    };
+    let errors = signup.validate().unwrap_err();
+    assert_eq!(
+        codes(&errors),
+        vec![
+            ("userName", "length"),
+            ("userName", "regex"),
+            ("email", "email"),
+            ("age", "range"),
+            ("tags", "length"),
+        ]
+    );
+    assert_eq!(
+        errors.errors()[3].message,
+        "value must be between 13 and 120"
+    );
+}
+
+#[test]
+fn test_nested_paths() {
+    let signup = Signup {
+        address: Some(Address {
+            city: String::new(),
+            zip: Some("123".to_string()),
+        }),
+        contacts: vec![
+            Contact {
+                email: "ok@example.com".to_string(),
+            },
+            Contact {
+                email: "broken".to_string(),
+            },
+        ],
+        ..valid_signup()
+    };
+    let errors = signup.validate().unwrap_err();
+    assert_eq!(
+        codes(&errors),
+        vec![
+            ("address.city", "length"),
+            ("address.zipCode", "length"),
+            ("contacts[1].email", "email"),
+        ]
+    );
+}
+
+#[test]
+fn test_optional_fields_skip_when_absent() {
+    let address = Address {
+        city: "Paris".to_string(),
+        zip: None,
+    };
+    assert!(address.validate().is_ok());
+}
+
+#[test]
+fn test_constraints() {
+    let constraints = Signup::constraints();
+    assert!(constraints.contains(&(
+        "userName".to_string(),
+        Constraint::Length {
+            min: Some(3),
+            max: Some(8)
+        }
+    )));
+    assert!(constraints.contains(&("email".to_string(), Constraint::Email)));
+    assert!(constraints.contains(&(
+        "address.zipCode".to_string(),
+        Constraint::Length {
+            min: Some(5),
+            max: Some(5)
+        }
+    )));
+    assert!(constraints.contains(&("contacts[].email".to_string(), Constraint::Email)));
+}
+
+// ============================================================================
+// 提取与 422 响应测试
+// ============================================================================
+
+#[test]
+fn test_get_valid_body() {
+    let event = json_event(r#"{"userName":"bob","email":"bob@example.com","age":20}"#);
+    let signup: Signup = get_valid_body(&event).unwrap();
+    assert_eq!(signup.user_name, "bob");
+
+    let event = json_event(r#"{"userName":"bob","email":"bob","age":20}"#);
+    match get_valid_body::<Signup>(&event) {
+        Err(RouteError::ValidationFailed(errors)) => {
+            assert_eq!(codes(&errors), vec![("email", "email")]);
+        }
+        other => panic!("expected ValidationFailed, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_get_valid_query() {
+    #[derive(Deserialize, Validate)]
+    struct Page {
+        #[validate(range(min = 1, max = 100))]
+        per_page: u32,
+    }
+
+    let event = Event::new(
+        Method::GET,
+        "/items".to_string(),
+        "/items?per_page=500".parse().unwrap(),
+        HeaderMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        bytes::Bytes::new(),
+    );
+    let err = get_valid_query::<Page>(&event).err().unwrap();
+    assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_validation_failed_response_body() {
+    let err: RouteError = Signup {
+        age: 200,
+        ..valid_signup()
+    }
+    .validate()
+    .unwrap_err()
+    .into();
+    assert_eq!(err.message(), "age: value must be between 13 and 120");
+
+    let response = err.into_response();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = astrea::axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body["errors"],
+        json!([{
+            "path": "age",
+            "code": "range",
+            "message": "value must be between 13 and 120",
+        }])
+    );
+}
+
+#[test]
+fn test_manual_impl() {
+    struct Passwords {
+        password: String,
+        confirm: String,
+    }
+
+    impl Validate for Passwords {
+        fn validate(&self) -> std::result::Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            if self.password != self.confirm {
+                errors.add("confirm", "mismatch", "must match password");
+            }
+            errors.into_result()
+        }
+    }
+
+    let passwords = Passwords {
+        password: "a".to_string(),
+        confirm: "b".to_string(),
+    };
+    let errors = passwords.validate().unwrap_err();
+    assert_eq!(errors.to_string(), "confirm: must match password");
+}